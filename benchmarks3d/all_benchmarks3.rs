@@ -22,6 +22,7 @@ mod joint_revolute3;
 mod keva3;
 mod many_kinematics3;
 mod many_pyramids3;
+mod many_sensors3;
 mod many_sleep3;
 mod many_static3;
 mod pyramid3;
@@ -60,6 +61,7 @@ pub fn demo_builders() -> Vec<(&'static str, fn(&mut Testbed))> {
         ("Convex polyhedron", convex_polyhedron3::init_world),
         ("Many kinematics", many_kinematics3::init_world),
         ("Many static", many_static3::init_world),
+        ("Many sensors", many_sensors3::init_world),
         ("Many sleep", many_sleep3::init_world),
         ("Heightfield", heightfield3::init_world),
         ("Stacks", stacks3::init_world),
@@ -0,0 +1,71 @@
+use rapier_testbed3d::Testbed;
+use rapier3d::prelude::*;
+
+pub fn init_world(testbed: &mut Testbed) {
+    /*
+     * World
+     */
+    let mut bodies = RigidBodySet::new();
+    let mut colliders = ColliderSet::new();
+    let impulse_joints = ImpulseJointSet::new();
+    let multibody_joints = MultibodyJointSet::new();
+
+    /*
+     * Ground
+     */
+    let ground_size = 100.0;
+    let ground_height = 0.1;
+
+    let rigid_body = RigidBodyBuilder::fixed().translation(vector![0.0, -ground_height, 0.0]);
+    let handle = bodies.insert(rigid_body);
+    let collider = ColliderBuilder::cuboid(ground_size, ground_height, ground_size);
+    colliders.insert_with_parent(collider, handle, &mut bodies);
+
+    /*
+     * Create the sensors. They overlap each other, and the falling balls
+     * have to go through them before hitting the ground.
+     */
+    let num = 100;
+    let rad = 1.0;
+    let shift = rad * 1.5;
+    let center = shift * (num as f32) / 2.0;
+
+    for i in 0..num {
+        for k in 0..num {
+            let x = i as f32 * shift - center;
+            let z = k as f32 * shift - center;
+
+            let rigid_body = RigidBodyBuilder::fixed().translation(vector![x, 5.0, z]);
+            let handle = bodies.insert(rigid_body);
+            let collider = ColliderBuilder::ball(rad)
+                .sensor(true)
+                .active_events(ActiveEvents::COLLISION_EVENTS);
+            colliders.insert_with_parent(collider, handle, &mut bodies);
+        }
+    }
+
+    /*
+     * Create the falling balls.
+     */
+    let num = 30;
+    let shift = rad * 2.0 + 1.0;
+    let center = shift * (num as f32) / 2.0;
+
+    for i in 0..num {
+        for k in 0..num {
+            let x = i as f32 * shift - center;
+            let z = k as f32 * shift - center;
+
+            let rigid_body = RigidBodyBuilder::dynamic().translation(vector![x, 10.0, z]);
+            let handle = bodies.insert(rigid_body);
+            let collider = ColliderBuilder::ball(rad * 0.5);
+            colliders.insert_with_parent(collider, handle, &mut bodies);
+        }
+    }
+
+    /*
+     * Set up the testbed.
+     */
+    testbed.set_world(bodies, colliders, impulse_joints, multibody_joints);
+    testbed.look_at(point![100.0, 100.0, 100.0], Point::origin());
+}
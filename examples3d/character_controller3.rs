@@ -192,6 +192,7 @@ pub fn init_world(testbed: &mut Testbed) {
         max_slope_climb_angle: impossible_slope_angle - 0.02,
         min_slope_slide_angle: impossible_slope_angle - 0.02,
         slide: true,
+        inherit_platform_velocity: true,
         ..Default::default()
     };
     let mut pid = PidController::default();
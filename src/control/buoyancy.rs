@@ -0,0 +1,342 @@
+use crate::dynamics::{RigidBodyHandle, RigidBodySet};
+use crate::geometry::{Collider, ColliderSet, Shape, TypedShape};
+use crate::math::{Isometry, Point, Real, Vector};
+use na::Unit;
+
+/// The part of a collider located below the surface of a fluid.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SubmergedVolume {
+    /// The submerged volume (or area, in 2D).
+    pub volume: Real,
+    /// The world-space centroid of the submerged part of the collider.
+    pub centroid: Point<Real>,
+}
+
+/// A body of fluid, bounded by a planar surface, applying buoyancy and drag to the
+/// rigid-bodies immersed into it.
+///
+/// The submerged volume of each collider is approximated by clipping a polyhedral (or polygonal,
+/// in 2D) approximation of its shape against the fluid surface. Only closed shapes are
+/// supported: balls, cuboids, capsules, cylinders, cones, convex polyhedra, triangles (in 2D),
+/// their rounded versions (ignoring the rounding), and compound shapes made of these.
+/// Other shapes are considered as not submerged.
+///
+/// # Example
+/// ```
+/// # use rapier3d::prelude::*;
+/// # use rapier3d::control::Buoyancy;
+/// # let mut bodies = RigidBodySet::new();
+/// # let mut colliders = ColliderSet::new();
+/// # let params = IntegrationParameters::default();
+/// # let gravity = vector![0.0, -9.81, 0.0];
+/// # let boat = bodies.insert(RigidBodyBuilder::dynamic());
+/// # colliders.insert_with_parent(ColliderBuilder::cuboid(2.0, 0.5, 1.0), boat, &mut bodies);
+/// // Water with its surface at y = 0.
+/// let water = Buoyancy::new(Point::origin(), Vector::y_axis(), 1000.0);
+///
+/// // Before each physics step:
+/// water.apply(params.dt, &gravity, &mut bodies, &colliders, boat);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Buoyancy {
+    /// A point on the surface of the fluid.
+    pub surface_point: Point<Real>,
+    /// The normal of the fluid surface, pointing outside of the fluid.
+    pub surface_normal: Unit<Vector<Real>>,
+    /// The density of the fluid (e.g. 1000 for water, in kg/m³).
+    pub fluid_density: Real,
+    /// The linear drag coefficient, per unit of submerged volume and of fluid density.
+    ///
+    /// The drag force is opposed to the velocity of the submerged centroid.
+    pub linear_drag: Real,
+    /// The angular drag coefficient, per unit of submerged volume and of fluid density.
+    pub angular_drag: Real,
+    /// The number of subdivisions used to approximate the curved parts of shapes.
+    pub subdivisions: u32,
+}
+
+impl Buoyancy {
+    /// A fluid with the given surface and density, and default drag coefficients.
+    pub fn new(
+        surface_point: Point<Real>,
+        surface_normal: Unit<Vector<Real>>,
+        fluid_density: Real,
+    ) -> Self {
+        Self {
+            surface_point,
+            surface_normal,
+            fluid_density,
+            linear_drag: 0.5,
+            angular_drag: 0.5,
+            subdivisions: 10,
+        }
+    }
+
+    /// Computes the part of a collider located below the fluid surface.
+    ///
+    /// Returns `None` if the collider isn’t submerged at all, or if its shape isn’t supported.
+    pub fn submerged_volume(&self, collider: &Collider) -> Option<SubmergedVolume> {
+        let pos = collider.position();
+        // Use a reference point on the surface close to the collider to limit
+        // floating-point cancellations.
+        let dist = self.signed_distance(&Point::from(pos.translation.vector));
+        let reference = Point::from(pos.translation.vector) - *self.surface_normal * dist;
+
+        let mut volume = 0.0;
+        let mut weighted_centroid = Vector::zeros();
+        self.accumulate_submerged_volume(
+            collider.shape(),
+            pos,
+            &reference,
+            &mut volume,
+            &mut weighted_centroid,
+        );
+
+        // The sign of the volume depends on the winding of the shape approximation.
+        if volume.abs() <= Real::EPSILON {
+            return None;
+        }
+
+        Some(SubmergedVolume {
+            volume: volume.abs(),
+            centroid: Point::from(weighted_centroid / volume),
+        })
+    }
+
+    /// Applies buoyancy and drag to the rigid-body `handle`, for a timestep of length `dt`.
+    ///
+    /// The buoyancy force is opposed to `gravity` and proportional to the submerged volume of
+    /// each collider attached to the rigid-body. It is applied at the centroid of the submerged
+    /// part with [`RigidBody::apply_impulse_at_point`](crate::dynamics::RigidBody::apply_impulse_at_point),
+    /// together with the linear and angular drag. This must be called before each timestep.
+    pub fn apply(
+        &self,
+        dt: Real,
+        gravity: &Vector<Real>,
+        bodies: &mut RigidBodySet,
+        colliders: &ColliderSet,
+        handle: RigidBodyHandle,
+    ) {
+        let Some(rb) = bodies.get_mut(handle) else {
+            return;
+        };
+
+        let submerged: Vec<_> = rb
+            .colliders()
+            .iter()
+            .filter_map(|co| self.submerged_volume(colliders.get(*co)?))
+            .collect();
+
+        for submerged in submerged {
+            let mass = self.fluid_density * submerged.volume;
+            let buoyancy = -gravity * mass;
+            let point_vel = rb.velocity_at_point(&submerged.centroid);
+            let drag = -point_vel * (mass * self.linear_drag);
+
+            rb.apply_impulse_at_point((buoyancy + drag) * dt, submerged.centroid, true);
+            rb.apply_torque_impulse(-rb.angvel() * (mass * self.angular_drag * dt), true);
+        }
+    }
+
+    fn signed_distance(&self, point: &Point<Real>) -> Real {
+        self.surface_normal.dot(&(point - self.surface_point))
+    }
+
+    fn accumulate_submerged_volume(
+        &self,
+        shape: &dyn Shape,
+        pos: &Isometry<Real>,
+        reference: &Point<Real>,
+        volume: &mut Real,
+        weighted_centroid: &mut Vector<Real>,
+    ) {
+        if let TypedShape::Compound(compound) = shape.as_typed_shape() {
+            for (sub_pos, sub_shape) in compound.shapes() {
+                self.accumulate_submerged_volume(
+                    &**sub_shape,
+                    &(pos * sub_pos),
+                    reference,
+                    volume,
+                    weighted_centroid,
+                );
+            }
+            return;
+        }
+
+        #[cfg(feature = "dim2")]
+        {
+            let Some(vertices) = self.polygon(shape) else {
+                return;
+            };
+
+            for (i, a) in vertices.iter().enumerate() {
+                let a = pos * a;
+                let b = pos * vertices[(i + 1) % vertices.len()];
+                if let Some([a, b]) = self.clip_segment(a, b) {
+                    // The part of the boundary lying on the surface is ignored since its
+                    // triangles with the reference point are degenerate.
+                    let area = (a - reference).perp(&(b - reference)) / 2.0;
+                    *volume += area;
+                    *weighted_centroid += (reference.coords + a.coords + b.coords) * (area / 3.0);
+                }
+            }
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            let Some((vertices, indices)) = self.trimesh(shape) else {
+                return;
+            };
+
+            let mut clipped = Vec::new();
+            for idx in indices {
+                let triangle = idx.map(|i| pos * vertices[i as usize]);
+                self.clip_triangle(&triangle, &mut clipped);
+
+                // The part of the boundary lying on the surface is ignored since its
+                // tetrahedra with the reference point are degenerate.
+                for k in 1..clipped.len().saturating_sub(1) {
+                    let (a, b, c) = (clipped[0], clipped[k], clipped[k + 1]);
+                    let tet_volume =
+                        (a - reference).dot(&(b - reference).cross(&(c - reference))) / 6.0;
+                    *volume += tet_volume;
+                    *weighted_centroid +=
+                        (reference.coords + a.coords + b.coords + c.coords) * (tet_volume / 4.0);
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "dim2")]
+    fn polygon(&self, shape: &dyn Shape) -> Option<Vec<Point<Real>>> {
+        match shape.as_typed_shape() {
+            TypedShape::Ball(s) => Some(s.to_polyline(self.subdivisions)),
+            TypedShape::Cuboid(s) => Some(s.to_polyline()),
+            TypedShape::Capsule(s) => Some(s.to_polyline(self.subdivisions)),
+            TypedShape::Triangle(s) => Some(vec![s.a, s.b, s.c]),
+            TypedShape::ConvexPolygon(s) => Some(s.points().to_vec()),
+            TypedShape::RoundCuboid(s) => Some(s.inner_shape.to_polyline()),
+            TypedShape::RoundTriangle(s) => {
+                let s = &s.inner_shape;
+                Some(vec![s.a, s.b, s.c])
+            }
+            TypedShape::RoundConvexPolygon(s) => Some(s.inner_shape.points().to_vec()),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "dim3")]
+    fn trimesh(&self, shape: &dyn Shape) -> Option<(Vec<Point<Real>>, Vec<[u32; 3]>)> {
+        let n = self.subdivisions;
+        match shape.as_typed_shape() {
+            TypedShape::Ball(s) => Some(s.to_trimesh(n, n)),
+            TypedShape::Cuboid(s) => Some(s.to_trimesh()),
+            TypedShape::Capsule(s) => Some(s.to_trimesh(n, n)),
+            TypedShape::Cylinder(s) => Some(s.to_trimesh(n)),
+            TypedShape::Cone(s) => Some(s.to_trimesh(n)),
+            TypedShape::ConvexPolyhedron(s) => Some(s.to_trimesh()),
+            TypedShape::RoundCuboid(s) => Some(s.inner_shape.to_trimesh()),
+            TypedShape::RoundCylinder(s) => Some(s.inner_shape.to_trimesh(n)),
+            TypedShape::RoundCone(s) => Some(s.inner_shape.to_trimesh(n)),
+            TypedShape::RoundConvexPolyhedron(s) => Some(s.inner_shape.to_trimesh()),
+            _ => None,
+        }
+    }
+
+    /// The part of the segment `[a, b]` below the fluid surface.
+    #[cfg(feature = "dim2")]
+    fn clip_segment(&self, a: Point<Real>, b: Point<Real>) -> Option<[Point<Real>; 2]> {
+        let (da, db) = (self.signed_distance(&a), self.signed_distance(&b));
+
+        match (da <= 0.0, db <= 0.0) {
+            (true, true) => Some([a, b]),
+            (false, false) => None,
+            (true, false) => Some([a, a + (b - a) * (da / (da - db))]),
+            (false, true) => Some([a + (b - a) * (da / (da - db)), b]),
+        }
+    }
+
+    /// The polygon resulting from the clipping of `triangle` by the fluid surface
+    /// (Sutherland-Hodgman).
+    #[cfg(feature = "dim3")]
+    fn clip_triangle(&self, triangle: &[Point<Real>; 3], out: &mut Vec<Point<Real>>) {
+        out.clear();
+
+        for i in 0..3 {
+            let (a, b) = (triangle[i], triangle[(i + 1) % 3]);
+            let (da, db) = (self.signed_distance(&a), self.signed_distance(&b));
+
+            if da <= 0.0 {
+                out.push(a);
+            }
+
+            if (da <= 0.0) != (db <= 0.0) {
+                out.push(a + (b - a) * (da / (da - db)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Buoyancy;
+    use crate::dynamics::{IntegrationParameters, RigidBodyBuilder};
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Point, Real, Vector};
+    use crate::pipeline::{PhysicsPipeline, PhysicsState};
+
+    #[test]
+    fn half_submerged_cuboid_volume() {
+        let water = Buoyancy::new(Point::origin(), Vector::y_axis(), 1000.0);
+
+        #[cfg(feature = "dim2")]
+        let collider = ColliderBuilder::cuboid(1.0, 1.0).translation(Vector::y() * 0.5);
+        #[cfg(feature = "dim3")]
+        let collider = ColliderBuilder::cuboid(1.0, 1.0, 1.0).translation(Vector::y() * 0.5);
+        let submerged = water.submerged_volume(&collider.build()).unwrap();
+
+        // A quarter of the cuboid is submerged.
+        #[cfg(feature = "dim2")]
+        assert!((submerged.volume - 1.0).abs() < 1.0e-4);
+        #[cfg(feature = "dim3")]
+        assert!((submerged.volume - 2.0).abs() < 1.0e-4);
+        assert!((submerged.centroid - Point::from(Vector::y() * -0.25)).norm() < 1.0e-4);
+    }
+
+    #[test]
+    fn floating_body_settles_at_equilibrium() {
+        let mut state = PhysicsState::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let params = IntegrationParameters::default();
+        let gravity = Vector::y() * -9.81;
+        let mut water = Buoyancy::new(Point::origin(), Vector::y_axis(), 1000.0);
+        water.linear_drag = 5.0;
+
+        // A body with half the density of water, dropped from above the surface, ends up
+        // floating half-submerged.
+        let body = state
+            .bodies
+            .insert(RigidBodyBuilder::dynamic().translation(Vector::y()));
+        #[cfg(feature = "dim2")]
+        let collider = ColliderBuilder::cuboid(0.5, 0.5);
+        #[cfg(feature = "dim3")]
+        let collider = ColliderBuilder::cuboid(0.5, 0.5, 0.5);
+        state
+            .colliders
+            .insert_with_parent(collider.density(500.0), body, &mut state.bodies);
+
+        for _ in 0..600 {
+            water.apply(
+                params.dt,
+                &gravity,
+                &mut state.bodies,
+                &state.colliders,
+                body,
+            );
+            state.step(&mut pipeline, &gravity, &params, &(), &());
+        }
+
+        let height: Real = state.bodies[body].translation().y;
+        assert!(height.abs() < 0.02, "height: {height}");
+    }
+}
@@ -1,3 +1,4 @@
+use crate::dynamics::{RigidBody, RigidBodyHandle};
 use crate::geometry::{ColliderHandle, ContactManifold, Shape, ShapeCastHit};
 use crate::math::{Isometry, Point, Real, UnitVector, Vector};
 use crate::pipeline::{QueryFilterFlags, QueryPipeline, QueryPipelineMut};
@@ -194,6 +195,10 @@ pub struct KinematicCharacterController {
     pub min_slope_slide_angle: Real,
     /// Should the character be automatically snapped to the ground if the distance between
     /// the ground and its feed are smaller than the specified threshold?
+    ///
+    /// Snapping only happens if the character was grounded before moving, is moving downward,
+    /// and wasn’t asked to move upward (e.g. when jumping). The character isn’t snapped to
+    /// grounds steeper than `max_slope_climb_angle`.
     pub snap_to_ground: Option<CharacterLength>,
     /// Increase this number if your character appears to get stuck when sliding against surfaces.
     ///
@@ -204,6 +209,14 @@ pub struct KinematicCharacterController {
     /// This value should remain fairly small since it can introduce artificial "bumps" when sliding
     /// along a flat surface.
     pub normal_nudge_factor: Real,
+    /// Should the character be carried along by the moving platform it is standing on?
+    ///
+    /// If enabled, the velocity of the rigid-body supporting the character, taken at the
+    /// character’s feet, is integrated over `dt` and added to the desired translation before
+    /// resolving collisions. The inherited motion only depends on the ground detected at the
+    /// beginning of each move, so it stops as soon as the character steps off the platform.
+    /// Disabled by default.
+    pub inherit_platform_velocity: bool,
 }
 
 impl Default for KinematicCharacterController {
@@ -217,6 +230,7 @@ impl Default for KinematicCharacterController {
             min_slope_slide_angle: Real::frac_pi_4(),
             snap_to_ground: Some(CharacterLength::Relative(0.2)),
             normal_nudge_factor: 1.0e-4,
+            inherit_platform_velocity: false,
         }
     }
 }
@@ -230,6 +244,14 @@ pub struct EffectiveCharacterMovement {
     pub grounded: bool,
     /// Is the character sliding down a slope due to slope angle being larger than `min_slope_slide_angle`?
     pub is_sliding_down_slope: bool,
+    /// Was the character pulled down to the ground due to `snap_to_ground`?
+    pub snapped_to_ground: bool,
+    /// The colliders hit by the character during its movement.
+    ///
+    /// Each collider appears only once, with the first collision detected with it. Unlike
+    /// the `events` callback of [`KinematicCharacterController::move_shape`], subsequent hits
+    /// on the same collider while sliding against it are not reported here.
+    pub collisions: Vec<CharacterCollision>,
 }
 
 impl KinematicCharacterController {
@@ -278,6 +300,8 @@ impl KinematicCharacterController {
             translation: Vector::zeros(),
             grounded: false,
             is_sliding_down_slope: false,
+            snapped_to_ground: false,
+            collisions: vec![],
         };
         let dims = self.compute_dims(character_shape);
 
@@ -285,6 +309,19 @@ impl KinematicCharacterController {
         self.check_and_fix_penetrations();
 
         let mut translation_remaining = desired_translation;
+        let mut kinematic_friction_translation = Vector::zeros();
+
+        if self.inherit_platform_velocity {
+            if let Some((platform_translation, normal)) =
+                self.platform_translation(dt, queries, character_shape, character_pos, &dims)
+            {
+                translation_remaining += platform_translation;
+                // The tangential motion of the platform is already inherited, the kinematic
+                // friction must not apply it a second time.
+                kinematic_friction_translation =
+                    platform_translation - normal * platform_translation.dot(&normal);
+            }
+        }
 
         let grounded_at_starting_pos = self.detect_grounded_status_and_apply_friction(
             dt,
@@ -297,7 +334,6 @@ impl KinematicCharacterController {
         );
 
         let mut max_iters = 20;
-        let mut kinematic_friction_translation = Vector::zeros();
         let offset = self.offset.eval(dims.y);
         let mut is_moving = false;
 
@@ -329,13 +365,17 @@ impl KinematicCharacterController {
                 result.translation += allowed_translation;
                 translation_remaining -= allowed_translation;
 
-                events(CharacterCollision {
+                let collision = CharacterCollision {
                     handle,
                     character_pos: Translation::from(result.translation) * character_pos,
                     translation_applied: result.translation,
                     translation_remaining,
                     hit,
-                });
+                };
+                if !result.collisions.iter().any(|c| c.handle == handle) {
+                    result.collisions.push(collision);
+                }
+                events(collision);
 
                 let hit_info = self.compute_hit_info(hit);
 
@@ -401,8 +441,9 @@ impl KinematicCharacterController {
                 None,
             );
         }
-        // If needed, and if we are not already grounded, snap to the ground.
-        if grounded_at_starting_pos {
+        // If needed, and if we are not already grounded, snap to the ground. Don’t snap if the
+        // character is trying to move upward (e.g. jumping).
+        if grounded_at_starting_pos && desired_translation.dot(&self.up) <= 0.0 {
             self.snap_to_ground(
                 queries,
                 character_shape,
@@ -439,9 +480,15 @@ impl KinematicCharacterController {
                         compute_impact_geometry_on_penetration: true,
                     },
                 ) {
+                    // Don’t snap onto walls.
+                    if self.up.angle(&hit.normal1) > self.max_slope_climb_angle {
+                        return None;
+                    }
+
                     // Apply the snap.
                     result.translation -= *self.up * hit.time_of_impact;
                     result.grounded = true;
+                    result.snapped_to_ground = true;
                     return Some((hit_handle, hit));
                 }
             }
@@ -450,6 +497,71 @@ impl KinematicCharacterController {
         None
     }
 
+    /// The translation, during `dt`, of the moving platform supporting the character, and the
+    /// normal of the ground at the character’s feet.
+    fn platform_translation(
+        &self,
+        dt: Real,
+        queries: &QueryPipeline,
+        character_shape: &dyn Shape,
+        character_pos: &Isometry<Real>,
+        dims: &Vector2<Real>,
+    ) -> Option<(Vector<Real>, Vector<Real>)> {
+        let prediction = self.predict_ground(dims.y);
+
+        // TODO: allow custom dispatchers.
+        let dispatcher = DefaultQueryDispatcher;
+
+        let mut manifolds: Vec<ContactManifold> = vec![];
+        let character_aabb = character_shape
+            .compute_aabb(character_pos)
+            .loosened(prediction);
+
+        // The supporting platform is the one with the closest ground contact.
+        let mut closest: Option<(Real, Vector<Real>, Vector<Real>)> = None;
+
+        for (_, collider) in queries.intersect_aabb_conservative(character_aabb) {
+            let Some(platform) = collider
+                .parent
+                .and_then(|p| queries.bodies.get(p.handle))
+                .filter(|rb| !rb.is_fixed())
+            else {
+                continue;
+            };
+
+            manifolds.clear();
+            let pos12 = character_pos.inv_mul(collider.position());
+            let _ = dispatcher.contact_manifolds(
+                &pos12,
+                character_shape,
+                collider.shape(),
+                prediction,
+                &mut manifolds,
+                &mut None,
+            );
+
+            for m in &manifolds {
+                if !self.is_grounded_at_contact_manifold(m, character_pos, dims) {
+                    continue;
+                }
+
+                let normal = -(character_pos * m.local_n1);
+
+                for contact in &m.points {
+                    if contact.dist <= prediction
+                        && closest.is_none_or(|(dist, _, _)| contact.dist < dist)
+                    {
+                        let foot = collider.position() * contact.local_p2;
+                        let translation = platform.velocity_at_point(&foot) * dt;
+                        closest = Some((contact.dist, translation, normal));
+                    }
+                }
+            }
+        }
+
+        closest.map(|(_, translation, normal)| (translation, normal))
+    }
+
     fn predict_ground(&self, up_extends: Real) -> Real {
         self.offset.eval(up_extends) + 0.05
     }
@@ -820,6 +932,8 @@ impl KinematicCharacterController {
     /// impulses to the rigid-bodies surrounding the character shape at the time of the collisions.
     /// Note that the impulse calculation is only approximate as it is not based on a global
     /// constraints resolution scheme.
+    ///
+    /// This is equivalent to [`Self::push_dynamic_bodies`] with an unlimited strength.
     #[profiling::function]
     pub fn solve_character_collision_impulses<'a>(
         &self,
@@ -829,13 +943,52 @@ impl KinematicCharacterController {
         character_mass: Real,
         collisions: impl IntoIterator<Item = &'a CharacterCollision>,
     ) {
+        self.push_dynamic_bodies(
+            dt,
+            queries,
+            character_shape,
+            character_mass,
+            Real::MAX,
+            collisions,
+            |_, _| true,
+        );
+    }
+
+    /// Pushes the dynamic rigid-bodies hit by the character during its movement.
+    ///
+    /// For each of the given `collisions` (as reported by [`Self::move_shape`]), this applies
+    /// impulses to the dynamic rigid-bodies surrounding the character shape at the time of the
+    /// collision, so that they move away from the character at the speed it was trying to move
+    /// into them. The magnitude of each impulse is limited by the `strength` of the character,
+    /// given as a maximum pushing force: bodies much lighter than the character (of mass
+    /// `character_mass`) are pushed at its speed while heavy bodies are only slightly
+    /// accelerated. Note that the impulse calculation is only
+    /// approximate as it is not based on a global constraints resolution scheme.
+    ///
+    /// The pushed rigid-bodies are woken up. Bodies for which `can_push` returns `false` are
+    /// left untouched.
+    #[profiling::function]
+    pub fn push_dynamic_bodies<'a>(
+        &self,
+        dt: Real,
+        queries: &mut QueryPipelineMut,
+        character_shape: &dyn Shape,
+        character_mass: Real,
+        strength: Real,
+        collisions: impl IntoIterator<Item = &'a CharacterCollision>,
+        mut can_push: impl FnMut(RigidBodyHandle, &RigidBody) -> bool,
+    ) {
+        let max_impulse = strength * dt;
+
         for collision in collisions {
             self.solve_single_character_collision_impulse(
                 dt,
                 queries,
                 character_shape,
                 character_mass,
+                max_impulse,
                 collision,
+                &mut can_push,
             );
         }
     }
@@ -851,7 +1004,9 @@ impl KinematicCharacterController {
         queries: &mut QueryPipelineMut,
         character_shape: &dyn Shape,
         character_mass: Real,
+        max_impulse: Real,
         collision: &CharacterCollision,
+        can_push: &mut impl FnMut(RigidBodyHandle, &RigidBody) -> bool,
     ) {
         let extents = character_shape.compute_local_aabb().extents();
         let up_extent = extents.dot(&self.up.abs());
@@ -870,7 +1025,7 @@ impl KinematicCharacterController {
         for (_, collider) in queries.as_ref().intersect_aabb_conservative(character_aabb) {
             if let Some(parent) = collider.parent {
                 if let Some(body) = queries.bodies.get(parent.handle) {
-                    if body.is_dynamic() {
+                    if body.is_dynamic() && can_push(parent.handle, body) {
                         manifolds.clear();
                         let pos12 = collision.character_pos.inv_mul(collider.position());
                         let prev_manifolds_len = manifolds.len();
@@ -907,8 +1062,10 @@ impl KinematicCharacterController {
                     .dot(&manifold.data.normal);
                     let mass_ratio = body_mass * character_mass / (body_mass + character_mass);
 
+                    let impulse = (delta_vel_per_contact.max(0.0) * mass_ratio).min(max_impulse);
+
                     body.apply_impulse_at_point(
-                        manifold.data.normal * delta_vel_per_contact.max(0.0) * mass_ratio,
+                        manifold.data.normal * impulse,
                         contact_point,
                         true,
                     );
@@ -1217,4 +1374,295 @@ mod test {
             translation.z
         );
     }
+
+    #[test]
+    fn character_controller_platform_inheritance() {
+        let mut state = PhysicsState::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let integration_parameters = IntegrationParameters::default();
+        let dt = integration_parameters.dt;
+
+        // A platform moving along the x axis.
+        let platform_handle = state
+            .bodies
+            .insert(RigidBodyBuilder::kinematic_velocity_based().linvel(vector![2.0, 0.0, 0.0]));
+        state.colliders.insert_with_parent(
+            ColliderBuilder::cuboid(2.0, 0.1, 2.0).translation(vector![0.0, -0.1, 0.0]),
+            platform_handle,
+            &mut state.bodies,
+        );
+
+        let character_shape = Ball::new(0.5);
+        let mut carried_pos = Isometry::translation(0.0, 0.51, 0.0);
+        let mut left_behind_pos = Isometry::translation(0.0, 0.51, 1.0);
+        let carried = KinematicCharacterController {
+            inherit_platform_velocity: true,
+            ..Default::default()
+        };
+        let left_behind = KinematicCharacterController::default();
+
+        for _ in 0..60 {
+            state.step(
+                &mut pipeline,
+                &Vector::zeros(),
+                &integration_parameters,
+                &(),
+                &(),
+            );
+            let query_pipeline = state.broad_phase.as_query_pipeline(
+                state.narrow_phase.query_dispatcher(),
+                &state.bodies,
+                &state.colliders,
+                QueryFilter::default(),
+            );
+
+            for (controller, pos) in [
+                (&carried, &mut carried_pos),
+                (&left_behind, &mut left_behind_pos),
+            ] {
+                let movement = controller.move_shape(
+                    dt,
+                    &query_pipeline,
+                    &character_shape,
+                    pos,
+                    Vector::zeros(),
+                    |_| {},
+                );
+                pos.translation.vector += movement.translation;
+            }
+        }
+
+        // The character standing on the platform followed it.
+        let platform_x = state.bodies[platform_handle].translation().x;
+        assert!((carried_pos.translation.x - platform_x).abs() < 0.1);
+        assert_eq!(left_behind_pos.translation.x, 0.0);
+
+        // Once the platform is gone, the character stops moving.
+        state.bodies.remove(
+            platform_handle,
+            &mut state.islands,
+            &mut state.colliders,
+            &mut state.impulse_joints,
+            &mut state.multibody_joints,
+            true,
+        );
+        state.step(
+            &mut pipeline,
+            &Vector::zeros(),
+            &integration_parameters,
+            &(),
+            &(),
+        );
+        let query_pipeline = state.broad_phase.as_query_pipeline(
+            state.narrow_phase.query_dispatcher(),
+            &state.bodies,
+            &state.colliders,
+            QueryFilter::default(),
+        );
+        let movement = carried.move_shape(
+            dt,
+            &query_pipeline,
+            &character_shape,
+            &carried_pos,
+            Vector::zeros(),
+            |_| {},
+        );
+        assert_eq!(movement.translation.x, 0.0);
+    }
+
+    #[test]
+    fn character_controller_snap_down_ledge() {
+        let mut state = PhysicsState::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let integration_parameters = IntegrationParameters::default();
+
+        // A floor with a small ledge going down at x = 1.
+        state
+            .colliders
+            .insert(ColliderBuilder::cuboid(1.0, 0.5, 5.0).translation(vector![0.0, -0.5, 0.0]));
+        state
+            .colliders
+            .insert(ColliderBuilder::cuboid(5.0, 0.5, 5.0).translation(vector![6.0, -0.6, 0.0]));
+        state.step(
+            &mut pipeline,
+            &Vector::zeros(),
+            &integration_parameters,
+            &(),
+            &(),
+        );
+        let query_pipeline = state.broad_phase.as_query_pipeline(
+            state.narrow_phase.query_dispatcher(),
+            &state.bodies,
+            &state.colliders,
+            QueryFilter::default(),
+        );
+
+        let character_shape = Ball::new(0.5);
+        let character_pos = Isometry::translation(1.2, 0.51, 0.0);
+        let controller = KinematicCharacterController::default();
+
+        // Walking off the ledge with a bit of gravity snaps the character down.
+        let movement = controller.move_shape(
+            integration_parameters.dt,
+            &query_pipeline,
+            &character_shape,
+            &character_pos,
+            vector![0.1, -0.01, 0.0],
+            |_| {},
+        );
+        assert!(movement.snapped_to_ground);
+        assert!(movement.grounded);
+        assert!(movement.translation.y < -0.09);
+
+        // Jumping off the ledge doesn’t snap.
+        let movement = controller.move_shape(
+            integration_parameters.dt,
+            &query_pipeline,
+            &character_shape,
+            &character_pos,
+            vector![0.1, 0.01, 0.0],
+            |_| {},
+        );
+        assert!(!movement.snapped_to_ground);
+        assert!(!movement.grounded);
+
+        // Without snapping, the character only moves down by the requested amount.
+        let controller = KinematicCharacterController {
+            snap_to_ground: None,
+            ..Default::default()
+        };
+        let movement = controller.move_shape(
+            integration_parameters.dt,
+            &query_pipeline,
+            &character_shape,
+            &character_pos,
+            vector![0.1, -0.01, 0.0],
+            |_| {},
+        );
+        assert!(!movement.snapped_to_ground);
+        assert!(!movement.grounded);
+    }
+
+    #[test]
+    fn character_controller_push_dynamic_bodies() {
+        let mut state = PhysicsState::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let integration_parameters = IntegrationParameters::default();
+        let dt = integration_parameters.dt;
+
+        // Three boxes in front of three characters: a light one, a heavy one, and a locked one.
+        let boxes: Vec<_> = [(0.0, 1.0), (3.0, 100.0), (6.0, 1.0)]
+            .into_iter()
+            .map(|(z, density)| {
+                let handle = state
+                    .bodies
+                    .insert(RigidBodyBuilder::dynamic().translation(vector![1.0, 0.0, z]));
+                state.colliders.insert_with_parent(
+                    ColliderBuilder::cuboid(0.5, 0.5, 0.5).density(density),
+                    handle,
+                    &mut state.bodies,
+                );
+                handle
+            })
+            .collect();
+        state.step(
+            &mut pipeline,
+            &Vector::zeros(),
+            &integration_parameters,
+            &(),
+            &(),
+        );
+
+        let controller = KinematicCharacterController::default();
+        let character_shape = Ball::new(0.5);
+        let mut collisions = vec![];
+
+        {
+            let query_pipeline = state.broad_phase.as_query_pipeline(
+                state.narrow_phase.query_dispatcher(),
+                &state.bodies,
+                &state.colliders,
+                QueryFilter::default(),
+            );
+            for z in [0.0, 3.0, 6.0] {
+                let _ = controller.move_shape(
+                    dt,
+                    &query_pipeline,
+                    &character_shape,
+                    &Isometry::translation(-0.1, 0.0, z),
+                    vector![0.5, 0.0, 0.0],
+                    |collision| collisions.push(collision),
+                );
+            }
+        }
+        assert_eq!(collisions.len(), 3);
+
+        let mut query_pipeline = state.broad_phase.as_query_pipeline_mut(
+            state.narrow_phase.query_dispatcher(),
+            &mut state.bodies,
+            &mut state.colliders,
+            QueryFilter::default(),
+        );
+        controller.push_dynamic_bodies(
+            dt,
+            &mut query_pipeline,
+            &character_shape,
+            10.0,
+            2000.0,
+            &collisions,
+            |handle, _| handle != boxes[2],
+        );
+
+        let linvels: Vec<_> = boxes.iter().map(|h| state.bodies[*h].linvel().x).collect();
+        assert!(linvels[0] > 0.0);
+        assert!(linvels[1] > 0.0);
+        assert!(linvels[0] > 10.0 * linvels[1]);
+        assert_eq!(linvels[2], 0.0);
+    }
+
+    #[test]
+    fn character_controller_collision_list() {
+        let mut state = PhysicsState::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let integration_parameters = IntegrationParameters::default();
+
+        // A corner made of two walls.
+        let wall1 = state
+            .colliders
+            .insert(ColliderBuilder::cuboid(0.1, 2.0, 5.0).translation(vector![1.0, 0.0, 0.0]));
+        let wall2 = state
+            .colliders
+            .insert(ColliderBuilder::cuboid(5.0, 2.0, 0.1).translation(vector![0.0, 0.0, 1.0]));
+        state.step(
+            &mut pipeline,
+            &Vector::zeros(),
+            &integration_parameters,
+            &(),
+            &(),
+        );
+        let query_pipeline = state.broad_phase.as_query_pipeline(
+            state.narrow_phase.query_dispatcher(),
+            &state.bodies,
+            &state.colliders,
+            QueryFilter::default(),
+        );
+
+        // Move diagonally into the corner: the character hits the first wall, slides along
+        // it, hits the second wall, and keeps hitting both while sliding.
+        let controller = KinematicCharacterController::default();
+        let mut num_events = 0;
+        let movement = controller.move_shape(
+            integration_parameters.dt,
+            &query_pipeline,
+            &Ball::new(0.5),
+            &Isometry::identity(),
+            vector![2.0, 0.0, 1.0],
+            |_| num_events += 1,
+        );
+
+        let handles: Vec<_> = movement.collisions.iter().map(|c| c.handle).collect();
+        assert_eq!(handles, [wall1, wall2]);
+        assert!(num_events >= 2);
+        assert!(movement.collisions[0].hit.time_of_impact <= 0.4 * 5.0f32.sqrt());
+    }
 }
@@ -1,5 +1,6 @@
 //! Utilities for controlling the trajectories of objects in a non-physical way.
 
+pub use self::buoyancy::{Buoyancy, SubmergedVolume};
 pub use self::character_controller::{
     CharacterAutostep, CharacterCollision, CharacterLength, EffectiveCharacterMovement,
     KinematicCharacterController,
@@ -9,6 +10,7 @@ pub use self::pid_controller::{PdController, PdErrors, PidController};
 #[cfg(feature = "dim3")]
 pub use self::ray_cast_vehicle_controller::{DynamicRayCastVehicleController, Wheel, WheelTuning};
 
+mod buoyancy;
 mod character_controller;
 
 mod pid_controller;
@@ -6,6 +6,26 @@ use std::fmt::{Display, Formatter, Result};
 pub struct CCDCounters {
     /// The number of substeps actually performed by the CCD resolution.
     pub num_substeps: usize,
+    /// The number of impacts found by the CCD solver during the last CCD substep allowed by
+    /// [`IntegrationParameters::max_ccd_substeps`](crate::dynamics::IntegrationParameters::max_ccd_substeps),
+    /// after all the previous substeps were used to resolve earlier impacts.
+    ///
+    /// The bodies stopped by these impacts lose the rest of their motion for the timestep instead
+    /// of being simulated in an additional substep. If this is often nonzero, `max_ccd_substeps`
+    /// may need to be increased. This is always zero if `max_ccd_substeps` is smaller than 2,
+    /// since the timestep is never split into several substeps. This is unrelated to the budget
+    /// of time-of-impact computations reported by [`Self::num_toi_budget_exceeded`].
+    pub num_impacts_after_last_substep: usize,
+    /// The number of time-of-impact computations actually performed by the CCD resolution.
+    ///
+    /// This doesn’t count the pairs of colliders discarded before reaching the
+    /// time-of-impact computation (e.g. because they are not moving fast enough).
+    pub num_toi_computations: usize,
+    /// The number of CCD sweeps cut short because the budget of time-of-impact computations was
+    /// exhausted.
+    ///
+    /// See [`CCDSolver::set_max_toi_computations_per_step`](crate::dynamics::CCDSolver::set_max_toi_computations_per_step).
+    pub num_toi_budget_exceeded: usize,
     /// The total time spent for TOI computation in the CCD resolution.
     pub toi_computation_time: Timer,
     /// The total time spent for force computation and integration in the CCD resolution.
@@ -21,6 +41,9 @@ impl CCDCounters {
     pub fn new() -> Self {
         CCDCounters {
             num_substeps: 0,
+            num_impacts_after_last_substep: 0,
+            num_toi_computations: 0,
+            num_toi_budget_exceeded: 0,
             toi_computation_time: Timer::new(),
             solver_time: Timer::new(),
             broad_phase_time: Timer::new(),
@@ -31,6 +54,9 @@ impl CCDCounters {
     /// Resets this counter to 0.
     pub fn reset(&mut self) {
         self.num_substeps = 0;
+        self.num_impacts_after_last_substep = 0;
+        self.num_toi_computations = 0;
+        self.num_toi_budget_exceeded = 0;
         self.toi_computation_time.reset();
         self.solver_time.reset();
         self.broad_phase_time.reset();
@@ -41,6 +67,21 @@ impl CCDCounters {
 impl Display for CCDCounters {
     fn fmt(&self, f: &mut Formatter) -> Result {
         writeln!(f, "Number of substeps: {}", self.num_substeps)?;
+        writeln!(
+            f,
+            "Number of impacts after the last substep: {}",
+            self.num_impacts_after_last_substep
+        )?;
+        writeln!(
+            f,
+            "Number of TOI computations: {}",
+            self.num_toi_computations
+        )?;
+        writeln!(
+            f,
+            "Number of exceeded TOI budgets: {}",
+            self.num_toi_budget_exceeded
+        )?;
         writeln!(f, "TOI computation time: {}", self.toi_computation_time)?;
         writeln!(f, "Constraints solver time: {}", self.solver_time)?;
         writeln!(f, "Broad-phase time: {}", self.broad_phase_time)?;
@@ -1,12 +1,26 @@
 use super::TOIEntry;
-use crate::dynamics::{IntegrationParameters, IslandManager, RigidBodyHandle, RigidBodySet};
-use crate::geometry::{BroadPhaseBvh, ColliderParent, ColliderSet, CollisionEvent, NarrowPhase};
-use crate::math::Real;
+use super::toi_entry::{MotionCache, PairFilterCache};
+use crate::dynamics::{
+    IntegrationParameters, IslandManager, RigidBody, RigidBodyHandle, RigidBodySet,
+};
+use crate::geometry::{
+    BroadPhaseBvh, CcdImpactEvent, ColliderHandle, ColliderParent, ColliderSet, CollisionEvent,
+    NarrowPhase,
+};
+use crate::math::{Isometry, Real, Vector};
 use crate::parry::utils::SortedPair;
 use crate::pipeline::{EventHandler, QueryFilter};
 use crate::prelude::{ActiveEvents, CollisionEventFlags};
 use parry::utils::hashmap::HashMap;
+use parry::utils::hashset::HashSet;
 use std::collections::BinaryHeap;
+use std::sync::Arc;
+
+/// A user-defined predicate deciding if CCD should be run between two colliders.
+///
+/// Returning `false` disables continuous collision detection between the given pair of
+/// colliders, even if both their parent rigid-bodies have CCD enabled.
+pub type CCDPairFilter = dyn Fn(ColliderHandle, ColliderHandle) -> bool + Send + Sync;
 
 pub enum PredictedImpacts {
     Impacts(HashMap<RigidBodyHandle, Real>),
@@ -36,14 +50,176 @@ pub enum PredictedImpacts {
 /// **Cost**: More expensive than regular collision detection. Only use when needed!
 ///
 /// Enable via `RigidBodyBuilder::ccd_enabled(true)` or `body.enable_ccd(true)`.
-#[derive(Clone, Default)]
+#[derive(Clone)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
-pub struct CCDSolver;
+pub struct CCDSolver {
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pair_filter: Option<Arc<CCDPairFilter>>,
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pair_filter_cache: PairFilterCache,
+    impact_events_enabled: bool,
+    linear_fallback_scale: Real,
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    motion_cache: MotionCache,
+    max_toi_computations_per_step: Option<usize>,
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pub(crate) num_toi_computations: usize,
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pub(crate) num_toi_budget_exceeded: usize,
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pub(crate) num_impacts: usize,
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pub(crate) tunneling_warnings_emitted: HashSet<RigidBodyHandle>,
+}
+
+impl Default for CCDSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl CCDSolver {
     /// Initializes a new CCD solver
     pub fn new() -> Self {
-        Self
+        Self {
+            pair_filter: None,
+            pair_filter_cache: PairFilterCache::default(),
+            impact_events_enabled: false,
+            linear_fallback_scale: 1.0,
+            motion_cache: MotionCache::default(),
+            max_toi_computations_per_step: None,
+            num_toi_computations: 0,
+            num_toi_budget_exceeded: 0,
+            num_impacts: 0,
+            tunneling_warnings_emitted: HashSet::default(),
+        }
+    }
+
+    /// The scaling factor applied to the search interval of the linear time-of-impact fallback.
+    ///
+    /// See [`CCDSolver::set_linear_fallback_scale`] for details.
+    pub fn linear_fallback_scale(&self) -> Real {
+        self.linear_fallback_scale
+    }
+
+    /// Sets the scaling factor applied to the search interval of the linear time-of-impact fallback.
+    ///
+    /// The CCD solver computes times of impact based on both the linear and angular motion of
+    /// the rigid-bodies. For pairs of shapes where this nonlinear computation isn’t supported,
+    /// it falls back to a linear time-of-impact computation ignoring the angular velocities,
+    /// which may under-predict impacts for fast-rotating shapes. This factor inflates the relative
+    /// linear velocity used by that fallback to make it more conservative: impacts are searched
+    /// over a longer distance and detected earlier, but never after the end of the timestep.
+    ///
+    /// If this is set to zero (or a negative value), the linear fallback is disabled entirely: a
+    /// debug message is logged and no CCD is performed for these pairs of shapes. This is useful
+    /// to identify which shape pairs hit the fallback.
+    ///
+    /// Defaults to `1.0`.
+    pub fn set_linear_fallback_scale(&mut self, scale: Real) {
+        self.linear_fallback_scale = scale;
+    }
+
+    /// The maximum number of time-of-impact computations performed during a single timestep.
+    ///
+    /// See [`CCDSolver::set_max_toi_computations_per_step`] for details.
+    pub fn max_toi_computations_per_step(&self) -> Option<usize> {
+        self.max_toi_computations_per_step
+    }
+
+    /// Sets the maximum number of time-of-impact computations performed during a single timestep.
+    ///
+    /// Once this budget is exhausted, the CCD solver stops searching for impacts for the rest of
+    /// the timestep: the impacts already found are still resolved, but the remaining fast
+    /// rigid-bodies are integrated normally and may tunnel through other colliders. This trades
+    /// accuracy for bounded computation times in worst-case scenarios (e.g. pileups of many
+    /// CCD-enabled bodies). Since the candidate pairs are always visited in the same order, the
+    /// impacts that are processed before the budget is exhausted are deterministic.
+    ///
+    /// The number of times the budget was exhausted is reported by
+    /// [`CCDCounters::num_toi_budget_exceeded`](crate::counters::CCDCounters::num_toi_budget_exceeded).
+    ///
+    /// Set to `None` (the default) for an unlimited budget.
+    pub fn set_max_toi_computations_per_step(&mut self, max: Option<usize>) {
+        self.max_toi_computations_per_step = max;
+    }
+
+    /// Resets the per-timestep counters, including the budget of time-of-impact computations,
+    /// and the verdicts of the pair filter cached during the previous timestep.
+    pub(crate) fn reset_step_counters(&mut self) {
+        self.num_toi_computations = 0;
+        self.num_toi_budget_exceeded = 0;
+        self.pair_filter_cache.clear();
+    }
+
+    /// Checks if the budget of time-of-impact computations of the current timestep is exhausted.
+    ///
+    /// The first time this happens during a sweep (as tracked by `exceeded`), it is recorded into
+    /// the counters.
+    fn toi_budget_exhausted(&mut self, exceeded: &mut bool) -> bool {
+        if *exceeded {
+            return true;
+        }
+
+        *exceeded = self
+            .max_toi_computations_per_step
+            .is_some_and(|max| self.num_toi_computations >= max);
+        self.num_toi_budget_exceeded += *exceeded as usize;
+        *exceeded
+    }
+
+    /// Sets a predicate deciding if CCD should be run between two colliders.
+    ///
+    /// The predicate is evaluated at most once per candidate collider pair and per timestep,
+    /// before any time-of-impact computation. If it returns `false`, no continuous collision
+    /// detection is performed between these colliders (they will still be handled by the
+    /// regular narrow-phase).
+    ///
+    /// This is useful to let fast projectiles CCD against walls while passing cheaply
+    /// through each other.
+    ///
+    /// # Example
+    /// ```
+    /// # use rapier3d::prelude::*;
+    /// # let mut colliders = ColliderSet::new();
+    /// # let projectile1 = colliders.insert(ColliderBuilder::ball(0.1));
+    /// # let projectile2 = colliders.insert(ColliderBuilder::ball(0.1));
+    /// let mut ccd_solver = CCDSolver::new();
+    /// ccd_solver.set_pair_filter(move |ch1, ch2| {
+    ///     // Disable CCD between the two projectiles.
+    ///     !((ch1 == projectile1 && ch2 == projectile2) || (ch1 == projectile2 && ch2 == projectile1))
+    /// });
+    /// ```
+    pub fn set_pair_filter(
+        &mut self,
+        filter: impl Fn(ColliderHandle, ColliderHandle) -> bool + Send + Sync + 'static,
+    ) {
+        self.pair_filter = Some(Arc::new(filter));
+        self.pair_filter_cache.clear();
+    }
+
+    /// Removes the predicate set with [`CCDSolver::set_pair_filter`], if any.
+    pub fn remove_pair_filter(&mut self) {
+        self.pair_filter = None;
+        self.pair_filter_cache.clear();
+    }
+
+    /// The predicate deciding if CCD should be run between two colliders, if any.
+    pub fn pair_filter(&self) -> Option<&CCDPairFilter> {
+        self.pair_filter.as_deref()
+    }
+
+    /// Enables or disables the emission of [`CcdImpactEvent`] whenever a rigid-body is stopped
+    /// at a time of impact.
+    ///
+    /// Events are sent to [`EventHandler::handle_ccd_impact_event`]. Disabled by default.
+    pub fn set_impact_events_enabled(&mut self, enabled: bool) {
+        self.impact_events_enabled = enabled;
+    }
+
+    /// Are [`CcdImpactEvent`] emitted whenever a rigid-body is stopped at a time of impact?
+    pub fn impact_events_enabled(&self) -> bool {
+        self.impact_events_enabled
     }
 
     /// Apply motion-clamping to the bodies affected by the given `impacts`.
@@ -66,10 +242,15 @@ impl CCDSolver {
                 //     rb.ccd.ccd_thickness,
                 //     rb.ccd.max_point_velocity(&rb.integrated_vels)
                 // );
-                let new_pos = rb
-                    .ccd_vels
-                    .integrate(toi.max(min_toi), &rb.pos.position, local_com);
+                let new_pos = rb.ccd.ccd_freeze_mode.integrate_frozen(
+                    &rb.ccd_vels,
+                    toi.max(min_toi),
+                    dt,
+                    &rb.pos.position,
+                    local_com,
+                );
                 rb.pos.next_position = new_pos;
+                rb.ccd.ccd_clamped = true;
             }
         }
     }
@@ -105,6 +286,53 @@ impl CCDSolver {
         ccd_active
     }
 
+    /// Logs a warning for each dynamic rigid-body moving fast enough to need CCD during the
+    /// last velocity solve of length `dt`, but that won’t have it resolved because CCD isn’t
+    /// enabled for it, or because `ccd_is_enabled` is `false` (no CCD substep is allowed).
+    ///
+    /// This applies the same test as [`Self::update_ccd_active_flags`]. These bodies are likely
+    /// to tunnel through thin obstacles. The warning is logged at most once per rigid-body.
+    pub(crate) fn warn_about_tunneling_risks(
+        &mut self,
+        islands: &IslandManager,
+        bodies: &RigidBodySet,
+        dt: Real,
+        ccd_is_enabled: bool,
+    ) {
+        for handle in islands.active_bodies() {
+            let rb = &bodies[*handle];
+
+            if !rb.is_dynamic() || (ccd_is_enabled && rb.ccd.ccd_enabled) {
+                continue;
+            }
+
+            // NOTE: the velocities used by the CCD are only stored for CCD-enabled bodies,
+            //       so compute them the same way here.
+            let vels = rb
+                .pos
+                .interpolate_velocity(1.0 / dt, rb.local_center_of_mass());
+            if rb.ccd.is_moving_fast(dt, &vels, None)
+                && self.tunneling_warnings_emitted.insert(*handle)
+            {
+                log::warn!(
+                    "Rigid-body {:?} moves fast enough relative to its CCD thickness ({}) to \
+                     need CCD, which won’t be resolved for it, and may tunnel through other \
+                     colliders. Consider enabling CCD for it.",
+                    handle,
+                    rb.ccd.ccd_thickness,
+                );
+            }
+        }
+    }
+
+    /// Forgets about the tunneling warnings emitted for rigid-bodies that were removed.
+    pub(crate) fn prune_tunneling_warnings(&mut self, bodies: &RigidBodySet) {
+        if !self.tunneling_warnings_emitted.is_empty() {
+            self.tunneling_warnings_emitted
+                .retain(|handle| bodies.contains(*handle));
+        }
+    }
+
     /// Find the first time a CCD-enabled body has a non-sensor collider hitting another non-sensor collider.
     #[profiling::function]
     pub fn find_first_impact(
@@ -141,8 +369,10 @@ impl CCDSolver {
 
         let mut pairs_seen = HashMap::default();
         let mut min_toi = dt;
+        let mut budget_exceeded = false;
+        self.motion_cache.clear();
 
-        for handle in islands.active_bodies() {
+        'bodies: for handle in islands.active_bodies() {
             let rb1 = &bodies[*handle];
 
             if rb1.ccd.ccd_active {
@@ -188,6 +418,8 @@ impl CCDSolver {
                             let bh1 = co1.parent.map(|p| p.handle);
                             let bh2 = co2.parent.map(|p| p.handle);
 
+                            let rb2 = bh2.and_then(|h| bodies.get(h));
+
                             // Ignore self-intersection and sensors and apply collision groups filter.
                             if bh1 == bh2                                                       // Ignore self-intersection.
                                     || (co1.is_sensor() || co2.is_sensor())                         // Ignore sensors.
@@ -198,16 +430,27 @@ impl CCDSolver {
                                 continue;
                             }
 
+                            // Kinematic-kinematic pairs are only intersection tests.
+                            if TOIEntry::is_kinematic_pair(Some(rb1), rb2) {
+                                continue;
+                            }
+
+                            if self.toi_budget_exhausted(&mut budget_exceeded) {
+                                break 'bodies;
+                            }
+
                             let smallest_dist = narrow_phase
                                 .contact_pair(*ch1, ch2)
                                 .and_then(|p| p.find_deepest_contact())
                                 .map(|c| c.1.dist)
                                 .unwrap_or(0.0);
 
-                            let rb2 = bh2.and_then(|h| bodies.get(h));
-
                             if let Some(toi) = TOIEntry::try_from_colliders(
                                 narrow_phase.query_dispatcher(),
+                                self.pair_filter.as_deref(),
+                                &mut self.pair_filter_cache,
+                                &mut self.motion_cache,
+                                &mut self.num_toi_computations,
                                 *ch1,
                                 ch2,
                                 co1,
@@ -219,6 +462,7 @@ impl CCDSolver {
                                 0.0,
                                 min_toi,
                                 smallest_dist,
+                                self.linear_fallback_scale,
                             ) {
                                 min_toi = min_toi.min(toi.toi);
                             }
@@ -248,6 +492,9 @@ impl CCDSolver {
         let mut all_toi = BinaryHeap::new();
         let mut pairs_seen = HashMap::default();
         let mut min_overstep = dt;
+        let mut budget_exceeded = false;
+        self.motion_cache.clear();
+        self.num_impacts = 0;
 
         // Update the query pipeline with the colliders’ `next_position`.
         for (handle, co) in colliders.iter_enabled() {
@@ -275,7 +522,7 @@ impl CCDSolver {
          *
          */
         // TODO: don't iterate through all the colliders.
-        for handle in islands.active_bodies() {
+        'bodies: for handle in islands.active_bodies() {
             let rb1 = &bodies[*handle];
 
             if rb1.ccd.ccd_active {
@@ -324,6 +571,10 @@ impl CCDSolver {
                                 continue;
                             }
 
+                            if self.toi_budget_exhausted(&mut budget_exceeded) {
+                                break 'bodies;
+                            }
+
                             let smallest_dist = narrow_phase
                                 .contact_pair(*ch1, ch2)
                                 .and_then(|p| p.find_deepest_contact())
@@ -335,6 +586,10 @@ impl CCDSolver {
 
                             if let Some(toi) = TOIEntry::try_from_colliders(
                                 query_pipeline.dispatcher,
+                                self.pair_filter.as_deref(),
+                                &mut self.pair_filter_cache,
+                                &mut self.motion_cache,
+                                &mut self.num_toi_computations,
                                 *ch1,
                                 ch2,
                                 co1,
@@ -348,6 +603,7 @@ impl CCDSolver {
                                 // there is at least one TOI before dt.
                                 min_overstep,
                                 smallest_dist,
+                                self.linear_fallback_scale,
                             ) {
                                 if toi.toi > dt {
                                     min_overstep = min_overstep.min(toi.toi);
@@ -408,13 +664,22 @@ impl CCDSolver {
                 continue;
             }
 
+            self.num_impacts += 1;
+
+            if self.impact_events_enabled {
+                let event = Self::impact_event(&toi, colliders, rb1, rb2, &frozen);
+                events.handle_ccd_impact_event(bodies, colliders, event);
+            }
+
             if should_freeze1 {
                 let _ = frozen.insert(toi.b1.unwrap(), toi.toi);
+                self.motion_cache.invalidate(toi.b1.unwrap());
                 colliders_to_check.extend_from_slice(&rb1.unwrap().colliders.0);
             }
 
             if should_freeze2 {
                 let _ = frozen.insert(toi.b2.unwrap(), toi.toi);
+                self.motion_cache.invalidate(toi.b2.unwrap());
                 colliders_to_check.extend_from_slice(&rb2.unwrap().colliders.0);
             }
 
@@ -422,7 +687,7 @@ impl CCDSolver {
 
             // NOTE: the 1 and 2 indices (e.g., `ch1`, `ch2`) below are unrelated to the
             //       ones we used above.
-            for ch1 in &colliders_to_check {
+            'resweep: for ch1 in &colliders_to_check {
                 let co1 = &colliders[*ch1];
                 let co1_parent = co1.parent.as_ref().unwrap();
                 let rb1 = &bodies[co1_parent.handle];
@@ -454,6 +719,12 @@ impl CCDSolver {
                         continue;
                     }
 
+                    if self.toi_budget_exhausted(&mut budget_exceeded) {
+                        // The resweep is skipped, but the impacts already found are still
+                        // processed.
+                        break 'resweep;
+                    }
+
                     let smallest_dist = narrow_phase
                         .contact_pair(*ch1, ch2)
                         .and_then(|p| p.find_deepest_contact())
@@ -462,6 +733,10 @@ impl CCDSolver {
 
                     if let Some(toi) = TOIEntry::try_from_colliders(
                         query_pipeline.dispatcher,
+                        self.pair_filter.as_deref(),
+                        &mut self.pair_filter_cache,
+                        &mut self.motion_cache,
+                        &mut self.num_toi_computations,
                         *ch1,
                         ch2,
                         co1,
@@ -473,6 +748,7 @@ impl CCDSolver {
                         start_time,
                         dt,
                         smallest_dist,
+                        self.linear_fallback_scale,
                     ) {
                         all_toi.push(toi);
                     }
@@ -490,8 +766,13 @@ impl CCDSolver {
             //   events because it will never be detected by the narrow-phase because of tunneling.
             let co1 = &colliders[toi.c1];
             let co2 = &colliders[toi.c2];
+            let is_sensor_pair = co1.is_sensor() || co2.is_sensor();
+            let is_kinematic_pair = TOIEntry::is_kinematic_pair(
+                toi.b1.and_then(|h| bodies.get(h)),
+                toi.b2.and_then(|h| bodies.get(h)),
+            );
 
-            if !co1.is_sensor() && !co2.is_sensor() {
+            if !is_sensor_pair && !is_kinematic_pair {
                 // TODO: this happens if we found a TOI between two non-sensor
                 //       colliders with mismatching solver_flags. It is not clear
                 //       what we should do in this case: we could report a
@@ -508,7 +789,15 @@ impl CCDSolver {
                 let local_com1 = &rb1.mprops.local_mprops.local_com;
                 let frozen1 = frozen.get(&b1);
                 let pos1 = frozen1
-                    .map(|t| rb1.ccd_vels.integrate(*t, &rb1.pos.position, local_com1))
+                    .map(|t| {
+                        rb1.ccd.ccd_freeze_mode.integrate_frozen(
+                            &rb1.ccd_vels,
+                            *t,
+                            dt,
+                            &rb1.pos.position,
+                            local_com1,
+                        )
+                    })
                     .unwrap_or(rb1.pos.next_position);
                 pos1 * co_parent1.pos_wrt_parent
             } else {
@@ -521,7 +810,15 @@ impl CCDSolver {
                 let local_com2 = &rb2.mprops.local_mprops.local_com;
                 let frozen2 = frozen.get(&b2);
                 let pos2 = frozen2
-                    .map(|t| rb2.ccd_vels.integrate(*t, &rb2.pos.position, local_com2))
+                    .map(|t| {
+                        rb2.ccd.ccd_freeze_mode.integrate_frozen(
+                            &rb2.ccd_vels,
+                            *t,
+                            dt,
+                            &rb2.pos.position,
+                            local_com2,
+                        )
+                    })
                     .unwrap_or(rb2.pos.next_position);
                 pos2 * co_parent2.pos_wrt_parent
             } else {
@@ -547,16 +844,21 @@ impl CCDSolver {
                     .contains(ActiveEvents::COLLISION_EVENTS)
             {
                 // Emit one intersection-started and one intersection-stopped event.
+                let flags = if is_sensor_pair {
+                    CollisionEventFlags::SENSOR
+                } else {
+                    CollisionEventFlags::empty()
+                };
                 events.handle_collision_event(
                     bodies,
                     colliders,
-                    CollisionEvent::Started(toi.c1, toi.c2, CollisionEventFlags::SENSOR),
+                    CollisionEvent::Started(toi.c1, toi.c2, flags),
                     None,
                 );
                 events.handle_collision_event(
                     bodies,
                     colliders,
-                    CollisionEvent::Stopped(toi.c1, toi.c2, CollisionEventFlags::SENSOR),
+                    CollisionEvent::Stopped(toi.c1, toi.c2, flags),
                     None,
                 );
             }
@@ -564,4 +866,66 @@ impl CCDSolver {
 
         PredictedImpacts::Impacts(frozen)
     }
+
+    fn impact_event(
+        toi: &TOIEntry,
+        colliders: &ColliderSet,
+        rb1: Option<&RigidBody>,
+        rb2: Option<&RigidBody>,
+        frozen: &HashMap<RigidBodyHandle, Real>,
+    ) -> CcdImpactEvent {
+        let co1 = &colliders[toi.c1];
+        let pos1 = match (rb1, co1.parent.as_ref()) {
+            (Some(rb1), Some(co_parent1)) => {
+                let frozen1 = frozen.get(&co_parent1.handle).copied();
+                Self::body_position_at_time(rb1, frozen1, toi.toi) * co_parent1.pos_wrt_parent
+            }
+            _ => co1.pos.0,
+        };
+        let impact_point = pos1 * toi.witness1;
+
+        // NOTE: bodies that don’t have CCD active are considered static by the TOI computation.
+        let point_velocity =
+            |rb: Option<&RigidBody>, handle: Option<RigidBodyHandle>| match (rb, handle) {
+                (Some(rb), Some(handle)) if rb.ccd.ccd_active => {
+                    let frozen = frozen.get(&handle).copied();
+                    let body_pos = Self::body_position_at_time(rb, frozen, toi.toi);
+                    let world_com = body_pos * rb.mprops.local_mprops.local_com;
+                    let vels = if frozen.is_some() {
+                        rb.ccd.ccd_freeze_mode.frozen_velocity(&rb.ccd_vels)
+                    } else {
+                        rb.ccd_vels
+                    };
+                    vels.velocity_at_point(&impact_point, &world_com)
+                }
+                _ => Vector::zeros(),
+            };
+
+        CcdImpactEvent {
+            collider1: toi.c1,
+            collider2: toi.c2,
+            toi: toi.toi,
+            relative_velocity: point_velocity(rb2, toi.b2) - point_velocity(rb1, toi.b1),
+            witness1: toi.witness1,
+            witness2: toi.witness2,
+            normal1: toi.normal1,
+        }
+    }
+
+    fn body_position_at_time(rb: &RigidBody, frozen: Option<Real>, t: Real) -> Isometry<Real> {
+        let local_com = &rb.mprops.local_mprops.local_com;
+        if !rb.ccd.ccd_active {
+            rb.pos.next_position
+        } else if let Some(freeze_time) = frozen {
+            rb.ccd.ccd_freeze_mode.integrate_frozen(
+                &rb.ccd_vels,
+                freeze_time,
+                t,
+                &rb.pos.position,
+                local_com,
+            )
+        } else {
+            rb.ccd_vels.integrate(t, &rb.pos.position, local_com)
+        }
+    }
 }
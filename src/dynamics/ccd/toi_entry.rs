@@ -1,8 +1,77 @@
-use crate::dynamics::{RigidBody, RigidBodyHandle};
+use super::ccd_solver::CCDPairFilter;
+use crate::dynamics::{
+    CcdFreezeMode, RigidBody, RigidBodyHandle, RigidBodyType, RigidBodyVelocity,
+};
 use crate::geometry::{Collider, ColliderHandle};
-use crate::math::Real;
-use parry::query::{NonlinearRigidMotion, QueryDispatcher, ShapeCastOptions};
+use crate::math::{Point, Real, Vector};
+use na::Unit;
+use parry::query::{NonlinearRigidMotion, QueryDispatcher, ShapeCastHit, ShapeCastOptions};
+use parry::utils::SortedPair;
+use parry::utils::hashmap::HashMap;
 
+/// Cache of the nonlinear motions of the rigid-bodies involved in CCD.
+///
+/// This avoids recomputing the motion of a single body for each of the (potentially numerous)
+/// TOI candidates it is involved in. The cache must be cleared at each CCD substep, and
+/// the motion of a body must be invalidated whenever it gets frozen.
+#[derive(Clone, Default)]
+pub(crate) struct MotionCache {
+    motions: HashMap<RigidBodyHandle, NonlinearRigidMotion>,
+}
+
+impl MotionCache {
+    pub fn clear(&mut self) {
+        self.motions.clear();
+    }
+
+    pub fn invalidate(&mut self, handle: RigidBodyHandle) {
+        let _ = self.motions.remove(&handle);
+    }
+
+    fn body_motion(
+        &mut self,
+        handle: RigidBodyHandle,
+        rb: &RigidBody,
+        frozen: Option<Real>,
+    ) -> NonlinearRigidMotion {
+        *self.motions.entry(handle).or_insert_with(|| {
+            let mut motion = TOIEntry::body_motion(rb);
+            if let Some(t) = frozen {
+                TOIEntry::freeze_motion(&mut motion, rb.ccd.ccd_freeze_mode, t);
+            }
+            motion
+        })
+    }
+}
+
+/// Cache of the verdicts of the user-defined CCD pair filter.
+///
+/// This ensures the filter is evaluated at most once per collider pair, even if that pair is
+/// visited again while resweeping. The cache must be cleared at each timestep, and whenever
+/// the filter changes.
+#[derive(Clone, Default)]
+pub(crate) struct PairFilterCache {
+    verdicts: HashMap<SortedPair<u32>, bool>,
+}
+
+impl PairFilterCache {
+    pub fn clear(&mut self) {
+        self.verdicts.clear();
+    }
+
+    fn test(&mut self, filter: &CCDPairFilter, ch1: ColliderHandle, ch2: ColliderHandle) -> bool {
+        let key = SortedPair::new(ch1.into_raw_parts().0, ch2.into_raw_parts().0);
+        *self.verdicts.entry(key).or_insert_with(|| filter(ch1, ch2))
+    }
+}
+
+/// A time of impact between two colliders found by the CCD solver.
+///
+/// TOI entries are totally ordered in a reproducible way: they are sorted by time of impact
+/// (reversed, so that the earliest impact comes first in a max-heap), and ties are broken
+/// using the collider handles. This ensures that impacts occurring at the exact same time
+/// are always processed in the same order, independently from the platform or from the
+/// order in which they were found.
 #[derive(Copy, Clone, Debug)]
 pub struct TOIEntry {
     pub toi: Real,
@@ -11,13 +80,23 @@ pub struct TOIEntry {
     pub c2: ColliderHandle,
     pub b2: Option<RigidBodyHandle>,
     // We call this "pseudo" intersection because this also
-    // includes colliders pairs with mismatching solver_groups.
+    // includes colliders pairs with mismatching solver_groups,
+    // and pairs of colliders attached to kinematic bodies.
     pub is_pseudo_intersection_test: bool,
+    /// The contact point on the first collider at the time of impact, expressed in the local
+    /// frame of the first collider.
+    pub witness1: Point<Real>,
+    /// The contact point on the second collider at the time of impact, expressed in the local
+    /// frame of the second collider.
+    pub witness2: Point<Real>,
+    /// The contact normal at the time of impact, pointing outward the first collider and
+    /// expressed in the local frame of the first collider.
+    pub normal1: Unit<Vector<Real>>,
 }
 
 impl TOIEntry {
     fn new(
-        toi: Real,
+        hit: &ShapeCastHit,
         c1: ColliderHandle,
         b1: Option<RigidBodyHandle>,
         c2: ColliderHandle,
@@ -25,18 +104,25 @@ impl TOIEntry {
         is_pseudo_intersection_test: bool,
     ) -> Self {
         Self {
-            toi,
+            toi: hit.time_of_impact,
             c1,
             b1,
             c2,
             b2,
             is_pseudo_intersection_test,
+            witness1: hit.witness1,
+            witness2: hit.witness2,
+            normal1: hit.normal1,
         }
     }
 
     #[profiling::function]
     pub fn try_from_colliders<QD: ?Sized + QueryDispatcher>(
         query_dispatcher: &QD,
+        pair_filter: Option<&CCDPairFilter>,
+        pair_filter_cache: &mut PairFilterCache,
+        motion_cache: &mut MotionCache,
+        num_toi_computations: &mut usize,
         ch1: ColliderHandle,
         ch2: ColliderHandle,
         co1: &Collider,
@@ -48,20 +134,43 @@ impl TOIEntry {
         start_time: Real,
         end_time: Real,
         smallest_contact_dist: Real,
+        linear_fallback_scale: Real,
     ) -> Option<Self> {
         assert!(start_time <= end_time);
         if rb1.is_none() && rb2.is_none() {
             return None;
         }
 
-        let linvel1 =
-            frozen1.is_none() as u32 as Real * rb1.map(|b| b.ccd_vels.linvel).unwrap_or(na::zero());
-        let linvel2 =
-            frozen2.is_none() as u32 as Real * rb2.map(|b| b.ccd_vels.linvel).unwrap_or(na::zero());
-        let angvel1 =
-            frozen1.is_none() as u32 as Real * rb1.map(|b| b.ccd_vels.angvel).unwrap_or(na::zero());
-        let angvel2 =
-            frozen2.is_none() as u32 as Real * rb2.map(|b| b.ccd_vels.angvel).unwrap_or(na::zero());
+        // Colliders teleported relative to their parent during this timestep have no meaningful
+        // swept motion.
+        if co1.ccd_teleported || co2.ccd_teleported {
+            return None;
+        }
+
+        // Filter based on the rigid-body types, like the narrow-phase does.
+        let rb_type1 = rb1.map(|rb| rb.body_type).unwrap_or(RigidBodyType::Fixed);
+        let rb_type2 = rb2.map(|rb| rb.body_type).unwrap_or(RigidBodyType::Fixed);
+        if !co1.flags.active_collision_types.test(rb_type1, rb_type2)
+            && !co2.flags.active_collision_types.test(rb_type1, rb_type2)
+        {
+            return None;
+        }
+
+        // Check the user-defined filter before doing any work on the motions.
+        if let Some(filter) = pair_filter {
+            if !pair_filter_cache.test(filter, ch1, ch2) {
+                return None;
+            }
+        }
+
+        let vels1 = rb1
+            .map(|b| Self::body_velocity(b, frozen1))
+            .unwrap_or_default();
+        let vels2 = rb2
+            .map(|b| Self::body_velocity(b, frozen2))
+            .unwrap_or_default();
+        let (linvel1, angvel1) = (vels1.linvel, vels1.angvel);
+        let (linvel2, angvel2) = (vels2.linvel, vels2.angvel);
 
         #[cfg(feature = "dim2")]
         let vel12 = (linvel2 - linvel1).norm()
@@ -75,11 +184,12 @@ impl TOIEntry {
         // We may be slightly over-conservative by taking the `max(0.0)` here.
         // But removing the `max` doesn't really affect performances so let's
         // keep it since more conservatism is good at this stage.
-        let thickness = (co1.shape.0.ccd_thickness() + co2.shape.0.ccd_thickness())
-            + smallest_contact_dist.max(0.0);
+        let thickness =
+            (co1.ccd_thickness() + co2.ccd_thickness()) + smallest_contact_dist.max(0.0);
         let is_pseudo_intersection_test = co1.is_sensor()
             || co2.is_sensor()
-            || !co1.flags.solver_groups.test(co2.flags.solver_groups);
+            || !co1.flags.solver_groups.test(co2.flags.solver_groups)
+            || Self::is_kinematic_pair(rb1, rb2);
 
         if (end_time - start_time) * vel12 < thickness {
             return None;
@@ -87,16 +197,18 @@ impl TOIEntry {
 
         // Compute the TOI.
         let identity = NonlinearRigidMotion::identity();
-        let mut motion1 = rb1.map(Self::body_motion).unwrap_or(identity);
-        let mut motion2 = rb2.map(Self::body_motion).unwrap_or(identity);
-
-        if let Some(t) = frozen1 {
-            motion1.freeze(t);
-        }
-
-        if let Some(t) = frozen2 {
-            motion2.freeze(t);
-        }
+        let motion1 = match (rb1, co1.parent) {
+            (Some(rb1), Some(co_parent1)) => {
+                motion_cache.body_motion(co_parent1.handle, rb1, frozen1)
+            }
+            _ => identity,
+        };
+        let motion2 = match (rb2, co2.parent) {
+            (Some(rb2), Some(co_parent2)) => {
+                motion_cache.body_motion(co_parent2.handle, rb2, frozen2)
+            }
+            _ => identity,
+        };
 
         let motion_c1 = motion1.prepend(co1.parent.map(|p| p.pos_wrt_parent).unwrap_or(co1.pos.0));
         let motion_c2 = motion2.prepend(co2.parent.map(|p| p.pos_wrt_parent).unwrap_or(co2.pos.0));
@@ -112,41 +224,25 @@ impl TOIEntry {
         // because the colliders may be in a separating trajectory.
         let stop_at_penetration = is_pseudo_intersection_test;
 
-        const USE_NONLINEAR_SHAPE_CAST: bool = true;
-
-        let toi = if USE_NONLINEAR_SHAPE_CAST {
-            query_dispatcher
-                .cast_shapes_nonlinear(
-                    &motion_c1,
-                    co1.shape.as_ref(),
-                    &motion_c2,
-                    co2.shape.as_ref(),
-                    start_time,
-                    end_time,
-                    stop_at_penetration,
-                )
-                .ok()??
-        } else {
-            let pos12 = motion_c1
-                .position_at_time(start_time)
-                .inv_mul(&motion_c2.position_at_time(start_time));
-            let vel12 = linvel2 - linvel1;
-            let options = ShapeCastOptions::with_max_time_of_impact(end_time - start_time);
-            let mut hit = query_dispatcher
-                .cast_shapes(
-                    &pos12,
-                    &vel12,
-                    co1.shape.as_ref(),
-                    co2.shape.as_ref(),
-                    options,
-                )
-                .ok()??;
-            hit.time_of_impact += start_time;
-            hit
-        };
+        *num_toi_computations += 1;
+
+        let toi = Self::cast_colliders(
+            query_dispatcher,
+            ch1,
+            ch2,
+            co1,
+            co2,
+            &motion_c1,
+            &motion_c2,
+            linvel2 - linvel1,
+            start_time,
+            end_time,
+            stop_at_penetration,
+            linear_fallback_scale,
+        )?;
 
         Some(Self::new(
-            toi.time_of_impact,
+            &toi,
             ch1,
             co1.parent.map(|p| p.handle),
             ch2,
@@ -155,6 +251,133 @@ impl TOIEntry {
         ))
     }
 
+    /// Computes the time of impact between two colliders following the given motions.
+    ///
+    /// The nonlinear shape-cast is used whenever it is supported for this pair of shapes.
+    /// Otherwise, this falls back to a linear shape-cast with the world-space relative linear
+    /// velocity `vel12` scaled by `linear_fallback_scale`, ignoring angular motions. The returned
+    /// time of impact never exceeds `end_time`.
+    /// Impacts with one-way platforms coming from the wrong side are ignored, unless
+    /// `stop_at_penetration` is `true` (i.e. this is an intersection test).
+    pub(crate) fn cast_colliders<QD: ?Sized + QueryDispatcher>(
+        query_dispatcher: &QD,
+        ch1: ColliderHandle,
+        ch2: ColliderHandle,
+        co1: &Collider,
+        co2: &Collider,
+        motion_c1: &NonlinearRigidMotion,
+        motion_c2: &NonlinearRigidMotion,
+        vel12: Vector<Real>,
+        start_time: Real,
+        end_time: Real,
+        stop_at_penetration: bool,
+        linear_fallback_scale: Real,
+    ) -> Option<ShapeCastHit> {
+        let toi = match query_dispatcher.cast_shapes_nonlinear(
+            motion_c1,
+            co1.shape.as_ref(),
+            motion_c2,
+            co2.shape.as_ref(),
+            start_time,
+            end_time,
+            stop_at_penetration,
+        ) {
+            Ok(toi) => toi?,
+            Err(_) if linear_fallback_scale > 0.0 => {
+                // The nonlinear shape-cast isn’t supported for this pair of shapes.
+                // Fallback to a linear shape-cast, ignoring angular velocities.
+                log::debug!(
+                    "Nonlinear TOI unsupported between {:?} and {:?}, falling back to linear TOI.",
+                    ch1,
+                    ch2
+                );
+                let pos1 = motion_c1.position_at_time(start_time);
+                let pos12 = pos1.inv_mul(&motion_c2.position_at_time(start_time));
+                // Inflate the distance searched by the linear cast, while keeping the
+                // resulting time of impact within `[start_time, end_time]`.
+                let local_vel12 =
+                    pos1.rotation.inverse_transform_vector(&vel12) * linear_fallback_scale;
+                let options = ShapeCastOptions::with_max_time_of_impact(end_time - start_time);
+                let mut hit = query_dispatcher
+                    .cast_shapes(
+                        &pos12,
+                        &local_vel12,
+                        co1.shape.as_ref(),
+                        co2.shape.as_ref(),
+                        options,
+                    )
+                    .ok()??;
+                hit.time_of_impact += start_time;
+                hit
+            }
+            Err(_) => {
+                log::debug!(
+                    "Nonlinear TOI unsupported between {:?} and {:?}, and the linear fallback is disabled.",
+                    ch1,
+                    ch2
+                );
+                return None;
+            }
+        };
+
+        // Ignore impacts with one-way platforms coming from the wrong side. Note that
+        // sensors (and other pseudo-intersections) still report any intersection.
+        if !stop_at_penetration {
+            let allowed1 = co1
+                .one_way_platform()
+                .is_none_or(|platform| platform.allows_impact(&toi.normal1));
+            let allowed2 = co2
+                .one_way_platform()
+                .is_none_or(|platform| platform.allows_impact(&toi.normal2));
+            if !allowed1 || !allowed2 {
+                return None;
+            }
+        }
+
+        Some(toi)
+    }
+
+    /// Are both colliders attached to kinematic rigid-bodies?
+    ///
+    /// The CCD between two kinematic bodies is never resolved since none of them can be
+    /// affected by the impact. It is only used for generating intersection events.
+    pub fn is_kinematic_pair(rb1: Option<&RigidBody>, rb2: Option<&RigidBody>) -> bool {
+        rb1.map(|rb| rb.is_kinematic()).unwrap_or(false)
+            && rb2.map(|rb| rb.is_kinematic()).unwrap_or(false)
+    }
+
+    fn body_velocity(rb: &RigidBody, frozen: Option<Real>) -> RigidBodyVelocity<Real> {
+        if frozen.is_some() {
+            rb.ccd.ccd_freeze_mode.frozen_velocity(&rb.ccd_vels)
+        } else {
+            rb.ccd_vels
+        }
+    }
+
+    fn freeze_motion(motion: &mut NonlinearRigidMotion, mode: CcdFreezeMode, t: Real) {
+        match mode {
+            CcdFreezeMode::All => motion.freeze(t),
+            CcdFreezeMode::Linear => {
+                // Move the center of mass to its position at time `t` without
+                // affecting the rotational motion.
+                motion.start.translation.vector += motion.linvel * t;
+                motion.linvel = na::zero();
+            }
+            CcdFreezeMode::Angular => {
+                // Apply the rotation at time `t` without affecting the translational motion.
+                let linvel = motion.linvel;
+                motion.linvel = na::zero();
+                motion.start = motion.position_at_time(t);
+                motion.angvel = na::zero();
+                motion.linvel = linvel;
+            }
+        }
+    }
+
+    fn sort_key(&self) -> ((u32, u32), (u32, u32)) {
+        (self.c1.0.into_raw_parts(), self.c2.0.into_raw_parts())
+    }
+
     fn body_motion(rb: &RigidBody) -> NonlinearRigidMotion {
         if rb.ccd.ccd_active {
             NonlinearRigidMotion::new(
@@ -177,16 +400,308 @@ impl PartialOrd for TOIEntry {
 
 impl Ord for TOIEntry {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        (-self.toi)
-            .partial_cmp(&(-other.toi))
-            .unwrap_or(std::cmp::Ordering::Equal)
+        other
+            .toi
+            .total_cmp(&self.toi)
+            .then_with(|| other.sort_key().cmp(&self.sort_key()))
     }
 }
 
 impl PartialEq for TOIEntry {
     fn eq(&self, other: &Self) -> bool {
-        self.toi == other.toi
+        self.cmp(other) == std::cmp::Ordering::Equal
     }
 }
 
 impl Eq for TOIEntry {}
+
+#[cfg(test)]
+mod test {
+    use super::{MotionCache, PairFilterCache, TOIEntry};
+    use crate::dynamics::{RigidBodyBuilder, RigidBodySet};
+    use crate::geometry::{ColliderBuilder, ColliderHandle, ColliderSet};
+    use crate::math::{Isometry, Point, Real, Vector};
+    use parry::query::{DefaultQueryDispatcher, NonlinearRigidMotion};
+
+    const DT: Real = 1.0 / 60.0;
+
+    /// A thin wall at `x = 2`, rotated so that its local frame differs from the world frame, and
+    /// a CCD-active ball of radius 0.1 moving toward it from the origin.
+    fn wall_and_ball(speed: Real) -> (RigidBodySet, ColliderSet, ColliderHandle, ColliderHandle) {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let angle = std::f64::consts::FRAC_PI_2 as Real;
+        #[cfg(feature = "dim2")]
+        let wall = ColliderBuilder::cuboid(10.0, 0.05).rotation(angle);
+        #[cfg(feature = "dim3")]
+        let wall = ColliderBuilder::cuboid(10.0, 0.05, 10.0).rotation(Vector::z() * angle);
+        let wall = colliders.insert(wall.translation(Vector::x() * 2.0));
+
+        let body = bodies.insert(RigidBodyBuilder::dynamic().linvel(Vector::x() * speed));
+        let ball = colliders.insert_with_parent(ColliderBuilder::ball(0.1), body, &mut bodies);
+        let rb = bodies.get_mut(body).unwrap();
+        rb.ccd.ccd_active = true;
+        rb.ccd_vels = rb.vels;
+
+        (bodies, colliders, wall, ball)
+    }
+
+    /// Computes the time of impact between two colliders over one timestep.
+    fn toi(
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        ch1: ColliderHandle,
+        ch2: ColliderHandle,
+        num_toi_computations: &mut usize,
+    ) -> Option<TOIEntry> {
+        let (co1, co2) = (&colliders[ch1], &colliders[ch2]);
+        TOIEntry::try_from_colliders(
+            &DefaultQueryDispatcher,
+            None,
+            &mut PairFilterCache::default(),
+            &mut MotionCache::default(),
+            num_toi_computations,
+            ch1,
+            ch2,
+            co1,
+            co2,
+            co1.parent().map(|h| &bodies[h]),
+            co2.parent().map(|h| &bodies[h]),
+            None,
+            None,
+            0.0,
+            DT,
+            0.0,
+            1.0,
+        )
+    }
+
+    #[test]
+    fn impact_geometry_is_expressed_in_the_collider_local_frames() {
+        let (bodies, colliders, wall, ball) = wall_and_ball(200.0);
+
+        // The ball touches the wall after its center moved by 2 - 0.05 - 0.1 = 1.85.
+        let entry = toi(&bodies, &colliders, wall, ball, &mut 0).unwrap();
+        approx::assert_relative_eq!(entry.toi * 200.0, 1.85, epsilon = 1.0e-2);
+        // The wall’s local `y` axis points toward the world’s `-x` direction.
+        approx::assert_relative_eq!(*entry.normal1, Vector::y(), epsilon = 1.0e-3);
+        approx::assert_relative_eq!(
+            entry.witness1,
+            Point::from(Vector::y() * 0.05),
+            epsilon = 1.0e-2
+        );
+        approx::assert_relative_eq!(
+            entry.witness2,
+            Point::from(Vector::x() * 0.1),
+            epsilon = 1.0e-2
+        );
+
+        // Swapping the colliders swaps the frames.
+        let entry = toi(&bodies, &colliders, ball, wall, &mut 0).unwrap();
+        approx::assert_relative_eq!(*entry.normal1, Vector::x(), epsilon = 1.0e-3);
+        approx::assert_relative_eq!(
+            entry.witness1,
+            Point::from(Vector::x() * 0.1),
+            epsilon = 1.0e-2
+        );
+        approx::assert_relative_eq!(
+            entry.witness2,
+            Point::from(Vector::y() * 0.05),
+            epsilon = 1.0e-2
+        );
+    }
+
+    #[test]
+    fn linear_fallback_stays_within_the_time_interval() {
+        let mut colliders = ColliderSet::new();
+        let ball = colliders.insert(ColliderBuilder::ball(0.1));
+        let floor = colliders.insert(ColliderBuilder::halfspace(Vector::y_axis()));
+        let dt = 1.0 / 60.0;
+
+        // The nonlinear shape-cast isn’t supported against half-spaces. The ball is rotated to
+        // check that the linear fallback doesn’t mix up the world and local frames.
+        #[cfg(feature = "dim2")]
+        let start = Isometry::new(Vector::y() * 3.0, std::f64::consts::FRAC_PI_2 as Real);
+        #[cfg(feature = "dim3")]
+        let start = Isometry::new(
+            Vector::y() * 3.0,
+            Vector::z() * std::f64::consts::FRAC_PI_2 as Real,
+        );
+        let linvel = -Vector::y() * 100.0;
+        let motion1 = NonlinearRigidMotion::new(start, Point::origin(), linvel, na::zero());
+        let motion2 = NonlinearRigidMotion::identity();
+
+        let cast = |scale: Real| {
+            TOIEntry::cast_colliders(
+                &DefaultQueryDispatcher,
+                ball,
+                floor,
+                &colliders[ball],
+                &colliders[floor],
+                &motion1,
+                &motion2,
+                -linvel,
+                0.0,
+                dt,
+                false,
+                scale,
+            )
+        };
+
+        // The ball reaches the floor after 1.74 timesteps.
+        assert!(cast(0.0).is_none());
+        assert!(cast(1.0).is_none());
+        let hit = cast(2.0).unwrap();
+        assert!(hit.time_of_impact <= dt);
+        approx::assert_relative_eq!(hit.time_of_impact, dt * 0.87, epsilon = 1.0e-4);
+    }
+
+    #[test]
+    fn frozen_motions_only_stop_the_frozen_components() {
+        use crate::dynamics::CcdFreezeMode;
+
+        let freeze_time = 0.5;
+        #[cfg(feature = "dim2")]
+        let angvel = 1.0;
+        #[cfg(feature = "dim3")]
+        let angvel = Vector::z();
+
+        for mode in [
+            CcdFreezeMode::All,
+            CcdFreezeMode::Linear,
+            CcdFreezeMode::Angular,
+        ] {
+            let mut bodies = RigidBodySet::new();
+            let rb = RigidBodyBuilder::dynamic()
+                .linvel(Vector::x() * 2.0)
+                .angvel(angvel)
+                .ccd_freeze_mode(mode);
+            let handle = bodies.insert(rb);
+            let rb = bodies.get_mut(handle).unwrap();
+            rb.ccd.ccd_active = true;
+            rb.ccd_vels = rb.vels;
+            let rb = &bodies[handle];
+
+            // The frozen components stay at their value at the freeze time.
+            let motion = MotionCache::default().body_motion(handle, rb, Some(freeze_time));
+            let end = motion.position_at_time(1.0);
+            let expected_x = if mode.freezes_linear() { 1.0 } else { 2.0 };
+            let expected_angle = if mode.freezes_angular() { 0.5 } else { 1.0 };
+            approx::assert_relative_eq!(
+                end.translation.vector,
+                Vector::x() * expected_x,
+                epsilon = 1.0e-5
+            );
+            approx::assert_relative_eq!(end.rotation.angle(), expected_angle, epsilon = 1.0e-5);
+
+            let vels = TOIEntry::body_velocity(rb, Some(freeze_time));
+            assert_eq!(vels.linvel == Vector::zeros(), mode.freezes_linear());
+            assert_eq!(vels.angvel == na::zero(), mode.freezes_angular());
+
+            // Freezing everything is the same as freezing the whole motion.
+            if mode == CcdFreezeMode::All {
+                let mut expected = TOIEntry::body_motion(rb);
+                expected.freeze(freeze_time);
+                assert_eq!(end, expected.position_at_time(1.0));
+            }
+        }
+    }
+
+    #[test]
+    fn motion_cache_is_refreshed_when_invalidated() {
+        let (mut bodies, colliders, _, ball) = wall_and_ball(2.0);
+        let handle = colliders[ball].parent().unwrap();
+        let mut cache = MotionCache::default();
+
+        let motion = cache.body_motion(handle, &bodies[handle], None);
+        assert_eq!(motion.linvel, Vector::x() * 2.0);
+
+        // The motion is computed only once per substep.
+        bodies.get_mut(handle).unwrap().ccd_vels.linvel = Vector::x() * 5.0;
+        let motion = cache.body_motion(handle, &bodies[handle], None);
+        assert_eq!(motion.linvel, Vector::x() * 2.0);
+
+        // Freezing the body invalidates its motion.
+        cache.invalidate(handle);
+        let motion = cache.body_motion(handle, &bodies[handle], Some(0.5));
+        assert_eq!(motion.linvel, Vector::zeros());
+        approx::assert_relative_eq!(
+            motion.position_at_time(1.0).translation.vector,
+            Vector::x() * 2.5,
+            epsilon = 1.0e-5
+        );
+
+        // The next substep starts from scratch.
+        cache.clear();
+        let motion = cache.body_motion(handle, &bodies[handle], None);
+        assert_eq!(motion.linvel, Vector::x() * 5.0);
+    }
+
+    #[test]
+    fn simultaneous_impacts_are_ordered_by_collider_handles() {
+        use std::collections::BinaryHeap;
+
+        let entry = |toi: Real, c1: u32, c2: u32| TOIEntry {
+            toi,
+            c1: ColliderHandle::from_raw_parts(c1, 0),
+            b1: None,
+            c2: ColliderHandle::from_raw_parts(c2, 0),
+            b2: None,
+            is_pseudo_intersection_test: false,
+            witness1: Point::origin(),
+            witness2: Point::origin(),
+            normal1: Vector::x_axis(),
+        };
+        let entries = [
+            entry(0.5, 3, 4),
+            entry(0.5, 1, 3),
+            entry(0.2, 5, 6),
+            entry(0.5, 1, 2),
+        ];
+
+        // Entries with the same time of impact are only equal if they involve the same colliders.
+        assert_ne!(entries[0], entries[1]);
+        let mut other = entries[0];
+        other.witness1 = Point::from(Vector::y());
+        assert_eq!(entries[0], other);
+
+        // The earliest impact comes first, and ties are broken the same way whatever the
+        // insertion order.
+        let pop_all = |entries: &mut dyn Iterator<Item = TOIEntry>| {
+            let mut heap: BinaryHeap<_> = entries.collect();
+            std::iter::from_fn(|| heap.pop())
+                .map(|e| (e.c1.into_raw_parts().0, e.c2.into_raw_parts().0))
+                .collect::<Vec<_>>()
+        };
+        let expected = [(5, 6), (1, 2), (1, 3), (3, 4)];
+        assert_eq!(pop_all(&mut entries.into_iter()), expected);
+        assert_eq!(pop_all(&mut entries.into_iter().rev()), expected);
+    }
+
+    #[test]
+    fn ccd_thickness_override_replaces_the_shape_thickness() {
+        // The ball moves by 1/60 during the timestep, less than the thickness of its shape.
+        let (bodies, mut colliders, wall, ball) = wall_and_ball(1.0);
+        let mut num_toi_computations = 0;
+        assert!(toi(&bodies, &colliders, wall, ball, &mut num_toi_computations).is_none());
+        assert_eq!(num_toi_computations, 0);
+
+        // Thinner colliders need the TOI to be computed.
+        colliders[wall].set_ccd_thickness_override(Some(0.0));
+        colliders[ball].set_ccd_thickness_override(Some(-1.0));
+        assert_eq!(colliders[ball].ccd_thickness(), 0.0);
+        assert!(toi(&bodies, &colliders, wall, ball, &mut num_toi_computations).is_none());
+        assert_eq!(num_toi_computations, 1);
+
+        // Thicker colliders skip the TOI computation even if they would hit.
+        let (bodies, mut colliders, wall, ball) = wall_and_ball(200.0);
+        assert!(toi(&bodies, &colliders, wall, ball, &mut num_toi_computations).is_some());
+        assert_eq!(num_toi_computations, 2);
+        colliders[ball].set_ccd_thickness_override(Some(5.0));
+        assert!(toi(&bodies, &colliders, wall, ball, &mut num_toi_computations).is_none());
+        assert_eq!(num_toi_computations, 2);
+
+        // The collider shape isn’t affected.
+        assert_eq!(colliders[ball].shape().as_ball().unwrap().radius, 0.1);
+    }
+}
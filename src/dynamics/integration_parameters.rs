@@ -136,7 +136,13 @@ pub struct IntegrationParameters {
     ///
     /// This value is implicitly scaled by [`IntegrationParameters::length_unit`].
     pub normalized_allowed_linear_error: Real,
-    /// Maximum amount of penetration the solver will attempt to resolve in one timestep (default: `10.0`).
+    /// Maximum velocity the solver will use to resolve penetrations (default: `10.0m/s`).
+    ///
+    /// This caps the velocity added by the positional correction of contacts, so that deep
+    /// penetrations (e.g. after teleporting a body inside another one) are resolved smoothly over
+    /// several timesteps instead of pushing the bodies apart violently. At most
+    /// `max_corrective_velocity * dt` of penetration is resolved at each timestep. Set it to
+    /// [`Real::MAX`] to disable the limit.
     ///
     /// This value is implicitly scaled by [`IntegrationParameters::length_unit`].
     pub normalized_max_corrective_velocity: Real,
@@ -144,6 +150,15 @@ pub struct IntegrationParameters {
     ///
     /// This value is implicitly scaled by [`IntegrationParameters::length_unit`].
     pub normalized_prediction_distance: Real,
+    /// The prediction distance used for the broad-phase bounding volumes of sensor colliders
+    /// (default: `None`).
+    ///
+    /// Sensors often need a larger margin than solid colliders so that fast objects entering
+    /// them are detected early, while solid colliders benefit from a tight margin for performance.
+    /// If `None`, [`Self::normalized_prediction_distance`] is used for sensors too.
+    ///
+    /// This value is implicitly scaled by [`IntegrationParameters::length_unit`].
+    pub normalized_sensor_prediction_distance: Option<Real>,
     /// The number of solver iterations run by the constraints solver for calculating forces (default: `4`).
     ///
     /// Higher values produce more accurate and stable simulations at the cost of performance.
@@ -302,7 +317,7 @@ impl IntegrationParameters {
         self.normalized_allowed_linear_error * self.length_unit
     }
 
-    /// Maximum amount of penetration the solver will attempt to resolve in one timestep.
+    /// Maximum velocity the solver will use to resolve penetrations.
     ///
     /// This is equal to [`Self::normalized_max_corrective_velocity`] multiplied by
     /// [`Self::length_unit`].
@@ -319,6 +334,14 @@ impl IntegrationParameters {
     pub fn prediction_distance(&self) -> Real {
         self.normalized_prediction_distance * self.length_unit
     }
+
+    /// The prediction distance used for the broad-phase bounding volumes of sensor colliders
+    /// (default: [`Self::prediction_distance`]).
+    pub fn sensor_prediction_distance(&self) -> Real {
+        self.normalized_sensor_prediction_distance
+            .unwrap_or(self.normalized_prediction_distance)
+            * self.length_unit
+    }
 }
 
 impl Default for IntegrationParameters {
@@ -343,6 +366,7 @@ impl Default for IntegrationParameters {
             normalized_allowed_linear_error: 0.001,
             normalized_max_corrective_velocity: 10.0,
             normalized_prediction_distance: 0.002,
+            normalized_sensor_prediction_distance: None,
             max_ccd_substeps: 1,
             length_unit: 1.0,
             #[cfg(feature = "dim3")]
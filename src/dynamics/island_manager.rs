@@ -6,6 +6,26 @@ use crate::geometry::{ColliderSet, NarrowPhase};
 use crate::math::Real;
 use crate::utils::SimdDot;
 
+/// Event generated when a rigid-body falls asleep or wakes up.
+///
+/// Only emitted if enabled with [`IslandManager::set_sleep_events_enabled`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SleepStateChangedEvent {
+    /// The rigid-body that fell asleep or woke up.
+    pub handle: RigidBodyHandle,
+    /// Is the rigid-body now sleeping?
+    pub sleeping: bool,
+}
+
+/// A set of rigid-bodies, as returned by [`IslandManager::islands`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct IslandInfo<'a> {
+    /// The rigid-bodies of this island.
+    pub bodies: &'a [RigidBodyHandle],
+    /// Are the rigid-bodies of this island sleeping?
+    pub sleeping: bool,
+}
+
 /// System that manages which bodies are active (awake) vs sleeping to optimize performance.
 ///
 /// ## Sleeping Optimization
@@ -27,9 +47,16 @@ pub struct IslandManager {
     pub(crate) active_islands: Vec<usize>,
     pub(crate) active_islands_additional_solver_iterations: Vec<usize>,
     active_set_timestamp: u32,
+    // The rigid-bodies that fell asleep together.
+    sleeping_islands: Vec<Vec<RigidBodyHandle>>,
+    sleep_events_enabled: bool,
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pub(crate) sleep_events: Vec<SleepStateChangedEvent>,
     #[cfg_attr(feature = "serde-serialize", serde(skip))]
     can_sleep: Vec<RigidBodyHandle>, // Workspace.
     #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    to_group: Vec<RigidBodyHandle>, // Workspace.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
     stack: Vec<RigidBodyHandle>, // Workspace.
 }
 
@@ -41,11 +68,29 @@ impl IslandManager {
             active_islands: vec![],
             active_islands_additional_solver_iterations: vec![],
             active_set_timestamp: 0,
+            sleeping_islands: vec![],
+            sleep_events_enabled: false,
+            sleep_events: vec![],
             can_sleep: vec![],
+            to_group: vec![],
             stack: vec![],
         }
     }
 
+    /// Enables or disables the emission of [`SleepStateChangedEvent`] whenever a rigid-body
+    /// falls asleep or wakes up.
+    ///
+    /// Events are sent to [`EventHandler::handle_sleep_state_changed_event`](crate::pipeline::EventHandler::handle_sleep_state_changed_event).
+    /// Disabled by default.
+    pub fn set_sleep_events_enabled(&mut self, enabled: bool) {
+        self.sleep_events_enabled = enabled;
+    }
+
+    /// Are [`SleepStateChangedEvent`] emitted whenever a rigid-body falls asleep or wakes up?
+    pub fn sleep_events_enabled(&self) -> bool {
+        self.sleep_events_enabled
+    }
+
     pub(crate) fn num_islands(&self) -> usize {
         self.active_islands.len().saturating_sub(1)
     }
@@ -89,6 +134,25 @@ impl IslandManager {
                 replacement.ids.active_set_id = removed_ids.active_set_id;
             }
         }
+
+        if let Some(island) = self
+            .sleeping_islands
+            .get_mut(removed_ids.sleeping_island_id)
+        {
+            island.retain(|h| *h != removed_handle);
+            if island.is_empty() {
+                let _ = remove_sleeping_island(
+                    &mut self.sleeping_islands,
+                    removed_ids.sleeping_island_id,
+                    bodies,
+                );
+            }
+        }
+
+        // The rigid-body still exists if it was only disabled.
+        if let Some(rb) = bodies.get_mut_internal(removed_handle) {
+            rb.ids.sleeping_island_id = usize::MAX;
+        }
     }
 
     /// Wakes up a sleeping body, forcing it back into the active simulation.
@@ -133,6 +197,93 @@ impl IslandManager {
         }
     }
 
+    /// Wakes up a rigid-body, as well as all the sleeping rigid-bodies connected to it through
+    /// contacts or joints.
+    ///
+    /// Waking up a single body with [`Self::wake_up`] is generally enough for the whole island
+    /// it belongs to to be woken up and solved as a whole during the next timestep. This method
+    /// is useful when the connectivity of sleeping bodies was changed without waking them up,
+    /// e.g., after inserting a joint between two sleeping stacks with `wake_up: false`: all the
+    /// connected bodies are eagerly woken up and put into the active set, so they are merged into
+    /// the same island by the next call to [`PhysicsPipeline::step`](crate::pipeline::PhysicsPipeline::step).
+    ///
+    /// # Cost
+    ///
+    /// This traverses the whole contact and joint graph of the bodies connected to `handle`,
+    /// which can be as large as the scene. Avoid calling it every frame: it is only
+    /// needed right after an operation changing the connectivity of sleeping bodies.
+    pub fn wake_up_and_rejoin(
+        &mut self,
+        bodies: &mut RigidBodySet,
+        colliders: &ColliderSet,
+        narrow_phase: &NarrowPhase,
+        impulse_joints: &ImpulseJointSet,
+        multibody_joints: &MultibodyJointSet,
+        handle: RigidBodyHandle,
+    ) {
+        let mut stack = std::mem::take(&mut self.stack);
+        stack.clear();
+        stack.push(handle);
+
+        // Use a new timestamp for marking the bodies visited by this traversal.
+        self.active_set_timestamp += 1;
+
+        while let Some(h) = stack.pop() {
+            let Some(rb) = bodies.get_mut_internal(h) else {
+                continue;
+            };
+
+            if !rb.is_dynamic() || rb.ids.active_set_timestamp == self.active_set_timestamp {
+                continue;
+            }
+
+            rb.ids.active_set_timestamp = self.active_set_timestamp;
+            push_contacting_bodies(&rb.colliders, colliders, narrow_phase, &mut stack);
+
+            for inter in impulse_joints.attached_enabled_joints(h) {
+                stack.push(crate::utils::select_other((inter.0, inter.1), h));
+            }
+
+            for other in multibody_joints.bodies_attached_with_enabled_joint(h) {
+                stack.push(other);
+            }
+
+            self.wake_up(bodies, h, true);
+        }
+
+        self.stack = stack;
+    }
+
+    /// Iterates through the islands computed during the last timestep.
+    ///
+    /// Each awake island is a set of rigid-bodies solved together by the constraints solver.
+    /// Note that small islands are merged together until they reach
+    /// [`IntegrationParameters::min_island_size`](crate::dynamics::IntegrationParameters::min_island_size)
+    /// bodies, so bodies of the same awake island aren’t necessarily interacting with each other.
+    ///
+    /// Each sleeping island is a set of connected rigid-bodies that fell asleep together. They
+    /// are yielded after the awake islands. Rigid-bodies put to sleep manually aren’t part of
+    /// any island until they wake up and fall asleep again.
+    ///
+    /// Removing rigid-bodies reorders the active set, so the islands are only meaningful until
+    /// a rigid-body is removed or the next timestep.
+    pub fn islands(&self) -> impl Iterator<Item = IslandInfo<'_>> + '_ {
+        let awake = self
+            .active_islands
+            .windows(2)
+            .filter_map(|range| self.active_set.get(range[0]..range[1]))
+            .filter(|island| !island.is_empty())
+            .map(|bodies| IslandInfo {
+                bodies,
+                sleeping: false,
+            });
+        let sleeping = self.sleeping_islands.iter().map(|bodies| IslandInfo {
+            bodies,
+            sleeping: true,
+        });
+        awake.chain(sleeping)
+    }
+
     pub(crate) fn active_island(&self, island_id: usize) -> &[RigidBodyHandle] {
         let island_range = self.active_islands[island_id]..self.active_islands[island_id + 1];
         &self.active_set[island_range]
@@ -198,6 +349,11 @@ impl IslandManager {
                 dt,
             );
 
+            // Bodies moved by a kinematic path must keep following it, even during pauses.
+            if rb.follows_kinematic_path() {
+                rb.activation.time_since_can_sleep = 0.0;
+            }
+
             if rb.activation.time_since_can_sleep >= rb.activation.time_until_sleep {
                 // Mark them as sleeping for now. This will
                 // be set to false during the graph traversal
@@ -209,32 +365,6 @@ impl IslandManager {
             }
         }
 
-        // Read all the contacts and push objects touching touching this rigid-body.
-        #[inline]
-        fn push_contacting_bodies(
-            rb_colliders: &RigidBodyColliders,
-            colliders: &ColliderSet,
-            narrow_phase: &NarrowPhase,
-            stack: &mut Vec<RigidBodyHandle>,
-        ) {
-            for collider_handle in &rb_colliders.0 {
-                for inter in narrow_phase.contact_pairs_with(*collider_handle) {
-                    for manifold in &inter.manifolds {
-                        if !manifold.data.solver_contacts.is_empty() {
-                            let other = crate::utils::select_other(
-                                (inter.collider1, inter.collider2),
-                                *collider_handle,
-                            );
-                            if let Some(other_body) = colliders[other].parent {
-                                stack.push(other_body.handle);
-                            }
-                            break;
-                        }
-                    }
-                }
-            }
-        }
-
         //        println!("Selection: {}", Instant::now() - t);
 
         //        let t = Instant::now();
@@ -297,6 +427,14 @@ impl IslandManager {
                 self.stack.push(other);
             }
 
+            if self.sleep_events_enabled && rb.ids.reported_sleeping {
+                rb.ids.reported_sleeping = false;
+                self.sleep_events.push(SleepStateChangedEvent {
+                    handle,
+                    sleeping: false,
+                });
+            }
+
             rb.activation.wake_up(false);
             rb.ids.active_island_id = self.active_islands.len() - 1;
             rb.ids.active_set_id = self.active_set.len();
@@ -310,21 +448,148 @@ impl IslandManager {
         self.active_islands_additional_solver_iterations
             .push(additional_solver_iterations);
         self.active_islands.push(self.active_set.len());
+
+        #[cfg(feature = "enhanced-determinism")]
+        self.sort_islands_by_smallest_handle(bodies);
+
         //        println!(
         //            "Extraction: {}, num islands: {}",
         //            Instant::now() - t,
         //            self.active_islands.len() - 1
         //        );
 
+        // The sleeping islands containing a rigid-body that is awake, or was awake until now,
+        // are no longer valid. Their bodies that are still sleeping are grouped again below.
+        self.to_group.clear();
+        for handle in self.active_set.iter().chain(&self.can_sleep) {
+            let island_id = bodies[*handle].ids.sleeping_island_id;
+            if island_id != usize::MAX {
+                let island = remove_sleeping_island(&mut self.sleeping_islands, island_id, bodies);
+                self.to_group.extend(island);
+            }
+        }
+
         // Actually put to sleep bodies which have not been detected as awake.
         for handle in &self.can_sleep {
             let rb = bodies.index_mut_internal(*handle);
             if rb.activation.sleeping {
                 rb.vels = RigidBodyVelocity::zero();
                 rb.activation.sleep();
+
+                if self.sleep_events_enabled && !rb.ids.reported_sleeping {
+                    rb.ids.reported_sleeping = true;
+                    self.sleep_events.push(SleepStateChangedEvent {
+                        handle: *handle,
+                        sleeping: true,
+                    });
+                }
+            }
+        }
+
+        self.to_group.extend_from_slice(&self.can_sleep);
+        self.group_sleeping_bodies(
+            bodies,
+            colliders,
+            narrow_phase,
+            impulse_joints,
+            multibody_joints,
+        );
+    }
+
+    /// Groups the sleeping rigid-bodies of `self.to_group` that aren’t part of any sleeping island
+    /// into new sleeping islands of connected rigid-bodies.
+    fn group_sleeping_bodies(
+        &mut self,
+        bodies: &mut RigidBodySet,
+        colliders: &ColliderSet,
+        narrow_phase: &NarrowPhase,
+        impulse_joints: &ImpulseJointSet,
+        multibody_joints: &MultibodyJointSet,
+    ) {
+        for root in self.to_group.drain(..) {
+            let mut island = vec![];
+            self.stack.clear();
+            self.stack.push(root);
+
+            while let Some(handle) = self.stack.pop() {
+                let Some(rb) = bodies.get_mut_internal(handle) else {
+                    continue;
+                };
+
+                if !rb.is_enabled()
+                    || !rb.is_sleeping()
+                    || !rb.is_dynamic_or_kinematic()
+                    || rb.ids.sleeping_island_id != usize::MAX
+                {
+                    continue;
+                }
+
+                rb.ids.sleeping_island_id = self.sleeping_islands.len();
+                island.push(handle);
+                push_contacting_bodies(&rb.colliders, colliders, narrow_phase, &mut self.stack);
+
+                for inter in impulse_joints.attached_enabled_joints(handle) {
+                    let other = crate::utils::select_other((inter.0, inter.1), handle);
+                    self.stack.push(other);
+                }
+
+                for other in multibody_joints.bodies_attached_with_enabled_joint(handle) {
+                    self.stack.push(other);
+                }
+            }
+
+            if !island.is_empty() {
+                self.sleeping_islands.push(island);
             }
         }
     }
+
+    /// Reorders the active islands by increasing smallest rigid-body handle.
+    ///
+    /// The islands are discovered in an order that depends on the history of the active set
+    /// (e.g. the order bodies were woken up). Sorting them makes the order of the islands only
+    /// depend on their content. The order of the bodies within each island isn’t changed.
+    #[cfg(feature = "enhanced-determinism")]
+    fn sort_islands_by_smallest_handle(&mut self, bodies: &mut RigidBodySet) {
+        let mut order: Vec<_> = (0..self.num_islands())
+            .map(|island_id| {
+                let smallest_handle = self
+                    .active_island(island_id)
+                    .iter()
+                    .map(|handle| handle.into_raw_parts())
+                    .min();
+                (smallest_handle, island_id)
+            })
+            .collect();
+
+        if order.is_sorted() {
+            return;
+        }
+
+        order.sort_unstable();
+
+        let old_active_set = std::mem::take(&mut self.active_set);
+        let old_islands = std::mem::take(&mut self.active_islands);
+        let old_additional_solver_iterations =
+            std::mem::take(&mut self.active_islands_additional_solver_iterations);
+        self.active_islands.push(0);
+
+        for (new_island_id, (_, old_island_id)) in order.into_iter().enumerate() {
+            let island_range = old_islands[old_island_id]..old_islands[old_island_id + 1];
+
+            for (offset, handle) in old_active_set[island_range].iter().enumerate() {
+                let rb = bodies.index_mut_internal(*handle);
+                rb.ids.active_island_id = new_island_id;
+                rb.ids.active_set_id = self.active_set.len();
+                rb.ids.active_set_offset = offset as u32;
+                self.active_set.push(*handle);
+            }
+
+            self.active_islands.push(self.active_set.len());
+            self.active_islands_additional_solver_iterations
+                .push(old_additional_solver_iterations[old_island_id]);
+        }
+    }
 }
 
 fn update_energy(
@@ -355,3 +620,55 @@ fn update_energy(
         activation.time_since_can_sleep = 0.0;
     }
 }
+
+/// Removes the sleeping island `island_id`, returning its rigid-bodies.
+fn remove_sleeping_island(
+    sleeping_islands: &mut Vec<Vec<RigidBodyHandle>>,
+    island_id: usize,
+    bodies: &mut RigidBodySet,
+) -> Vec<RigidBodyHandle> {
+    let island = sleeping_islands.swap_remove(island_id);
+
+    for handle in &island {
+        if let Some(rb) = bodies.get_mut_internal(*handle) {
+            rb.ids.sleeping_island_id = usize::MAX;
+        }
+    }
+
+    // Update the id of the island that took the place of the removed one.
+    if let Some(moved) = sleeping_islands.get(island_id) {
+        for handle in moved {
+            if let Some(rb) = bodies.get_mut_internal(*handle) {
+                rb.ids.sleeping_island_id = island_id;
+            }
+        }
+    }
+
+    island
+}
+
+// Read all the contacts and push objects touching touching this rigid-body.
+#[inline]
+fn push_contacting_bodies(
+    rb_colliders: &RigidBodyColliders,
+    colliders: &ColliderSet,
+    narrow_phase: &NarrowPhase,
+    stack: &mut Vec<RigidBodyHandle>,
+) {
+    for collider_handle in &rb_colliders.0 {
+        for inter in narrow_phase.contact_pairs_with(*collider_handle) {
+            for manifold in &inter.manifolds {
+                if !manifold.data.solver_contacts.is_empty() {
+                    let other = crate::utils::select_other(
+                        (inter.collider1, inter.collider2),
+                        *collider_handle,
+                    );
+                    if let Some(other_body) = colliders[other].parent {
+                        stack.push(other_body.handle);
+                    }
+                    break;
+                }
+            }
+        }
+    }
+}
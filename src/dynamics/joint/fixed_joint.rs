@@ -44,6 +44,31 @@ impl FixedJoint {
         self
     }
 
+    /// The user-defined data associated to this joint.
+    pub fn user_data(&self) -> u128 {
+        self.data.user_data
+    }
+
+    /// Sets the user-defined data associated to this joint.
+    pub fn set_user_data(&mut self, data: u128) -> &mut Self {
+        self.data.set_user_data(data);
+        self
+    }
+
+    /// The compliance (inverse stiffness) of this fixed joint.
+    #[must_use]
+    pub fn compliance(&self) -> Real {
+        self.data.compliance
+    }
+
+    /// Sets the compliance (inverse stiffness) of this fixed joint.
+    ///
+    /// See [`GenericJoint::set_compliance`] for details.
+    pub fn set_compliance(&mut self, compliance: Real) -> &mut Self {
+        self.data.set_compliance(compliance);
+        self
+    }
+
     /// The joint’s frame, expressed in the first rigid-body’s local-space.
     #[must_use]
     pub fn local_frame1(&self) -> &Isometry<Real> {
@@ -117,6 +142,22 @@ impl FixedJointBuilder {
         self
     }
 
+    /// Sets the user-defined data associated to this joint.
+    #[must_use]
+    pub fn user_data(mut self, data: u128) -> Self {
+        self.0.set_user_data(data);
+        self
+    }
+
+    /// Sets the compliance (inverse stiffness) of the fixed joint.
+    ///
+    /// See [`GenericJoint::set_compliance`] for details.
+    #[must_use]
+    pub fn compliance(mut self, compliance: Real) -> Self {
+        self.0.set_compliance(compliance);
+        self
+    }
+
     /// Sets the joint’s frame, expressed in the first rigid-body’s local-space.
     #[must_use]
     pub fn local_frame1(mut self, local_frame: Isometry<Real>) -> Self {
@@ -197,6 +197,13 @@ pub struct JointMotor {
     pub damping: Real,
     /// Maximum force the motor can apply (Newtons for prismatic, Nm for revolute).
     pub max_force: Real,
+    /// Maximum velocity the motor can drive the joint at (units/sec for prismatic, rad/sec for
+    /// revolute).
+    ///
+    /// This clamps the velocity the motor aims for, including the part derived from the position
+    /// error. This lets a position-based motor ramp toward its target like a servo instead of
+    /// reaching it at full speed. Set to `Real::MAX` by default (no clamping).
+    pub max_vel: Real,
     /// Internal: current impulse being applied.
     pub impulse: Real,
     /// Force-based or acceleration-based motor model.
@@ -211,6 +218,7 @@ impl Default for JointMotor {
             stiffness: 0.0,
             damping: 0.0,
             max_force: Real::MAX,
+            max_vel: Real::MAX,
             impulse: 0.0,
             model: MotorModel::AccelerationBased,
         }
@@ -229,6 +237,7 @@ impl JointMotor {
             // keep_lhs,
             target_pos: self.target_pos,
             target_vel: self.target_vel,
+            max_vel: self.max_vel,
             max_impulse: self.max_force * dt,
         }
     }
@@ -284,6 +293,18 @@ pub struct GenericJoint {
     pub contacts_enabled: bool,
     /// Whether the joint is enabled.
     pub enabled: JointEnabled,
+    /// The force magnitude above which this joint breaks, if any.
+    ///
+    /// See [`GenericJoint::set_breaking_force`] for details.
+    pub breaking_force: Option<Real>,
+    /// The torque magnitude above which this joint breaks, if any.
+    ///
+    /// See [`GenericJoint::set_breaking_torque`] for details.
+    pub breaking_torque: Option<Real>,
+    /// The compliance (inverse stiffness) of this joint’s locked and limited degrees of freedom.
+    ///
+    /// See [`GenericJoint::set_compliance`] for details.
+    pub compliance: Real,
     /// User-defined data associated to this joint.
     pub user_data: u128,
 }
@@ -301,6 +322,9 @@ impl Default for GenericJoint {
             motors: [JointMotor::default(); SPATIAL_DIM],
             contacts_enabled: true,
             enabled: JointEnabled::Enabled,
+            breaking_force: None,
+            breaking_torque: None,
+            compliance: 0.0,
             user_data: 0,
         }
     }
@@ -316,7 +340,7 @@ impl GenericJoint {
     #[cfg(feature = "simd-is-enabled")]
     /// Can this joint use SIMD-accelerated constraint formulations?
     pub(crate) fn supports_simd_constraints(&self) -> bool {
-        self.limit_axes.is_empty() && self.motor_axes.is_empty()
+        self.limit_axes.is_empty() && self.motor_axes.is_empty() && self.compliance == 0.0
     }
 
     #[doc(hidden)]
@@ -438,6 +462,60 @@ impl GenericJoint {
         self
     }
 
+    /// The user-defined data associated to this joint.
+    pub fn user_data(&self) -> u128 {
+        self.user_data
+    }
+
+    /// Sets the user-defined data associated to this joint.
+    ///
+    /// This can be used to store a stable external identifier, e.g., an ECS entity, since
+    /// joint handles may be reused after the joint is removed.
+    pub fn set_user_data(&mut self, data: u128) -> &mut Self {
+        self.user_data = data;
+        self
+    }
+
+    /// Sets the force magnitude above which this joint breaks.
+    ///
+    /// After each timestep, the force applied by the solver to enforce the joint’s linear
+    /// degrees of freedom (including its limits and motors) is compared to this threshold. If it is exceeded, the joint is removed
+    /// at the end of the timestep and a [`JointBrokenEvent`](crate::dynamics::JointBrokenEvent)
+    /// is emitted. Set to `None` (the default) to make the joint unbreakable.
+    ///
+    /// This only applies to impulse joints. It is ignored by multibody joints.
+    pub fn set_breaking_force(&mut self, breaking_force: Option<Real>) -> &mut Self {
+        self.breaking_force = breaking_force;
+        self
+    }
+
+    /// Sets the torque magnitude above which this joint breaks.
+    ///
+    /// This is similar to [`Self::set_breaking_force`], but for the torque applied by the
+    /// solver to enforce the joint’s angular degrees of freedom.
+    pub fn set_breaking_torque(&mut self, breaking_torque: Option<Real>) -> &mut Self {
+        self.breaking_torque = breaking_torque;
+        self
+    }
+
+    /// Sets the compliance (inverse stiffness) of this joint’s locked and limited degrees of freedom.
+    ///
+    /// With a zero compliance (the default), the joint is rigid, up to the global softness set by
+    /// [`IntegrationParameters::joint_natural_frequency`](crate::dynamics::IntegrationParameters::joint_natural_frequency)
+    /// and [`IntegrationParameters::joint_damping_ratio`](crate::dynamics::IntegrationParameters::joint_damping_ratio).
+    /// A positive compliance is interpreted as in XPBD: the joint behaves like a spring of stiffness
+    /// `1 / compliance`, so a constant force `f` applied along a locked axis stretches it by
+    /// `compliance * f`. It is expressed in meters per newton for linear axes, and in radians per
+    /// newton-meter for angular axes. Unlike the global softness, this doesn’t depend on the
+    /// timestep length.
+    ///
+    /// Motors are not affected. This only applies to impulse joints between rigid-bodies that
+    /// aren’t multibody links. It is ignored by multibody joints.
+    pub fn set_compliance(&mut self, compliance: Real) -> &mut Self {
+        self.compliance = compliance;
+        self
+    }
+
     /// The joint limits along the specified axis.
     #[must_use]
     pub fn limits(&self, axis: JointAxis) -> Option<&JointLimits<Real>> {
@@ -508,6 +586,14 @@ impl GenericJoint {
         self
     }
 
+    /// Sets the maximum velocity the motor can drive the joint at along the specified axis.
+    ///
+    /// The sign of `max_vel` is ignored.
+    pub fn set_motor_max_velocity(&mut self, axis: JointAxis, max_vel: Real) -> &mut Self {
+        self.motors[axis as usize].max_vel = max_vel.abs();
+        self
+    }
+
     /// The motor affecting the joint’s degree of freedom along the specified axis.
     #[must_use]
     pub fn motor(&self, axis: JointAxis) -> Option<&JointMotor> {
@@ -659,6 +745,33 @@ impl GenericJointBuilder {
         self
     }
 
+    /// Sets the force magnitude above which the joint breaks.
+    ///
+    /// See [`GenericJoint::set_breaking_force`] for details.
+    #[must_use]
+    pub fn breaking_force(mut self, breaking_force: Real) -> Self {
+        self.0.breaking_force = Some(breaking_force);
+        self
+    }
+
+    /// Sets the torque magnitude above which the joint breaks.
+    ///
+    /// See [`GenericJoint::set_breaking_torque`] for details.
+    #[must_use]
+    pub fn breaking_torque(mut self, breaking_torque: Real) -> Self {
+        self.0.breaking_torque = Some(breaking_torque);
+        self
+    }
+
+    /// Sets the compliance (inverse stiffness) of the joint.
+    ///
+    /// See [`GenericJoint::set_compliance`] for details.
+    #[must_use]
+    pub fn compliance(mut self, compliance: Real) -> Self {
+        self.0.compliance = compliance;
+        self
+    }
+
     /// Sets the joint’s frame, expressed in the first rigid-body’s local-space.
     #[must_use]
     pub fn local_frame1(mut self, local_frame: Isometry<Real>) -> Self {
@@ -765,6 +878,13 @@ impl GenericJointBuilder {
         self
     }
 
+    /// Sets the maximum velocity the motor can drive the joint at along the specified axis.
+    #[must_use]
+    pub fn motor_max_velocity(mut self, axis: JointAxis, max_vel: Real) -> Self {
+        self.0.set_motor_max_velocity(axis, max_vel);
+        self
+    }
+
     /// An arbitrary user-defined 128-bit integer associated to the joints built by this builder.
     pub fn user_data(mut self, data: u128) -> Self {
         self.0.user_data = data;
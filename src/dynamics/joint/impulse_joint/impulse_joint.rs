@@ -1,5 +1,7 @@
-use crate::dynamics::{GenericJoint, ImpulseJointHandle, RigidBodyHandle};
-use crate::math::{Real, SpacialVector};
+use crate::dynamics::{GenericJoint, ImpulseJointHandle, RigidBodyHandle, RigidBodySet};
+use crate::math::{
+    ANG_DIM, AngVector, DIM, Isometry, Real, SPATIAL_DIM, SpacialVector, UnitVector, Vector,
+};
 
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, PartialEq)]
@@ -16,6 +18,224 @@ pub struct ImpulseJoint {
     /// The impulses applied by this joint.
     pub impulses: SpacialVector<Real>,
 
+    /// The forces applied by this joint during the last timestep it was simulated.
+    pub(crate) forces: SpacialVector<Real>,
+
     // A joint needs to know its handle to simplify its removal.
     pub(crate) handle: ImpulseJointHandle,
 }
+
+impl ImpulseJoint {
+    /// The rotation angle (in radians) of one of the joint’s local frames beyond which
+    /// [`Self::set_local_frame1`] and its siblings reset the joint’s accumulated impulses.
+    pub const IMPULSES_RESET_ANGLE: Real = 0.1;
+
+    /// Sets the joint’s frame, expressed in the first rigid-body’s local-space.
+    ///
+    /// Unlike replacing the joint, this keeps its accumulated [`Self::impulses`], unless the
+    /// frame is rotated by more than [`Self::IMPULSES_RESET_ANGLE`]. In that case the impulses,
+    /// which are expressed along the axes of the joint’s frames, no longer match the constraint
+    /// and are reset to zero. Translating the frame alone never resets them.
+    ///
+    /// The joint’s constraints are rebuilt from its frames at each timestep so the change is
+    /// taken into account at the next step. Use [`ImpulseJointSet::get_mut`] with `wake_up` set
+    /// to `true` to make sure the attached rigid-bodies are awake to react to it.
+    ///
+    /// [`ImpulseJointSet::get_mut`]: crate::dynamics::ImpulseJointSet::get_mut
+    pub fn set_local_frame1(&mut self, local_frame: Isometry<Real>) -> &mut Self {
+        self.modify_frames(|data| {
+            data.set_local_frame1(local_frame);
+        })
+    }
+
+    /// Sets the joint’s frame, expressed in the second rigid-body’s local-space.
+    ///
+    /// See [`Self::set_local_frame1`] for the effect on the joint’s accumulated impulses.
+    pub fn set_local_frame2(&mut self, local_frame: Isometry<Real>) -> &mut Self {
+        self.modify_frames(|data| {
+            data.set_local_frame2(local_frame);
+        })
+    }
+
+    /// Sets the principal axis of this joint, expressed in the first rigid-body’s local-space.
+    ///
+    /// This is the free axis of revolute joints and the sliding axis of prismatic joints.
+    /// See [`Self::set_local_frame1`] for the effect on the joint’s accumulated impulses.
+    pub fn set_local_axis1(&mut self, local_axis: UnitVector<Real>) -> &mut Self {
+        self.modify_frames(|data| {
+            data.set_local_axis1(local_axis);
+        })
+    }
+
+    /// Sets the principal axis of this joint, expressed in the second rigid-body’s local-space.
+    ///
+    /// This is the free axis of revolute joints and the sliding axis of prismatic joints.
+    /// See [`Self::set_local_frame1`] for the effect on the joint’s accumulated impulses.
+    pub fn set_local_axis2(&mut self, local_axis: UnitVector<Real>) -> &mut Self {
+        self.modify_frames(|data| {
+            data.set_local_axis2(local_axis);
+        })
+    }
+
+    fn modify_frames(&mut self, modify: impl FnOnce(&mut GenericJoint)) -> &mut Self {
+        let rot1 = self.data.local_frame1.rotation;
+        let rot2 = self.data.local_frame2.rotation;
+        modify(&mut self.data);
+
+        let angle1 = (rot1.inverse() * self.data.local_frame1.rotation).angle();
+        let angle2 = (rot2.inverse() * self.data.local_frame2.rotation).angle();
+        if angle1.abs().max(angle2.abs()) > Self::IMPULSES_RESET_ANGLE {
+            self.impulses = na::zero();
+        }
+
+        self
+    }
+
+    /// The force applied by this joint to enforce its linear degrees of freedom.
+    ///
+    /// This is computed from the impulses applied by the constraints solver during the last
+    /// timestep this joint was simulated, including the impulses of its limits and motors.
+    /// It is expressed in world-space.
+    pub fn force(&self) -> Vector<Real> {
+        self.forces.fixed_rows::<DIM>(0).into_owned()
+    }
+
+    /// The torque applied by this joint to enforce its angular degrees of freedom.
+    ///
+    /// This is computed from the impulses applied by the constraints solver during the last
+    /// timestep this joint was simulated, including the impulses of its limits and motors.
+    #[cfg(feature = "dim2")]
+    pub fn torque(&self) -> AngVector<Real> {
+        self.forces[DIM]
+    }
+
+    /// The torque applied by this joint to enforce its angular degrees of freedom.
+    ///
+    /// This is computed from the impulses applied by the constraints solver during the last
+    /// timestep this joint was simulated, including the impulses of its limits and motors.
+    /// It is expressed in world-space.
+    #[cfg(feature = "dim3")]
+    pub fn torque(&self) -> AngVector<Real> {
+        self.forces.fixed_rows::<ANG_DIM>(DIM).into_owned()
+    }
+
+    /// Converts the impulses written back by the solver into world-space forces.
+    ///
+    /// The `inv_dt` is the inverse of the length of the solver substep the joint’s impulses
+    /// were computed for.
+    pub(crate) fn update_forces(&mut self, inv_dt: Real, bodies: &RigidBodySet) {
+        let (Some(rb1), Some(rb2)) = (bodies.get(self.body1), bodies.get(self.body2)) else {
+            return;
+        };
+        let frame1 = rb1.position() * self.data.local_frame1;
+        let frame2 = rb2.position() * self.data.local_frame2;
+
+        let locked_axes = self.data.locked_axes.bits();
+        let limit_axes = self.data.limit_axes.bits();
+        let motor_axes = self.data.motor_axes.bits();
+        let coupled_axes = self.data.coupled_axes.bits();
+
+        // Sum the impulses applied along each axis of the first frame. Coupled axes share
+        // a single constraint, so their impulses are accounted for separately below.
+        let mut impulses = SpacialVector::<Real>::zeros();
+        let mut coupled_impulses = SpacialVector::<Real>::zeros();
+
+        for i in 0..SPATIAL_DIM {
+            let mut impulse = 0.0;
+            if locked_axes & (1 << i) != 0 {
+                impulse += self.impulses[i];
+            }
+            if limit_axes & (1 << i) != 0 {
+                impulse += self.data.limits[i].impulse;
+            }
+            if motor_axes & (1 << i) != 0 {
+                impulse += self.data.motors[i].impulse;
+            }
+
+            if coupled_axes & (1 << i) == 0 {
+                impulses[i] = impulse;
+            } else {
+                coupled_impulses[i] = impulse;
+            }
+        }
+
+        let mut force = frame1.rotation * impulses.fixed_rows::<DIM>(0).into_owned();
+
+        // Coupled linear axes are constrained along the direction between the anchors,
+        // projected on these axes.
+        let coupled_lin_impulse = coupled_impulses.fixed_rows::<DIM>(0).sum();
+        if coupled_lin_impulse != 0.0 {
+            let lin_err = frame2.translation.vector - frame1.translation.vector;
+            let mut dir = Vector::zeros();
+            for i in 0..DIM {
+                if coupled_axes & (1 << i) != 0 {
+                    let axis = frame1.rotation * Vector::ith(i, 1.0);
+                    dir += axis * axis.dot(&lin_err);
+                }
+            }
+            if let Some(dir) = dir.try_normalize(1.0e-5) {
+                force += dir * coupled_lin_impulse;
+            }
+        }
+
+        #[cfg(feature = "dim2")]
+        let torque = impulses[DIM];
+        #[cfg(feature = "dim3")]
+        let torque = {
+            let mut torque = frame1.rotation * impulses.fixed_rows::<ANG_DIM>(DIM).into_owned();
+
+            // Coupled angular axes are constrained along the rotation between the first frame’s
+            // uncoupled axis and its counterpart in the second frame.
+            let coupled_ang_impulse = coupled_impulses.fixed_rows::<ANG_DIM>(DIM).sum();
+            if coupled_ang_impulse != 0.0 {
+                let ang_coupled_axes = coupled_axes >> DIM;
+                let not_coupled_index = ang_coupled_axes.trailing_ones() as usize;
+                let axis1 = frame1.rotation * Vector::ith(not_coupled_index, 1.0);
+                let axis2 = frame2.rotation * Vector::ith(not_coupled_index, 1.0);
+                if let Some(dir) = axis1.cross(&axis2).try_normalize(1.0e-5) {
+                    torque += dir * coupled_ang_impulse;
+                }
+            }
+
+            torque
+        };
+
+        self.forces
+            .fixed_rows_mut::<DIM>(0)
+            .copy_from(&(force * inv_dt));
+        #[cfg(feature = "dim2")]
+        {
+            self.forces[DIM] = torque * inv_dt;
+        }
+        #[cfg(feature = "dim3")]
+        self.forces
+            .fixed_rows_mut::<ANG_DIM>(DIM)
+            .copy_from(&(torque * inv_dt));
+    }
+
+    /// Does the force or torque applied by this joint exceed its breaking thresholds?
+    pub(crate) fn exceeds_breaking_thresholds(&self) -> bool {
+        self.data
+            .breaking_force
+            .is_some_and(|max| self.force().norm() > max)
+            || self
+                .data
+                .breaking_torque
+                .is_some_and(|max| self.forces.fixed_rows::<ANG_DIM>(DIM).norm() > max)
+    }
+}
+
+/// Event generated when an impulse joint breaks because the force or torque it applied exceeded
+/// its [`GenericJoint::breaking_force`] or [`GenericJoint::breaking_torque`].
+///
+/// The joint has already been removed from the [`ImpulseJointSet`](crate::dynamics::ImpulseJointSet)
+/// when this event is emitted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct JointBrokenEvent {
+    /// The (now invalid) handle of the joint that broke.
+    pub handle: ImpulseJointHandle,
+    /// Handle to the first body that was attached to the joint.
+    pub body1: RigidBodyHandle,
+    /// Handle to the second body that was attached to the joint.
+    pub body2: RigidBodyHandle,
+}
@@ -336,6 +336,7 @@ impl ImpulseJointSet {
             body2,
             data,
             impulses: na::zero(),
+            forces: na::zero(),
             handle: ImpulseJointHandle(handle),
         };
 
@@ -488,6 +488,188 @@ impl Multibody {
             });
     }
 
+    /// Computes the generalized forces needed to obtain the given generalized accelerations
+    /// (inverse dynamics).
+    ///
+    /// The returned forces (torques for the angular degrees of freedom) are the ones the joint
+    /// motors would have to apply so that the multibody accelerates at `accelerations`, given its
+    /// current generalized velocities, `gravity`, and the damping of its degrees of freedom. They
+    /// account for the inertia of the links, and the Coriolis, centrifugal, and gyroscopic
+    /// effects. The gravity is multiplied by the [`RigidBody::gravity_scale`] of each link. Other
+    /// external forces applied to the rigid-bodies, as well as contacts and joint limits, are not
+    /// taken into account.
+    ///
+    /// Both `accelerations` and the result are ordered like [`Self::generalized_velocity`]. This
+    /// relies on the link poses and jacobians computed by the last call to
+    /// [`Self::forward_kinematics`], and on the world-space mass properties of the rigid-bodies.
+    ///
+    /// # Panics
+    /// Panics if `accelerations` doesn’t have exactly [`Self::ndofs`] elements.
+    pub fn inverse_dynamics(
+        &self,
+        bodies: &RigidBodySet,
+        gravity: &Vector<Real>,
+        accelerations: &[Real],
+    ) -> DVector<Real> {
+        assert_eq!(
+            accelerations.len(),
+            self.ndofs,
+            "The number of generalized accelerations must match the number of degrees of freedom."
+        );
+
+        let accelerations = DVectorView::from_slice(accelerations, self.ndofs);
+        let mut forces = DVector::zeros(self.ndofs);
+        // The velocities of the links, and their accelerations due to these velocities only.
+        let mut vels: Vec<RigidBodyVelocity<Real>> = Vec::with_capacity(self.links.len());
+        let mut accs: Vec<RigidBodyVelocity<Real>> = Vec::with_capacity(self.links.len());
+
+        for (i, link) in self.links.iter().enumerate() {
+            let rb = &bodies[link.rigid_body];
+            let joint_velocity = link
+                .joint
+                .jacobian_mul_coordinates(&self.velocities.as_slice()[link.assembly_id..]);
+
+            // Same as the velocities computed by `Self::update_dynamics` and the accelerations
+            // computed by `Self::update_acceleration`.
+            let vel;
+            let mut acc = RigidBodyVelocity::zero();
+
+            if i != 0 {
+                let parent_id = link.parent_internal_id;
+                let parent_link = &self.links[parent_id];
+                let parent_rb = &bodies[parent_link.rigid_body];
+                let parent_vel = vels[parent_id];
+                let joint_velocity = joint_velocity.transformed(
+                    &(parent_link.local_to_world.rotation * link.joint.data.local_frame1.rotation),
+                );
+
+                let mut link_vel = parent_vel + joint_velocity;
+                let shift = rb.mprops.world_com - parent_rb.mprops.world_com;
+                link_vel.linvel += parent_vel.angvel.gcross(shift);
+                link_vel.linvel += joint_velocity.angvel.gcross(link.shift23);
+                vel = link_vel;
+
+                acc += accs[parent_id];
+                acc.linvel += 2.0 * parent_vel.angvel.gcross(joint_velocity.linvel);
+                #[cfg(feature = "dim3")]
+                {
+                    acc.angvel += parent_vel.angvel.cross(&joint_velocity.angvel);
+                }
+
+                acc.linvel += parent_vel
+                    .angvel
+                    .gcross(parent_vel.angvel.gcross(link.shift02));
+                acc.linvel += accs[parent_id].angvel.gcross(link.shift02);
+            } else {
+                vel = joint_velocity;
+            }
+
+            acc.linvel += vel.angvel.gcross(vel.angvel.gcross(link.shift23));
+            acc.linvel += acc.angvel.gcross(link.shift23);
+
+            vels.push(vel);
+            accs.push(acc);
+
+            // Add the accelerations due to the generalized accelerations.
+            let body_jacobian = &self.body_jacobians[i];
+            let jacobian_acc = body_jacobian * accelerations;
+            let linacc = acc.linvel + jacobian_acc.fixed_rows::<DIM>(0);
+            #[cfg(feature = "dim2")]
+            let angacc = acc.angvel + jacobian_acc[DIM];
+            #[cfg(feature = "dim3")]
+            let angacc = acc.angvel + jacobian_acc.fixed_rows::<ANG_DIM>(DIM);
+
+            let gyroscopic;
+            let rb_inertia = rb.mprops.effective_angular_inertia();
+            let rb_mass = rb.mprops.effective_mass();
+
+            #[cfg(feature = "dim3")]
+            {
+                gyroscopic = vel.angvel.cross(&(rb_inertia * vel.angvel));
+            }
+            #[cfg(feature = "dim2")]
+            {
+                gyroscopic = 0.0;
+            }
+
+            let weight = gravity.component_mul(&rb_mass) * rb.forces.gravity_scale;
+            let force = Force::new(
+                rb_mass.component_mul(&linacc) - weight,
+                rb_inertia * angacc + gyroscopic,
+            );
+            forces.gemv_tr(1.0, body_jacobian, force.as_vector(), 1.0);
+        }
+
+        forces.cmpy(
+            1.0,
+            &self.damping.rows(0, self.ndofs),
+            &self.velocities.rows(0, self.ndofs),
+            1.0,
+        );
+        forces
+    }
+
+    /// The joint-space mass matrix of this multibody.
+    ///
+    /// This is the matrix `M` such that the kinetic energy of the multibody is
+    /// `qdot.transpose() * M * qdot / 2`, where `qdot` are the generalized velocities. Its rows
+    /// and columns are ordered like [`Self::generalized_velocity`]. This relies on the body
+    /// jacobians computed by the last call to [`Self::forward_kinematics`], and on the
+    /// world-space mass properties of the rigid-bodies.
+    pub fn mass_matrix(&self, bodies: &RigidBodySet) -> DMatrix<Real> {
+        let mut mass_matrix = DMatrix::zeros(self.ndofs, self.ndofs);
+
+        for (link, body_jacobian) in self.links.iter().zip(self.body_jacobians.iter()) {
+            let rb = &bodies[link.rigid_body];
+            let rb_mass = rb.mprops.effective_mass();
+            let rb_inertia = rb.mprops.effective_angular_inertia().into_matrix();
+            let rb_mass_matrix = concat_rb_mass_matrix(rb_mass, rb_inertia);
+            mass_matrix.quadform(1.0, &rb_mass_matrix, body_jacobian, 1.0);
+        }
+
+        mass_matrix
+    }
+
+    /// Computes the generalized accelerations resulting from the given generalized forces
+    /// (forward dynamics), without stepping the simulation.
+    ///
+    /// This is the inverse of [`Self::inverse_dynamics`]: the accelerations account for
+    /// `forces` (torques for the angular degrees of freedom), `gravity`, the Coriolis,
+    /// centrifugal, and gyroscopic effects, and the damping of the degrees of freedom.
+    /// Contacts and other external forces applied to the rigid-bodies are not included.
+    /// Kinematic degrees of freedom are treated like the other ones.
+    ///
+    /// Both `forces` and the result are ordered like [`Self::generalized_velocity`]. Returns
+    /// `None` if the mass matrix of this multibody isn’t invertible (e.g. if some of its links
+    /// have no mass).
+    ///
+    /// # Joint limits and motors
+    /// Joint limits and motors are always **ignored**, there is no option to include them: they
+    /// are velocity constraints resolved by the constraints solver during a timestep, not
+    /// forces, so they have no well-defined contribution to an instantaneous acceleration. The
+    /// result is the acceleration of the unconstrained multibody. To predict the effect of a
+    /// motor, add the force you expect it to apply to `forces`.
+    ///
+    /// # Panics
+    /// Panics if `forces` doesn’t have exactly [`Self::ndofs`] elements.
+    pub fn forward_dynamics(
+        &self,
+        bodies: &RigidBodySet,
+        gravity: &Vector<Real>,
+        forces: &[Real],
+    ) -> Option<DVector<Real>> {
+        assert_eq!(
+            forces.len(),
+            self.ndofs,
+            "The number of generalized forces must match the number of degrees of freedom."
+        );
+
+        // The forces needed for a zero acceleration are the velocity-dependent and gravity terms.
+        let bias = self.inverse_dynamics(bodies, gravity, &vec![0.0; self.ndofs]);
+        let rhs = DVector::from_column_slice(forces) - bias;
+        self.mass_matrix(bodies).lu().solve(&rhs)
+    }
+
     /// Computes the constant terms of the dynamics.
     #[profiling::function]
     pub(crate) fn update_dynamics(&mut self, dt: Real, bodies: &mut RigidBodySet) {
@@ -842,6 +1024,45 @@ impl Multibody {
         self.velocities.rows(0, self.ndofs)
     }
 
+    /// The generalized coordinates (joint-space positions) of this multibody.
+    ///
+    /// The coordinates of each link’s joint, as given by
+    /// [`MultibodyJoint::generalized_positions`], are stored one after the other in the order of
+    /// [`Self::links`], i.e., in the same order as [`Self::generalized_velocity`]. If the root
+    /// of this multibody is dynamic, its first coordinates are those of the free joint attaching
+    /// the root to the world: its translation followed by its rotation.
+    pub fn generalized_positions(&self) -> DVector<Real> {
+        let mut positions = DVector::zeros(self.ndofs);
+        for link in self.links.iter() {
+            link.joint
+                .generalized_positions(&mut positions.as_mut_slice()[link.assembly_id..]);
+        }
+        positions
+    }
+
+    /// Sets the generalized coordinates (joint-space positions) of this multibody.
+    ///
+    /// See [`Self::generalized_positions`] for the ordering of the coordinates. This does **not**
+    /// update the link poses, call [`Self::forward_kinematics`] and [`Self::update_rigid_bodies`]
+    /// to apply the change immediately. Otherwise, it is applied at the next timestep. The
+    /// coordinates of a dynamic root are overwritten by the pose of its rigid-body at the next
+    /// timestep: move the root rigid-body instead.
+    ///
+    /// # Panics
+    /// Panics if `positions` doesn’t have exactly [`Self::ndofs`] elements.
+    pub fn set_generalized_positions(&mut self, positions: &[Real]) {
+        assert_eq!(
+            positions.len(),
+            self.ndofs,
+            "The number of generalized coordinates must match the number of degrees of freedom."
+        );
+
+        for link in self.links.iter_mut() {
+            link.joint
+                .set_generalized_positions(&positions[link.assembly_id..]);
+        }
+    }
+
     /// The body jacobian for link `link_id` calculated by the last call to [`Multibody::forward_kinematics`].
     #[inline]
     pub fn body_jacobian(&self, link_id: usize) -> &Jacobian<Real> {
@@ -1567,4 +1788,233 @@ mod test {
             DVector::from(vec![0.0, 10.0, 0.0, 0.0, 0.0, 50.0, 60.0, 0.0, 80.0, 0.0])
         );
     }
+
+    #[test]
+    fn generalized_coordinates_drive_the_links() {
+        use crate::dynamics::IntegrationParameters;
+        use crate::math::Vector;
+        use crate::pipeline::{PhysicsPipeline, PhysicsState};
+
+        let mut state = PhysicsState::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let params = IntegrationParameters::default();
+
+        let root = state.bodies.insert(RigidBodyBuilder::fixed());
+        let arm = state.bodies.insert(RigidBodyBuilder::dynamic());
+        #[cfg(feature = "dim2")]
+        let joint = RevoluteJoint::new();
+        #[cfg(feature = "dim3")]
+        let joint = RevoluteJoint::new(Vector::z_axis());
+        let handle = state
+            .multibody_joints
+            .insert(root, arm, joint, true)
+            .unwrap();
+
+        // The fixed root has no degree of freedom.
+        assert!(
+            state
+                .multibody_joints
+                .set_generalized_positions(handle, &[0.5], true)
+        );
+        assert!(
+            state
+                .multibody_joints
+                .set_generalized_velocities(handle, &[1.0], true)
+        );
+        assert_eq!(
+            state.multibody_joints.generalized_positions(handle),
+            Some(DVector::from(vec![0.5]))
+        );
+
+        state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+
+        let q = state
+            .multibody_joints
+            .generalized_positions(handle)
+            .unwrap();
+        let qdot = state
+            .multibody_joints
+            .generalized_velocities(handle)
+            .unwrap();
+        assert_eq!(q.len(), 1);
+        assert!(q[0] > 0.5 && q[0] <= 0.5 + params.dt + 1.0e-5);
+        assert!(qdot[0] > 0.0);
+        assert!((state.bodies[arm].rotation().angle() - q[0]).abs() < 1.0e-5);
+    }
+
+    #[cfg(feature = "dim3")]
+    #[test]
+    fn spherical_generalized_coordinates_are_rotation_vectors() {
+        use crate::dynamics::SphericalJoint;
+
+        let mut bodies = RigidBodySet::new();
+        let mut joints = MultibodyJointSet::new();
+        let root = bodies.insert(RigidBodyBuilder::fixed());
+        let body = bodies.insert(RigidBodyBuilder::dynamic());
+        let handle = joints
+            .insert(root, body, SphericalJoint::new(), true)
+            .unwrap();
+
+        let (multibody, _) = joints.get_mut(handle).unwrap();
+        multibody.set_generalized_positions(&[0.1, 0.2, 0.3]);
+        multibody.forward_kinematics(&bodies, true);
+        multibody.update_rigid_bodies(&mut bodies, false);
+
+        let q = multibody.generalized_positions();
+        assert!((q - DVector::from(vec![0.1, 0.2, 0.3])).norm() < 1.0e-5);
+        let rotvec = bodies[body].rotation().scaled_axis();
+        assert!((rotvec - na::Vector3::new(0.1, 0.2, 0.3)).norm() < 1.0e-5);
+    }
+
+    #[cfg(feature = "dim3")]
+    #[test]
+    fn two_free_rotations_generalized_coordinates_are_composed_angles() {
+        use crate::dynamics::{GenericJointBuilder, JointAxesMask, MultibodyJoint};
+        use crate::math::{Rotation, Vector};
+
+        // A joint only free to rotate around its Y and Z axes.
+        let locked_axes = JointAxesMask::LIN_AXES | JointAxesMask::ANG_X;
+        let data = GenericJointBuilder::new(locked_axes).build();
+        let mut joint = MultibodyJoint::new(data, false);
+        assert_eq!(joint.ndofs(), 2);
+
+        joint.set_generalized_positions(&[0.1, 0.2]);
+        let mut q = [0.0; 2];
+        joint.generalized_positions(&mut q);
+        assert_eq!(q, [0.1, 0.2]);
+
+        let expected = Rotation::from_axis_angle(&Vector::y_axis(), 0.1)
+            * Rotation::from_axis_angle(&Vector::z_axis(), 0.2);
+        assert!(joint.body_to_parent().rotation.angle_to(&expected) < 1.0e-5);
+    }
+
+    // Two links rotating around the Z axis, with their centers of mass at `lc1` and `lc2`
+    // from their joints, and with angular inertias `i1` and `i2` around these centers.
+    // The values are `[m1, m2, l1, lc1, lc2, i1, i2]`.
+    const DOUBLE_PENDULUM: [Real; 7] = [2.0, 1.5, 1.0, 0.4, 0.3, 0.2, 0.1];
+
+    fn double_pendulum(
+        positions: [Real; 2],
+        velocities: [Real; 2],
+    ) -> (RigidBodySet, MultibodyJointSet, MultibodyJointHandle) {
+        use crate::dynamics::{MassProperties, RevoluteJointBuilder};
+        use crate::math::{Point, Vector};
+
+        let [m1, m2, l1, lc1, lc2, i1, i2] = DOUBLE_PENDULUM;
+        let mut bodies = RigidBodySet::new();
+        let mut joints = MultibodyJointSet::new();
+        let link_body = |lc: Real, m: Real, i: Real| {
+            #[cfg(feature = "dim2")]
+            let inertia = i;
+            #[cfg(feature = "dim3")]
+            let inertia = Vector::repeat(i);
+            let mprops = MassProperties::new(Point::from(Vector::x() * lc), m, inertia);
+            RigidBodyBuilder::dynamic().additional_mass_properties(mprops)
+        };
+        let root = bodies.insert(RigidBodyBuilder::fixed());
+        let body1 = bodies.insert(link_body(lc1, m1, i1));
+        let body2 = bodies.insert(link_body(lc2, m2, i2));
+        for handle in [body1, body2] {
+            bodies[handle].recompute_mass_properties_from_colliders(&ColliderSet::new());
+        }
+
+        #[cfg(feature = "dim2")]
+        let joint = RevoluteJointBuilder::new();
+        #[cfg(feature = "dim3")]
+        let joint = RevoluteJointBuilder::new(Vector::z_axis());
+        joints.insert(root, body1, joint, true).unwrap();
+        let handle = joints
+            .insert(
+                body1,
+                body2,
+                joint.local_anchor1(Point::from(Vector::x() * l1)),
+                true,
+            )
+            .unwrap();
+
+        let (multibody, _) = joints.get_mut(handle).unwrap();
+        multibody.damping_mut().fill(0.0);
+        multibody.set_generalized_positions(&positions);
+        multibody
+            .generalized_velocity_mut()
+            .copy_from_slice(&velocities);
+        multibody.forward_kinematics(&bodies, true);
+        multibody.update_rigid_bodies(&mut bodies, true);
+
+        (bodies, joints, handle)
+    }
+
+    #[test]
+    fn inverse_dynamics_matches_analytic_double_pendulum() {
+        use crate::math::Vector;
+
+        let [m1, m2, l1, lc1, lc2, i1, i2] = DOUBLE_PENDULUM;
+        let g = 9.81;
+        let (q1, q2): (Real, Real) = (0.3, -0.7);
+        let (qd1, qd2) = (1.2, -0.5);
+        let (qdd1, qdd2) = (0.4, -1.1);
+
+        let (bodies, joints, handle) = double_pendulum([q1, q2], [qd1, qd2]);
+        let gravity = Vector::y() * -g;
+        let (multibody, _) = joints.get(handle).unwrap();
+        let forces = multibody.inverse_dynamics(&bodies, &gravity, &[qdd1, qdd2]);
+
+        let m11 = i1 + i2 + m1 * lc1 * lc1 + m2 * (l1 * l1 + lc2 * lc2 + 2.0 * l1 * lc2 * q2.cos());
+        let m12 = i2 + m2 * (lc2 * lc2 + l1 * lc2 * q2.cos());
+        let m22 = i2 + m2 * lc2 * lc2;
+        let h = -m2 * l1 * lc2 * q2.sin();
+        let g1 = (m1 * lc1 + m2 * l1) * g * q1.cos() + m2 * lc2 * g * (q1 + q2).cos();
+        let g2 = m2 * lc2 * g * (q1 + q2).cos();
+        let tau1 = m11 * qdd1 + m12 * qdd2 + h * qd2 * qd2 + 2.0 * h * qd1 * qd2 + g1;
+        let tau2 = m12 * qdd1 + m22 * qdd2 - h * qd1 * qd1 + g2;
+
+        assert!((forces - DVector::from(vec![tau1, tau2])).norm() < 1.0e-3);
+
+        let mass_matrix = multibody.mass_matrix(&bodies);
+        let expected_mass_matrix = na::DMatrix::from_row_slice(2, 2, &[m11, m12, m12, m22]);
+        assert!((mass_matrix - expected_mass_matrix).norm() < 1.0e-4);
+    }
+
+    #[test]
+    fn forward_dynamics_inverts_inverse_dynamics() {
+        use crate::math::Vector;
+
+        let (bodies, joints, handle) = double_pendulum([0.3, -0.7], [1.2, -0.5]);
+        let gravity = Vector::y() * -9.81;
+        let accelerations = [0.4, -1.1];
+        let (multibody, _) = joints.get(handle).unwrap();
+
+        let forces = multibody.inverse_dynamics(&bodies, &gravity, &accelerations);
+        let predicted = multibody
+            .forward_dynamics(&bodies, &gravity, forces.as_slice())
+            .unwrap();
+        assert!((predicted - DVector::from(accelerations.to_vec())).norm() < 1.0e-4);
+    }
+
+    #[test]
+    fn unactuated_double_pendulum_falls() {
+        use crate::math::Vector;
+
+        // Both links start horizontal and at rest.
+        let [m1, m2, l1, lc1, lc2, i1, i2] = DOUBLE_PENDULUM;
+        let g = 9.81;
+        let (bodies, joints, handle) = double_pendulum([0.0, 0.0], [0.0, 0.0]);
+        let (multibody, _) = joints.get(handle).unwrap();
+        let accelerations = multibody
+            .forward_dynamics(&bodies, &(Vector::y() * -g), &[0.0, 0.0])
+            .unwrap();
+
+        // Solve `M * qdd = -G` with the analytic mass matrix and gravity terms.
+        let m11 = i1 + i2 + m1 * lc1 * lc1 + m2 * (l1 * l1 + lc2 * lc2 + 2.0 * l1 * lc2);
+        let m12 = i2 + m2 * (lc2 * lc2 + l1 * lc2);
+        let m22 = i2 + m2 * lc2 * lc2;
+        let g1 = (m1 * lc1 + m2 * l1) * g + m2 * lc2 * g;
+        let g2 = m2 * lc2 * g;
+        let det = m11 * m22 - m12 * m12;
+        let qdd1 = (-g1 * m22 + g2 * m12) / det;
+        let qdd2 = (-g2 * m11 + g1 * m12) / det;
+
+        assert!(qdd1 < 0.0);
+        assert!((accelerations - DVector::from(vec![qdd1, qdd2])).norm() < 1.0e-3);
+    }
 }
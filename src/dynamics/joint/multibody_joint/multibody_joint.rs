@@ -54,6 +54,10 @@ impl MultibodyJoint {
         self.coords
             .fixed_rows_mut::<DIM>(0)
             .copy_from(&pos.translation.vector);
+        #[cfg(feature = "dim2")]
+        {
+            self.coords[DIM] = pos.rotation.angle();
+        }
         self.joint_rot = pos.rotation;
     }
 
@@ -85,6 +89,104 @@ impl MultibodyJoint {
         self.data.local_frame1 * transform
     }
 
+    /// Writes into `out` the generalized coordinates of this multibody_joint.
+    ///
+    /// The first [`Self::ndofs`] elements of `out` are set to the positions along the free
+    /// degrees of freedom, in this order:
+    /// - the free translations along the X, Y (and Z in 3D) axes of the joint’s frame,
+    /// - then the free rotations: the angle around the free axis if there is only one (e.g. for
+    ///   revolute joints, and always in 2D), the angles around the two free axes if there are two
+    ///   (the joint’s rotation being the rotation around the first one composed with the rotation
+    ///   around the second one), or the rotation vector (axis multiplied by angle) of the joint’s
+    ///   rotation if the three rotations are free (e.g. for spherical joints).
+    pub fn generalized_positions(&self, out: &mut [Real]) {
+        let locked_bits = self.data.locked_axes.bits();
+        let mut curr_free_dof = 0;
+
+        for i in 0..DIM {
+            if (locked_bits & (1 << i)) == 0 {
+                out[curr_free_dof] = self.coords[i];
+                curr_free_dof += 1;
+            }
+        }
+
+        let locked_ang_bits = locked_bits >> DIM;
+        let num_free_ang_dofs = ANG_DIM - locked_ang_bits.count_ones() as usize;
+        match num_free_ang_dofs {
+            #[cfg(feature = "dim3")]
+            3 => {
+                out[curr_free_dof..curr_free_dof + 3]
+                    .copy_from_slice(self.joint_rot.scaled_axis().as_slice());
+            }
+            _ => {
+                for i in 0..ANG_DIM {
+                    if (locked_ang_bits & (1 << i)) == 0 {
+                        out[curr_free_dof] = self.coords[DIM + i];
+                        curr_free_dof += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sets the generalized coordinates of this multibody_joint.
+    ///
+    /// See [`Self::generalized_positions`] for the ordering of the coordinates. Only the first
+    /// [`Self::ndofs`] elements of `positions` are read. This does not update the poses of the
+    /// multibody links, see [`Multibody::forward_kinematics`].
+    pub fn set_generalized_positions(&mut self, positions: &[Real]) {
+        let locked_bits = self.data.locked_axes.bits();
+        let mut curr_free_dof = 0;
+
+        for i in 0..DIM {
+            if (locked_bits & (1 << i)) == 0 {
+                self.coords[i] = positions[curr_free_dof];
+                curr_free_dof += 1;
+            }
+        }
+
+        let locked_ang_bits = locked_bits >> DIM;
+        let num_free_ang_dofs = ANG_DIM - locked_ang_bits.count_ones() as usize;
+        match num_free_ang_dofs {
+            0 => { /* No free dofs. */ }
+            1 => {
+                let dof_id = (!locked_ang_bits).trailing_zeros() as usize;
+                self.coords[DIM + dof_id] = positions[curr_free_dof];
+                #[cfg(feature = "dim2")]
+                {
+                    self.joint_rot = Rotation::new(self.coords[DIM + dof_id]);
+                }
+                #[cfg(feature = "dim3")]
+                {
+                    self.joint_rot = Rotation::from_axis_angle(
+                        &Vector::ith_axis(dof_id),
+                        self.coords[DIM + dof_id],
+                    );
+                }
+            }
+            #[cfg(feature = "dim3")]
+            2 => {
+                // Compose the rotations around the free axes, in the order of the axes.
+                self.joint_rot = Rotation::identity();
+                for i in 0..ANG_DIM {
+                    if (locked_ang_bits & (1 << i)) == 0 {
+                        self.coords[DIM + i] = positions[curr_free_dof];
+                        self.joint_rot *=
+                            Rotation::from_axis_angle(&Vector::ith_axis(i), self.coords[DIM + i]);
+                        curr_free_dof += 1;
+                    }
+                }
+            }
+            #[cfg(feature = "dim3")]
+            3 => {
+                let rotvec = Vector3::from_row_slice(&positions[curr_free_dof..curr_free_dof + 3]);
+                self.joint_rot = UnitQuaternion::new(rotvec);
+                self.coords.fixed_rows_mut::<3>(3).copy_from(&rotvec);
+            }
+            _ => unreachable!(),
+        }
+    }
+
     /// Integrate the position of this multibody_joint.
     #[profiling::function]
     pub fn integrate(&mut self, dt: Real, vels: &[Real]) {
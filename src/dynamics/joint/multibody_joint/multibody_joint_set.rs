@@ -4,6 +4,8 @@ use crate::data::{Arena, Coarena, Index};
 use crate::dynamics::joint::MultibodyLink;
 use crate::dynamics::{GenericJoint, Multibody, MultibodyJoint, RigidBodyHandle};
 use crate::geometry::{InteractionGraph, RigidBodyGraphIndex};
+use crate::math::Real;
+use na::{DVector, DVectorView};
 
 /// The unique handle of an multibody_joint added to a `MultibodyJointSet`.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -358,6 +360,88 @@ impl MultibodyJointSet {
         Some((multibody, link.id))
     }
 
+    /// The generalized coordinates (joint-space positions) of the multibody containing the
+    /// joint identified by `handle`.
+    ///
+    /// See [`Multibody::generalized_positions`] for the ordering of the coordinates.
+    pub fn generalized_positions(&self, handle: MultibodyJointHandle) -> Option<DVector<Real>> {
+        Some(self.get(handle)?.0.generalized_positions())
+    }
+
+    /// The generalized velocities of the multibody containing the joint identified by `handle`.
+    ///
+    /// The velocities are ordered like the coordinates returned by
+    /// [`Self::generalized_positions`].
+    pub fn generalized_velocities(
+        &self,
+        handle: MultibodyJointHandle,
+    ) -> Option<DVectorView<'_, Real>> {
+        Some(self.get(handle)?.0.generalized_velocity())
+    }
+
+    /// Sets the generalized coordinates (joint-space positions) of the multibody containing the
+    /// joint identified by `handle`.
+    ///
+    /// The poses of the rigid-bodies attached to the multibody are refreshed by
+    /// forward-kinematics at the beginning of the next timestep. If `wake_up` is `true`, these
+    /// rigid-bodies are woken up. See [`Multibody::set_generalized_positions`] for details.
+    ///
+    /// Returns `false` if `handle` doesn’t identify any multibody joint.
+    pub fn set_generalized_positions(
+        &mut self,
+        handle: MultibodyJointHandle,
+        positions: &[Real],
+        wake_up: bool,
+    ) -> bool {
+        let Some((multibody, _)) = self.get_mut(handle) else {
+            return false;
+        };
+        multibody.set_generalized_positions(positions);
+        self.wake_up_multibody(handle, wake_up);
+        true
+    }
+
+    /// Sets the generalized velocities of the multibody containing the joint identified by
+    /// `handle`.
+    ///
+    /// The velocities are ordered like the coordinates returned by
+    /// [`Self::generalized_positions`]. If `wake_up` is `true`, the rigid-bodies attached to the
+    /// multibody are woken up.
+    ///
+    /// Returns `false` if `handle` doesn’t identify any multibody joint.
+    ///
+    /// # Panics
+    /// Panics if `velocities` doesn’t have exactly [`Multibody::ndofs`] elements.
+    pub fn set_generalized_velocities(
+        &mut self,
+        handle: MultibodyJointHandle,
+        velocities: &[Real],
+        wake_up: bool,
+    ) -> bool {
+        let Some((multibody, _)) = self.get_mut(handle) else {
+            return false;
+        };
+        multibody
+            .generalized_velocity_mut()
+            .copy_from_slice(velocities);
+        self.wake_up_multibody(handle, wake_up);
+        true
+    }
+
+    fn wake_up_multibody(&mut self, handle: MultibodyJointHandle, wake_up: bool) {
+        if !wake_up {
+            return;
+        }
+
+        let Some(link) = self.rb2mb.get(handle.0) else {
+            return;
+        };
+        if let Some(multibody) = self.multibodies.get(link.multibody.0) {
+            self.to_wake_up
+                .extend(multibody.links().map(|link| link.rigid_body));
+        }
+    }
+
     /// Gets the joint with the given handle without a known generation.
     ///
     /// This is useful when you know you want the joint at index `i` but
@@ -113,6 +113,10 @@ pub fn unit_joint_motor_constraint(
     };
 
     rhs_wo_bias += -target_vel;
+    // NOTE: don’t use `clamp`, it panics if `max_vel` is negative or NaN.
+    rhs_wo_bias = rhs_wo_bias
+        .max(-motor_params.max_vel)
+        .min(motor_params.max_vel);
 
     let constraint = GenericJointConstraint {
         is_rigid_body1: false,
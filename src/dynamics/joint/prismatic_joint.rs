@@ -54,6 +54,17 @@ impl PrismaticJoint {
         self
     }
 
+    /// The user-defined data associated to this joint.
+    pub fn user_data(&self) -> u128 {
+        self.data.user_data
+    }
+
+    /// Sets the user-defined data associated to this joint.
+    pub fn set_user_data(&mut self, data: u128) -> &mut Self {
+        self.data.set_user_data(data);
+        self
+    }
+
     /// The joint’s anchor, expressed in the local-space of the first rigid-body.
     #[must_use]
     pub fn local_anchor1(&self) -> Point<Real> {
@@ -165,6 +176,15 @@ impl PrismaticJoint {
         self
     }
 
+    /// Sets the maximum velocity (units/sec) the motor can drive the joint at.
+    ///
+    /// Combined with [`Self::set_motor_position`], this makes the motor behave like a servo: it
+    /// moves toward its target position without exceeding this velocity.
+    pub fn set_motor_max_velocity(&mut self, max_vel: Real) -> &mut Self {
+        self.data.set_motor_max_velocity(JointAxis::LinX, max_vel);
+        self
+    }
+
     /// The limit distance attached bodies can translate along the joint’s principal axis.
     #[must_use]
     pub fn limits(&self) -> Option<&JointLimits<Real>> {
@@ -206,6 +226,13 @@ impl PrismaticJointBuilder {
         self
     }
 
+    /// Sets the user-defined data associated to this joint.
+    #[must_use]
+    pub fn user_data(mut self, data: u128) -> Self {
+        self.0.set_user_data(data);
+        self
+    }
+
     /// Sets the joint’s anchor, expressed in the local-space of the first rigid-body.
     #[must_use]
     pub fn local_anchor1(mut self, anchor1: Point<Real>) -> Self {
@@ -275,6 +302,13 @@ impl PrismaticJointBuilder {
         self
     }
 
+    /// Sets the maximum velocity (units/sec) the motor can drive the joint at.
+    #[must_use]
+    pub fn motor_max_velocity(mut self, max_vel: Real) -> Self {
+        self.0.set_motor_max_velocity(max_vel);
+        self
+    }
+
     /// Sets the `[min,max]` limit distances attached bodies can translate along the joint’s principal axis.
     #[must_use]
     pub fn limits(mut self, limits: [Real; 2]) -> Self {
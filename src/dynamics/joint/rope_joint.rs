@@ -19,6 +19,11 @@ use super::JointMotor;
 ///
 /// Unlike spring joints, rope joints are inelastic - they don't bounce or stretch smoothly,
 /// they just enforce a hard maximum distance.
+///
+/// An optional minimum distance can also be set with [`RopeJoint::set_min_distance`]. No force
+/// is applied while the distance stays within `[min_distance, max_distance]`, and setting both
+/// to the same value yields a rigid distance constraint. A nonzero compliance, set with
+/// [`RopeJoint::set_compliance`], makes the rope stretch proportionally to the force it applies.
 pub struct RopeJoint {
     /// The underlying joint data.
     pub data: GenericJoint,
@@ -53,6 +58,17 @@ impl RopeJoint {
         self
     }
 
+    /// The user-defined data associated to this joint.
+    pub fn user_data(&self) -> u128 {
+        self.data.user_data
+    }
+
+    /// Sets the user-defined data associated to this joint.
+    pub fn set_user_data(&mut self, data: u128) -> &mut Self {
+        self.data.set_user_data(data);
+        self
+    }
+
     /// The joint’s anchor, expressed in the local-space of the first rigid-body.
     #[must_use]
     pub fn local_anchor1(&self) -> Point<Real> {
@@ -150,7 +166,43 @@ impl RopeJoint {
     /// rope_joint.set_max_distance(10.0);  // Max 10 units apart
     /// ```
     pub fn set_max_distance(&mut self, max_dist: Real) -> &mut Self {
-        self.data.set_limits(JointAxis::LinX, [0.0, max_dist]);
+        let min_dist = self.min_distance();
+        self.data.set_limits(JointAxis::LinX, [min_dist, max_dist]);
+        self
+    }
+
+    /// The minimum distance between anchor points.
+    ///
+    /// Bodies are pushed apart if they get closer than this distance. Defaults to 0.0.
+    #[must_use]
+    pub fn min_distance(&self) -> Real {
+        self.data
+            .limits(JointAxis::LinX)
+            .map(|l| l.min)
+            .unwrap_or(0.0)
+    }
+
+    /// Changes the minimum distance between anchor points.
+    ///
+    /// Must be non-negative and not greater than [`RopeJoint::max_distance`]. Setting it equal
+    /// to the maximum distance turns the rope into a rigid rod.
+    pub fn set_min_distance(&mut self, min_dist: Real) -> &mut Self {
+        let max_dist = self.max_distance();
+        self.data.set_limits(JointAxis::LinX, [min_dist, max_dist]);
+        self
+    }
+
+    /// The compliance (inverse stiffness) of this rope joint.
+    #[must_use]
+    pub fn compliance(&self) -> Real {
+        self.data.compliance
+    }
+
+    /// Sets the compliance (inverse stiffness) of this rope joint.
+    ///
+    /// See [`GenericJoint::set_compliance`] for details.
+    pub fn set_compliance(&mut self, compliance: Real) -> &mut Self {
+        self.data.set_compliance(compliance);
         self
     }
 }
@@ -181,6 +233,13 @@ impl RopeJointBuilder {
         self
     }
 
+    /// Sets the user-defined data associated to this joint.
+    #[must_use]
+    pub fn user_data(mut self, data: u128) -> Self {
+        self.0.set_user_data(data);
+        self
+    }
+
     /// Sets the joint’s anchor, expressed in the local-space of the first rigid-body.
     #[must_use]
     pub fn local_anchor1(mut self, anchor1: Point<Real>) -> Self {
@@ -245,6 +304,22 @@ impl RopeJointBuilder {
         self
     }
 
+    /// Sets the minimum allowed distance between the attached bodies.
+    #[must_use]
+    pub fn min_distance(mut self, min_dist: Real) -> Self {
+        self.0.set_min_distance(min_dist);
+        self
+    }
+
+    /// Sets the compliance (inverse stiffness) of the rope joint.
+    ///
+    /// See [`GenericJoint::set_compliance`] for details.
+    #[must_use]
+    pub fn compliance(mut self, compliance: Real) -> Self {
+        self.0.set_compliance(compliance);
+        self
+    }
+
     /// Builds the rope joint.
     #[must_use]
     pub fn build(self) -> RopeJoint {
@@ -56,6 +56,17 @@ impl SphericalJoint {
         self
     }
 
+    /// The user-defined data associated to this joint.
+    pub fn user_data(&self) -> u128 {
+        self.data.user_data
+    }
+
+    /// Sets the user-defined data associated to this joint.
+    pub fn set_user_data(&mut self, data: u128) -> &mut Self {
+        self.data.set_user_data(data);
+        self
+    }
+
     /// The joint’s anchor, expressed in the local-space of the first rigid-body.
     #[must_use]
     pub fn local_anchor1(&self) -> Point<Real> {
@@ -192,6 +203,32 @@ impl SphericalJoint {
         self.data.set_limits(axis, limits);
         self
     }
+
+    /// Sets the cone (swing) limit of this joint.
+    ///
+    /// The angle between the X axes of both joint frames ([`Self::local_frame1`] and
+    /// [`Self::local_frame2`]) is limited to `half_angle`, i.e., the X axis of the second frame
+    /// remains inside of a cone of half-angle `half_angle` around the X axis of the first frame.
+    ///
+    /// This couples the `AngY` and `AngZ` axes, and stores the limit as the `AngY` limits:
+    /// any limit or motor previously set on `AngY` or `AngZ` is no longer applied. Cone limits
+    /// are only supported by impulse joints.
+    pub fn set_cone_limit(&mut self, half_angle: Real) -> &mut Self {
+        self.data.coupled_axes |= JointAxesMask::ANG_Y | JointAxesMask::ANG_Z;
+        self.data
+            .set_limits(JointAxis::AngY, [-half_angle, half_angle]);
+        self
+    }
+
+    /// Sets the `[min, max]` twist limit angles of this joint.
+    ///
+    /// The twist is the rotation along the X axis of the joint frames. If a cone limit is set
+    /// (see [`Self::set_cone_limit`]), the twist is measured independently from the swing.
+    /// This is equivalent to setting the limits of the `AngX` axis.
+    pub fn set_twist_limit(&mut self, min: Real, max: Real) -> &mut Self {
+        self.data.set_limits(JointAxis::AngX, [min, max]);
+        self
+    }
 }
 
 impl From<SphericalJoint> for GenericJoint {
@@ -218,6 +255,13 @@ impl SphericalJointBuilder {
         self
     }
 
+    /// Sets the user-defined data associated to this joint.
+    #[must_use]
+    pub fn user_data(mut self, data: u128) -> Self {
+        self.0.set_user_data(data);
+        self
+    }
+
     /// Sets the joint’s anchor, expressed in the local-space of the first rigid-body.
     #[must_use]
     pub fn local_anchor1(mut self, anchor1: Point<Real>) -> Self {
@@ -305,6 +349,24 @@ impl SphericalJointBuilder {
         self
     }
 
+    /// Sets the cone (swing) limit of the joint.
+    ///
+    /// See [`SphericalJoint::set_cone_limit`] for details.
+    #[must_use]
+    pub fn cone_limit(mut self, half_angle: Real) -> Self {
+        self.0.set_cone_limit(half_angle);
+        self
+    }
+
+    /// Sets the `[min, max]` twist limit angles of the joint.
+    ///
+    /// See [`SphericalJoint::set_twist_limit`] for details.
+    #[must_use]
+    pub fn twist_limit(mut self, min: Real, max: Real) -> Self {
+        self.0.set_twist_limit(min, max);
+        self
+    }
+
     /// Builds the spherical joint.
     #[must_use]
     pub fn build(self) -> SphericalJoint {
@@ -0,0 +1,249 @@
+use crate::math::{Isometry, Real};
+
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+/// A pose a [`KinematicPath`] must reach at a given time.
+pub struct KinematicPathKeyframe {
+    /// The time at which `position` is reached, relative to the beginning of the path.
+    pub time: Real,
+    /// The pose of the rigid-body at `time`.
+    pub position: Isometry<Real>,
+}
+
+impl KinematicPathKeyframe {
+    /// Initializes a keyframe reaching `position` at the given `time`.
+    pub fn new(time: Real, position: Isometry<Real>) -> Self {
+        Self { time, position }
+    }
+}
+
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+/// A path followed by a position-based kinematic rigid-body.
+///
+/// The path is a sequence of keyframes sorted by increasing time. At each timestep, the
+/// physics pipeline advances the time of the path and sets the next kinematic position of the
+/// rigid-body to the path sampled at that time. Translations are interpolated linearly and
+/// rotations are interpolated spherically between two consecutive keyframes. The velocity of
+/// the rigid-body is then derived from that motion, so that contacts and CCD account for it.
+///
+/// Attach it to a rigid-body with [`RigidBody::set_kinematic_path`](crate::dynamics::RigidBody::set_kinematic_path).
+///
+/// # Example
+/// ```
+/// # use rapier3d::prelude::*;
+/// # use rapier3d::dynamics::{KinematicPath, KinematicPathKeyframe};
+/// let path = KinematicPath::new(vec![
+///     KinematicPathKeyframe::new(0.0, Isometry::translation(0.0, 0.0, 0.0)),
+///     KinematicPathKeyframe::new(2.0, Isometry::translation(5.0, 0.0, 0.0)),
+///     KinematicPathKeyframe::new(4.0, Isometry::translation(0.0, 0.0, 0.0)),
+/// ])
+/// .looping(true);
+/// ```
+pub struct KinematicPath {
+    keyframes: Vec<KinematicPathKeyframe>,
+    time: Real,
+    looping: bool,
+}
+
+impl KinematicPath {
+    /// Creates a path going through the given keyframes.
+    ///
+    /// # Panics
+    /// Panics if `keyframes` is empty or isn’t sorted by increasing time.
+    pub fn new(keyframes: Vec<KinematicPathKeyframe>) -> Self {
+        assert!(
+            !keyframes.is_empty(),
+            "A kinematic path needs at least one keyframe."
+        );
+        assert!(
+            keyframes.windows(2).all(|w| w[0].time <= w[1].time),
+            "The keyframes of a kinematic path must be sorted by increasing time."
+        );
+
+        Self {
+            keyframes,
+            time: 0.0,
+            looping: false,
+        }
+    }
+
+    /// Sets whether the path restarts from its first keyframe once its last keyframe is reached.
+    #[must_use]
+    pub fn looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Does this path restart from its first keyframe once its last keyframe is reached?
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Sets whether the path restarts from its first keyframe once its last keyframe is reached.
+    pub fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
+    }
+
+    /// The keyframes of this path.
+    pub fn keyframes(&self) -> &[KinematicPathKeyframe] {
+        &self.keyframes
+    }
+
+    /// The current time along this path.
+    pub fn time(&self) -> Real {
+        self.time
+    }
+
+    /// Sets the current time along this path.
+    pub fn set_time(&mut self, time: Real) {
+        self.time = time;
+    }
+
+    /// The time of the last keyframe of this path.
+    pub fn duration(&self) -> Real {
+        self.keyframes.last().map(|k| k.time).unwrap_or(0.0)
+    }
+
+    /// Has the last keyframe of this (non-looping) path been reached?
+    pub fn is_finished(&self) -> bool {
+        !self.looping && self.time >= self.duration()
+    }
+
+    /// The pose of this path at the given time.
+    ///
+    /// Times before the first keyframe return the first keyframe’s pose. Times after the last
+    /// keyframe return the last keyframe’s pose, or wrap around if this path is looping.
+    pub fn sample(&self, time: Real) -> Isometry<Real> {
+        let first = &self.keyframes[0];
+        let last = &self.keyframes[self.keyframes.len() - 1];
+        let duration = last.time - first.time;
+
+        let time = if self.looping && duration > 0.0 && time > last.time {
+            first.time + (time - first.time).rem_euclid(duration)
+        } else {
+            time
+        };
+
+        // Index of the first keyframe strictly after `time`.
+        let i = self.keyframes.partition_point(|k| k.time <= time);
+
+        if i == 0 {
+            first.position
+        } else if i == self.keyframes.len() {
+            last.position
+        } else {
+            let k1 = &self.keyframes[i - 1];
+            let k2 = &self.keyframes[i];
+            let t = (time - k1.time) / (k2.time - k1.time);
+            Self::interpolate(&k1.position, &k2.position, t)
+        }
+    }
+
+    #[cfg(feature = "dim2")]
+    fn interpolate(pos1: &Isometry<Real>, pos2: &Isometry<Real>, t: Real) -> Isometry<Real> {
+        pos1.lerp_slerp(pos2, t)
+    }
+
+    #[cfg(feature = "dim3")]
+    fn interpolate(pos1: &Isometry<Real>, pos2: &Isometry<Real>, t: Real) -> Isometry<Real> {
+        pos1.try_lerp_slerp(pos2, t, 1.0e-6).unwrap_or_else(|| {
+            // Slerp isn’t well-defined for these rotations. Rotate around the axis of their
+            // relative rotation instead, which is deterministic even for half-turns.
+            let translation = pos1.translation.vector.lerp(&pos2.translation.vector, t);
+            let rotation = pos1.rotation.rotation_to(&pos2.rotation).powf(t) * pos1.rotation;
+            Isometry::from_parts(translation.into(), rotation)
+        })
+    }
+
+    /// Advances the time of this path by `dt` and returns the pose at the new time.
+    pub(crate) fn advance(&mut self, dt: Real) -> Isometry<Real> {
+        self.time += dt;
+
+        if self.looping {
+            let duration = self.duration() - self.keyframes[0].time;
+            if duration > 0.0 && self.time > self.duration() {
+                self.time -= duration;
+            }
+        }
+
+        self.sample(self.time)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::math::{Rotation, Vector};
+
+    #[test]
+    fn sample_interpolates_between_keyframes() {
+        let p1 = Isometry::identity();
+        #[cfg(feature = "dim2")]
+        let p2 = Isometry::new(Vector::x() * 4.0, 1.0);
+        #[cfg(feature = "dim3")]
+        let p2 = Isometry::new(Vector::x() * 4.0, Vector::y());
+        let path = KinematicPath::new(vec![
+            KinematicPathKeyframe::new(0.0, p1),
+            KinematicPathKeyframe::new(2.0, p2),
+        ]);
+
+        assert_eq!(path.sample(-1.0), p1);
+        assert_eq!(path.sample(3.0), p2);
+
+        let mid = path.sample(1.0);
+        approx::assert_relative_eq!(mid.translation.vector, Vector::x() * 2.0);
+        approx::assert_relative_eq!(
+            mid.rotation,
+            Rotation::identity().slerp(&p2.rotation, 0.5),
+            epsilon = 1.0e-6
+        );
+    }
+
+    #[test]
+    fn looping_path_wraps_around() {
+        let mut path = KinematicPath::new(vec![
+            KinematicPathKeyframe::new(0.0, Isometry::identity()),
+            KinematicPathKeyframe::new(1.0, Isometry::new(Vector::x(), na::zero())),
+        ])
+        .looping(true);
+
+        let pos = path.advance(1.5);
+        assert!(!path.is_finished());
+        approx::assert_relative_eq!(path.time(), 0.5);
+        approx::assert_relative_eq!(pos.translation.vector, Vector::x() * 0.5);
+    }
+
+    #[test]
+    #[cfg(feature = "dim3")]
+    fn sample_interpolates_half_turns() {
+        use crate::math::Point;
+
+        let pi = std::f64::consts::PI as Real;
+        let p1 = Isometry::identity();
+        let p2 = Isometry::new(Vector::x() * 4.0, Vector::y() * pi);
+        // Going back and forth between opposite rotations must not panic either.
+        let path = KinematicPath::new(vec![
+            KinematicPathKeyframe::new(0.0, p1),
+            KinematicPathKeyframe::new(2.0, p2),
+            KinematicPathKeyframe::new(4.0, p2),
+            KinematicPathKeyframe::new(6.0, p1),
+        ]);
+
+        for i in 0..=60 {
+            let pos = path.sample(i as Real * 0.1);
+            assert!(pos.rotation.coords.iter().all(|x| x.is_finite()));
+        }
+
+        // Halfway through the first half-turn, the body rotated by a quarter turn around `y`.
+        let mid = path.sample(1.0);
+        approx::assert_relative_eq!(mid.translation.vector, Vector::x() * 2.0, epsilon = 1.0e-5);
+        approx::assert_relative_eq!(mid.rotation.angle(), pi / 2.0, epsilon = 1.0e-5);
+        approx::assert_relative_eq!(
+            (mid * Point::from(Vector::y())).coords,
+            Vector::y(),
+            epsilon = 1.0e-5
+        );
+        approx::assert_relative_eq!(path.sample(2.0).rotation, p2.rotation, epsilon = 1.0e-5);
+    }
+}
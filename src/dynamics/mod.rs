@@ -1,9 +1,11 @@
 //! Structures related to dynamics: bodies, impulse_joints, etc.
 
-pub use self::ccd::CCDSolver;
+pub use self::ccd::{CCDPairFilter, CCDSolver};
+pub(crate) use self::ccd::{PredictedImpacts, TOIEntry};
 pub use self::coefficient_combine_rule::CoefficientCombineRule;
 pub use self::integration_parameters::IntegrationParameters;
-pub use self::island_manager::IslandManager;
+pub use self::island_manager::{IslandInfo, IslandManager, SleepStateChangedEvent};
+pub use self::kinematic_path::{KinematicPath, KinematicPathKeyframe};
 
 #[cfg(feature = "dim3")]
 pub use self::integration_parameters::FrictionModel;
@@ -27,6 +29,7 @@ mod coefficient_combine_rule;
 mod integration_parameters;
 mod island_manager;
 mod joint;
+mod kinematic_path;
 mod rigid_body_components;
 mod solver;
 
@@ -1,15 +1,18 @@
 #[cfg(doc)]
 use super::IntegrationParameters;
 use crate::dynamics::{
-    LockedAxes, MassProperties, RigidBodyActivation, RigidBodyAdditionalMassProps, RigidBodyCcd,
-    RigidBodyChanges, RigidBodyColliders, RigidBodyDamping, RigidBodyDominance, RigidBodyForces,
-    RigidBodyIds, RigidBodyMassProps, RigidBodyPosition, RigidBodyType, RigidBodyVelocity,
+    CcdFreezeMode, KinematicPath, LockedAxes, MassProperties, RigidBodyActivation,
+    RigidBodyAdditionalMassProps, RigidBodyCcd, RigidBodyChanges, RigidBodyColliders,
+    RigidBodyDamping, RigidBodyDominance, RigidBodyForces, RigidBodyIds, RigidBodyMassProps,
+    RigidBodyMassPropsBreakdown, RigidBodyPosition, RigidBodyType, RigidBodyVelocity,
 };
 use crate::geometry::{
     ColliderHandle, ColliderMassProps, ColliderParent, ColliderPosition, ColliderSet, ColliderShape,
 };
 use crate::math::{AngVector, Isometry, Point, Real, Rotation, Vector};
 use crate::utils::SimdCross;
+#[cfg(feature = "dim3")]
+use na::Unit;
 use num::Zero;
 
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -59,6 +62,7 @@ pub struct RigidBody {
     pub(crate) dominance: RigidBodyDominance,
     pub(crate) enabled: bool,
     pub(crate) additional_solver_iterations: usize,
+    pub(crate) kinematic_path: Option<Box<KinematicPath>>,
     /// User-defined data associated to this rigid-body.
     pub user_data: u128,
 }
@@ -88,6 +92,7 @@ impl RigidBody {
             enabled: true,
             user_data: 0,
             additional_solver_iterations: 0,
+            kinematic_path: None,
         }
     }
 
@@ -151,7 +156,7 @@ impl RigidBody {
         self.changes = RigidBodyChanges::all();
     }
 
-    /// Set the additional number of solver iterations run for this rigid-body and
+    /// The additional number of solver iterations run for this rigid-body and
     /// everything interacting with it.
     ///
     /// See [`Self::set_additional_solver_iterations`] for additional information.
@@ -166,6 +171,11 @@ impl RigidBody {
     /// and every rigid-body interacting directly or indirectly with it (through joints
     /// or contacts). This implies a performance hit.
     ///
+    /// The number of iterations of a simulation island is the maximum of the additional
+    /// iterations of all the rigid-bodies it contains, so the extra cost only applies to
+    /// islands containing such bodies. This makes it possible to give more iterations to a
+    /// stiff joint chain (e.g. a vehicle suspension) without affecting the rest of the scene.
+    ///
     /// The default value is 0, meaning exactly [`IntegrationParameters::num_solver_iterations`] will
     /// be used as number of solver iterations for this body.
     pub fn set_additional_solver_iterations(&mut self, additional_iterations: usize) {
@@ -183,12 +193,42 @@ impl RigidBody {
         &mut self.activation
     }
 
+    /// Sets the velocity thresholds below which this rigid-body is allowed to fall asleep.
+    ///
+    /// The body can fall asleep once both its linear velocity magnitude and its angular velocity
+    /// magnitude stayed below these thresholds for [`RigidBodyActivation::time_until_sleep`].
+    /// The `linear` threshold is multiplied by [`IntegrationParameters::length_unit`](crate::dynamics::IntegrationParameters::length_unit).
+    ///
+    /// A negative threshold prevents this body from ever falling asleep. Note that an awake body
+    /// keeps awake all the bodies it is interacting with (through contacts or joints).
+    pub fn set_sleep_threshold(&mut self, linear: Real, angular: Real) {
+        self.activation.normalized_linear_threshold = linear;
+        self.activation.angular_threshold = angular;
+    }
+
+    /// The linear and angular velocity thresholds below which this rigid-body is allowed to fall
+    /// asleep.
+    ///
+    /// See [`Self::set_sleep_threshold`] for details.
+    pub fn sleep_threshold(&self) -> (Real, Real) {
+        (
+            self.activation.normalized_linear_threshold,
+            self.activation.angular_threshold,
+        )
+    }
+
     /// Is this rigid-body enabled?
     pub fn is_enabled(&self) -> bool {
         self.enabled
     }
 
     /// Sets whether this rigid-body is enabled or not.
+    ///
+    /// A disabled rigid-body is excluded from the simulation as if it was removed, but keeps its
+    /// handle, position, velocity, and impulse joints. Its colliders are disabled too (they are
+    /// removed from the broad-phase and the narrow-phase), and its attached impulse joints are
+    /// ignored by the solver. Re-enabling the rigid-body restores it with its last position and
+    /// velocity. This is useful to temporarily freeze distant objects.
     pub fn set_enabled(&mut self, enabled: bool) {
         if enabled != self.enabled {
             if enabled {
@@ -240,6 +280,42 @@ impl RigidBody {
         self.damping.angular_damping = damping
     }
 
+    /// The quadratic linear damping coefficient.
+    ///
+    /// See [`Self::set_linear_damping_quadratic`].
+    #[inline]
+    pub fn linear_damping_quadratic(&self) -> Real {
+        self.damping.linear_damping_quadratic
+    }
+
+    /// Sets a linear damping coefficient that scales with the linear speed.
+    ///
+    /// This models aerodynamic drag, which grows with the square of the speed. At each
+    /// timestep, the linear velocity `v` is updated as `v / (1 + (c_lin + c_quad * |v|) * dt)`
+    /// where `c_lin` is the [linear damping](Self::set_linear_damping) and `c_quad` is this
+    /// coefficient. The default value `0.0` disables quadratic damping.
+    #[inline]
+    pub fn set_linear_damping_quadratic(&mut self, damping: Real) {
+        self.damping.linear_damping_quadratic = damping;
+    }
+
+    /// The quadratic angular damping coefficient.
+    ///
+    /// See [`Self::set_angular_damping_quadratic`].
+    #[inline]
+    pub fn angular_damping_quadratic(&self) -> Real {
+        self.damping.angular_damping_quadratic
+    }
+
+    /// Sets an angular damping coefficient that scales with the angular speed.
+    ///
+    /// Like [`Self::set_linear_damping_quadratic`] but for rotation. The default value `0.0`
+    /// disables quadratic damping.
+    #[inline]
+    pub fn set_angular_damping_quadratic(&mut self, damping: Real) {
+        self.damping.angular_damping_quadratic = damping;
+    }
+
     /// The type of this rigid-body.
     pub fn body_type(&self) -> RigidBodyType {
         self.body_type
@@ -278,6 +354,29 @@ impl RigidBody {
         &self.mprops.local_mprops.local_com
     }
 
+    /// Overrides the center of mass of this rigid-body, expressed in its local coordinate system.
+    ///
+    /// Only the center of mass is affected: the mass and the angular inertia tensor remain the
+    /// ones computed from the attached colliders and additional mass-properties. The override is
+    /// kept when colliders are added or removed, and takes effect at the next physics step, or
+    /// after calling [`Self::recompute_mass_properties_from_colliders`].
+    ///
+    /// Use [`Self::clear_local_center_of_mass_override`] to go back to the computed center of mass.
+    pub fn set_local_center_of_mass(&mut self, local_com: Point<Real>) {
+        if self.mprops.local_com_override != Some(local_com) {
+            self.changes.insert(RigidBodyChanges::LOCAL_MASS_PROPERTIES);
+            self.mprops.local_com_override = Some(local_com);
+        }
+    }
+
+    /// Removes the center of mass override set with [`Self::set_local_center_of_mass`].
+    pub fn clear_local_center_of_mass_override(&mut self) {
+        if self.mprops.local_com_override.is_some() {
+            self.changes.insert(RigidBodyChanges::LOCAL_MASS_PROPERTIES);
+            self.mprops.local_com_override = None;
+        }
+    }
+
     /// The mass-properties of this rigid-body.
     #[inline]
     pub fn mass_properties(&self) -> &RigidBodyMassProps {
@@ -312,6 +411,35 @@ impl RigidBody {
         self.mprops.flags
     }
 
+    /// Restricts the rotations of this rigid-body to the given world-space axis.
+    ///
+    /// Unlike the rotation locks of [`Self::set_locked_axes`], the allowed axis can be arbitrary.
+    /// This is useful for objects that must only spin about one axis, like a character capsule
+    /// that should only yaw. The angular velocity of the rigid-body is projected on `axis` at
+    /// each timestep, and the torques and constraint impulses (contacts, joints) it receives
+    /// only affect its rotation along `axis`.
+    ///
+    /// Set to `None` to allow rotations along any axis again.
+    #[cfg(feature = "dim3")]
+    pub fn set_locked_rotation_axis(&mut self, axis: Option<Unit<Vector<Real>>>, wake_up: bool) {
+        if axis != self.mprops.locked_rotation_axis {
+            if self.is_dynamic_or_kinematic() && wake_up {
+                self.wake_up(true);
+            }
+
+            self.mprops.locked_rotation_axis = axis;
+            self.update_world_mass_properties();
+        }
+    }
+
+    /// The world-space axis this rigid-body is only allowed to rotate along, if any.
+    ///
+    /// See [`Self::set_locked_rotation_axis`].
+    #[cfg(feature = "dim3")]
+    pub fn locked_rotation_axis(&self) -> Option<Unit<Vector<Real>>> {
+        self.mprops.locked_rotation_axis
+    }
+
     /// Locks or unlocks all rotational movement for this body.
     ///
     /// When locked, the body cannot rotate at all (useful for keeping objects upright).
@@ -511,6 +639,15 @@ impl RigidBody {
     /// It is a generally cheaper variant of regular CCD (that can be enabled with
     /// [`RigidBody::enable_ccd`] since it relies on predictive constraints instead of
     /// shape-cast and substeps.
+    ///
+    /// Soft-CCD is a form of speculative contacts: the contact prediction distance between this
+    /// body and the other colliders is enlarged by up to the distance traveled by this body
+    /// during the timestep (capped by `prediction_distance`), and the constraints solver then
+    /// prevents the body from moving past the predicted contacts. No time-of-impact search is
+    /// performed. Prefer it to regular CCD for many moderately fast bodies. Prefer regular CCD
+    /// for very fast and small bodies (e.g. bullets), and when contacts must never be missed,
+    /// since speculative contacts may be inaccurate if the body rotates quickly, or generate
+    /// ghost collisions with obstacles the body would have avoided.
     pub fn set_soft_ccd_prediction(&mut self, prediction_distance: Real) {
         self.ccd.soft_ccd_prediction = prediction_distance;
     }
@@ -523,6 +660,20 @@ impl RigidBody {
         self.ccd.soft_ccd_prediction
     }
 
+    /// Sets which components of this body’s motion are stopped by the CCD solver at a time of impact.
+    ///
+    /// By default, both the translation and rotation are stopped. Freezing only the translation
+    /// (with [`CcdFreezeMode::Linear`]) lets a fast-rotating body (e.g. a pinball flipper) keep
+    /// rotating after an impact within the same substep.
+    pub fn set_ccd_freeze_mode(&mut self, mode: CcdFreezeMode) {
+        self.ccd.ccd_freeze_mode = mode;
+    }
+
+    /// The components of this body’s motion stopped by the CCD solver at a time of impact.
+    pub fn ccd_freeze_mode(&self) -> CcdFreezeMode {
+        self.ccd.ccd_freeze_mode
+    }
+
     // This is different from `is_ccd_enabled`. This checks that CCD
     // is active for this rigid-body, i.e., if it was seen to move fast
     // enough to justify a CCD run.
@@ -538,6 +689,15 @@ impl RigidBody {
         self.ccd.ccd_active
     }
 
+    /// Was the motion of this rigid-body clamped by the CCD solver during the last timestep?
+    ///
+    /// This is `true` if the CCD solver stopped this body at a time of impact instead of letting
+    /// it move to the position resulting from its velocity. It is reset at the beginning of
+    /// each timestep.
+    pub fn was_clamped_by_ccd(&self) -> bool {
+        self.ccd.ccd_clamped
+    }
+
     /// Recalculates mass, center of mass, and inertia from attached colliders.
     ///
     /// Normally automatic, but call this if you modify collider shapes/masses at runtime.
@@ -551,6 +711,27 @@ impl RigidBody {
         );
     }
 
+    /// The individual contributions of the attached colliders and of the additional
+    /// mass-properties to the mass-properties of this rigid-body.
+    ///
+    /// This is useful to check where the mass of a rigid-body comes from, in particular when it
+    /// has both colliders with a nonzero density and an explicit additional mass (see
+    /// [`RigidBodyMassPropsBreakdown::has_mass_conflict`]). The contributions reflect the current
+    /// state of the colliders, even if the mass-properties of this rigid-body haven’t been
+    /// recomputed since they were modified.
+    pub fn mass_properties_breakdown(
+        &self,
+        colliders: &ColliderSet,
+    ) -> RigidBodyMassPropsBreakdown {
+        RigidBodyMassPropsBreakdown {
+            colliders: self
+                .colliders
+                .mass_properties_contributions(colliders)
+                .collect(),
+            additional: self.mprops.additional_local_mprops.as_deref().copied(),
+        }
+    }
+
     /// Adds extra mass on top of collider-computed mass.
     ///
     /// Total mass = collider masses + this additional mass. Use when you want to make
@@ -1065,6 +1246,39 @@ impl RigidBody {
         }
     }
 
+    /// The path followed by this rigid-body, if any.
+    pub fn kinematic_path(&self) -> Option<&KinematicPath> {
+        self.kinematic_path.as_deref()
+    }
+
+    /// Mutable reference to the path followed by this rigid-body, if any.
+    pub fn kinematic_path_mut(&mut self) -> Option<&mut KinematicPath> {
+        self.kinematic_path.as_deref_mut()
+    }
+
+    /// Sets the path followed by this rigid-body.
+    ///
+    /// This only has an effect on position-based kinematic rigid-bodies: at each timestep, the
+    /// physics pipeline will set their next kinematic position by sampling the path, instead of
+    /// relying on [`Self::set_next_kinematic_position`]. The rigid-body doesn’t fall asleep until
+    /// the end of a non-looping path is reached.
+    pub fn set_kinematic_path(&mut self, path: Option<KinematicPath>) {
+        self.kinematic_path = path.map(Box::new);
+
+        if self.kinematic_path.is_some() && self.is_kinematic() {
+            self.wake_up(true);
+        }
+    }
+
+    /// Is this rigid-body currently moved by a [`KinematicPath`] that hasn’t reached its end?
+    pub(crate) fn follows_kinematic_path(&self) -> bool {
+        self.body_type == RigidBodyType::KinematicPositionBased
+            && self
+                .kinematic_path
+                .as_ref()
+                .is_some_and(|path| !path.is_finished())
+    }
+
     /// Predicts the next position of this rigid-body, by integrating its velocity and forces
     /// by a time of `dt`.
     pub(crate) fn predict_position_using_velocity_and_forces_with_max_dist(
@@ -1313,6 +1527,11 @@ impl RigidBody {
     /// // Box will move right AND spin
     /// ```
     ///
+    /// The `point` is expressed in world-space. The induced angular impulse is
+    /// `(point - world_com) × impulse`, where `world_com` is the world-space center-of-mass
+    /// of this rigid-body (it is a scalar in 2D). As for [`Self::apply_impulse`], the
+    /// rigid-body is woken up if `wake_up` is `true` and the impulse is nonzero.
+    ///
     /// Only affects dynamic bodies.
     pub fn apply_impulse_at_point(
         &mut self,
@@ -1381,6 +1600,14 @@ impl RigidBody {
         self.vels.velocity_at_point(point, &self.mprops.world_com)
     }
 
+    /// Calculates the velocity at a specific point on this body, given in its local-space.
+    ///
+    /// This is the same as [`Self::velocity_at_point`] after transforming `local_point` into
+    /// world-space with the body’s current position. The returned velocity is in world-space.
+    pub fn velocity_at_point_local(&self, local_point: &Point<Real>) -> Vector<Real> {
+        self.velocity_at_point(&(self.pos.position * local_point))
+    }
+
     /// Calculates the kinetic energy of this body (energy from motion).
     ///
     /// Returns `0.5 * mass * velocity² + 0.5 * inertia * angular_velocity²`
@@ -1486,7 +1713,14 @@ pub struct RigidBodyBuilder {
     /// It is a generally cheaper variant of regular CCD (that can be enabled with
     /// [`RigidBodyBuilder::ccd_enabled`] since it relies on predictive constraints instead of
     /// shape-cast and substeps.
+    ///
+    /// See [`RigidBody::set_soft_ccd_prediction`] for details on when to prefer it over
+    /// regular CCD.
     pub soft_ccd_prediction: Real,
+    /// The components of the rigid-body’s motion stopped by the CCD solver at a time of impact.
+    ///
+    /// See [`RigidBody::set_ccd_freeze_mode`] for additional information.
+    pub ccd_freeze_mode: CcdFreezeMode,
     /// The dominance group of the rigid-body to be built.
     pub dominance_group: i8,
     /// Will the rigid-body being built be enabled?
@@ -1525,6 +1759,7 @@ impl RigidBodyBuilder {
             sleeping: false,
             ccd_enabled: false,
             soft_ccd_prediction: 0.0,
+            ccd_freeze_mode: CcdFreezeMode::All,
             dominance_group: 0,
             enabled: true,
             user_data: 0,
@@ -1888,6 +2123,15 @@ impl RigidBodyBuilder {
         self
     }
 
+    /// Sets which components of the rigid-body’s motion are stopped by the CCD solver at a
+    /// time of impact.
+    ///
+    /// See [`RigidBody::set_ccd_freeze_mode`] for additional information.
+    pub fn ccd_freeze_mode(mut self, mode: CcdFreezeMode) -> Self {
+        self.ccd_freeze_mode = mode;
+        self
+    }
+
     /// Sets whether the rigid-body is to be created asleep.
     pub fn sleeping(mut self, sleeping: bool) -> Self {
         self.sleeping = sleeping;
@@ -1942,6 +2186,7 @@ impl RigidBodyBuilder {
         rb.enabled = self.enabled;
         rb.enable_ccd(self.ccd_enabled);
         rb.set_soft_ccd_prediction(self.soft_ccd_prediction);
+        rb.set_ccd_freeze_mode(self.ccd_freeze_mode);
 
         if self.can_sleep && self.sleeping {
             rb.sleep();
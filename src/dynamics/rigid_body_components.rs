@@ -12,6 +12,8 @@ use crate::math::{
     AngVector, AngularInertia, Isometry, Point, Real, Rotation, Translation, Vector,
 };
 use crate::utils::{SimdAngularInertia, SimdCross, SimdDot, SimdRealCopy};
+#[cfg(feature = "dim3")]
+use na::Unit;
 use num::Zero;
 
 /// The unique handle of a rigid body added to a `RigidBodySet`.
@@ -314,6 +316,36 @@ bitflags::bitflags! {
     }
 }
 
+/// The individual contributions to the mass-properties of a rigid-body.
+///
+/// See [`RigidBody::mass_properties_breakdown`](crate::dynamics::RigidBody::mass_properties_breakdown).
+#[derive(Clone, Debug, PartialEq)]
+pub struct RigidBodyMassPropsBreakdown {
+    /// The mass-properties of each enabled collider attached to the rigid-body, expressed in the
+    /// local-space of the rigid-body (so their `local_com` is the center-of-mass of the
+    /// collider relative to the rigid-body).
+    pub colliders: Vec<(ColliderHandle, MassProperties)>,
+    /// The additional mass-properties explicitly set on the rigid-body, if any.
+    pub additional: Option<RigidBodyAdditionalMassProps>,
+}
+
+impl RigidBodyMassPropsBreakdown {
+    /// Does this rigid-body combine colliders with a nonzero mass and an explicit additional mass?
+    ///
+    /// In that case, the total mass of the rigid-body is the sum of both, which may not be the
+    /// mass that was intended when setting one of them.
+    pub fn has_mass_conflict(&self) -> bool {
+        let colliders_have_mass = self.colliders.iter().any(|(_, mprops)| mprops.mass() > 0.0);
+        let additional_mass = match self.additional {
+            Some(RigidBodyAdditionalMassProps::MassProps(mprops)) => mprops.mass(),
+            Some(RigidBodyAdditionalMassProps::Mass(mass)) => mass,
+            None => 0.0,
+        };
+
+        colliders_have_mass && additional_mass > 0.0
+    }
+}
+
 /// Mass and angular inertia added to a rigid-body on top of its attached colliders’ contributions.
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -349,6 +381,14 @@ pub struct RigidBodyMassProps {
     pub flags: LockedAxes,
     /// Mass-properties of this rigid-bodies, added to the contributions of its attached colliders.
     pub additional_local_mprops: Option<Box<RigidBodyAdditionalMassProps>>,
+    /// A user-defined local center-of-mass, replacing the one computed from the attached colliders.
+    pub local_com_override: Option<Point<Real>>,
+    /// The local center-of-mass computed from the attached colliders, before applying
+    /// `local_com_override`.
+    pub(crate) natural_local_com: Point<Real>,
+    /// If set, the world-space axis this rigid-body is only allowed to rotate along.
+    #[cfg(feature = "dim3")]
+    pub locked_rotation_axis: Option<Unit<Vector<Real>>>,
 }
 
 impl Default for RigidBodyMassProps {
@@ -357,6 +397,10 @@ impl Default for RigidBodyMassProps {
             flags: LockedAxes::empty(),
             local_mprops: MassProperties::zero(),
             additional_local_mprops: None,
+            local_com_override: None,
+            natural_local_com: Point::origin(),
+            #[cfg(feature = "dim3")]
+            locked_rotation_axis: None,
             world_com: Point::origin(),
             effective_inv_mass: Vector::zero(),
             effective_world_inv_inertia: AngularInertia::zero(),
@@ -400,6 +444,14 @@ impl RigidBodyMassProps {
     /// this rigid-body.
     #[must_use]
     pub fn effective_angular_inertia(&self) -> AngularInertia<Real> {
+        // The effective inverse angular inertia is the projection of the inverse angular
+        // inertia on the allowed rotation axis, so its pseudo-inverse is returned instead.
+        #[cfg(feature = "dim3")]
+        if let Some(axis) = self.locked_rotation_axis {
+            let inv_inertia = axis.dot(&(self.effective_world_inv_inertia * *axis));
+            return axis_outer_product(&axis, crate::utils::inv(inv_inertia));
+        }
+
         #[allow(unused_mut)] // mut needed in 3D.
         let mut ang_inertia = self.effective_world_inv_inertia;
 
@@ -453,18 +505,8 @@ impl RigidBodyMassProps {
 
         self.local_mprops = MassProperties::default();
 
-        for handle in &attached_colliders.0 {
-            if let Some(co) = colliders.get(*handle) {
-                if co.is_enabled() {
-                    if let Some(co_parent) = co.parent {
-                        let to_add = co
-                            .mprops
-                            .mass_properties(&*co.shape)
-                            .transform_by(&co_parent.pos_wrt_parent);
-                        self.local_mprops += to_add;
-                    }
-                }
-            }
+        for (_, to_add) in attached_colliders.mass_properties_contributions(colliders) {
+            self.local_mprops += to_add;
         }
 
         match added_mprops {
@@ -477,6 +519,13 @@ impl RigidBodyMassProps {
             }
         }
 
+        // NOTE: only the center-of-mass is overridden. The mass and principal angular inertia
+        //       remain the ones computed from the colliders and additional mass-properties.
+        self.natural_local_com = self.local_mprops.local_com;
+        if let Some(local_com) = self.local_com_override {
+            self.local_mprops.local_com = local_com;
+        }
+
         self.update_world_mass_properties(body_type, position);
     }
 
@@ -528,10 +577,30 @@ impl RigidBodyMassProps {
                 self.effective_world_inv_inertia.m13 = 0.0;
                 self.effective_world_inv_inertia.m23 = 0.0;
             }
+
+            // Only allow rotations along the locked rotation axis by projecting the
+            // inverse angular inertia on it.
+            if let Some(axis) = self.locked_rotation_axis {
+                let inv_inertia = axis.dot(&(self.effective_world_inv_inertia * *axis));
+                self.effective_world_inv_inertia = axis_outer_product(&axis, inv_inertia);
+            }
         }
     }
 }
 
+/// Computes `scale * axis * axis^T`.
+#[cfg(feature = "dim3")]
+fn axis_outer_product(axis: &Unit<Vector<Real>>, scale: Real) -> AngularInertia<Real> {
+    AngularInertia::new(
+        scale * axis.x * axis.x,
+        scale * axis.x * axis.y,
+        scale * axis.x * axis.z,
+        scale * axis.y * axis.y,
+        scale * axis.y * axis.z,
+        scale * axis.z * axis.z,
+    )
+}
+
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Copy, PartialEq)]
 /// The velocities of this rigid-body.
@@ -745,9 +814,27 @@ impl<T: SimdRealCopy> RigidBodyVelocity<T> {
     #[must_use]
     pub fn apply_damping(&self, dt: T, damping: &RigidBodyDamping<T>) -> Self {
         let one = T::one();
+
+        // NOTE: the quadratic coefficients scale with the velocity magnitude so the drag
+        //       force grows with the square of the speed. They are zero by default, so skip
+        //       computing the velocity magnitudes in that case.
+        let mut linear_damping = damping.linear_damping;
+        if !damping.linear_damping_quadratic.is_zero() {
+            linear_damping += damping.linear_damping_quadratic * self.linvel.norm();
+        }
+
+        let mut angular_damping = damping.angular_damping;
+        if !damping.angular_damping_quadratic.is_zero() {
+            #[cfg(feature = "dim2")]
+            let angvel_norm = self.angvel.simd_abs();
+            #[cfg(feature = "dim3")]
+            let angvel_norm = self.angvel.norm();
+            angular_damping += damping.angular_damping_quadratic * angvel_norm;
+        }
+
         RigidBodyVelocity {
-            linvel: self.linvel * (one / (one + dt * damping.linear_damping)),
-            angvel: self.angvel * (one / (one + dt * damping.angular_damping)),
+            linvel: self.linvel * (one / (one + dt * linear_damping)),
+            angvel: self.angvel * (one / (one + dt * angular_damping)),
         }
     }
 
@@ -862,6 +949,12 @@ pub struct RigidBodyDamping<T> {
     pub linear_damping: T,
     /// Damping factor for gradually slowing down the angular motion of the rigid-body.
     pub angular_damping: T,
+    /// Damping factor, scaled by the linear velocity magnitude, for modeling a drag
+    /// growing with the square of the translational speed.
+    pub linear_damping_quadratic: T,
+    /// Damping factor, scaled by the angular velocity magnitude, for modeling a drag
+    /// growing with the square of the angular speed.
+    pub angular_damping_quadratic: T,
 }
 
 impl<T: SimdRealCopy> Default for RigidBodyDamping<T> {
@@ -869,6 +962,8 @@ impl<T: SimdRealCopy> Default for RigidBodyDamping<T> {
         Self {
             linear_damping: T::zero(),
             angular_damping: T::zero(),
+            linear_damping_quadratic: T::zero(),
+            angular_damping_quadratic: T::zero(),
         }
     }
 }
@@ -948,6 +1043,78 @@ impl RigidBodyForces {
     }
 }
 
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash, Default)]
+/// Selects which components of a rigid-body’s motion are stopped by the CCD solver
+/// when it reaches a time of impact.
+pub enum CcdFreezeMode {
+    /// Both the translational and rotational motions are stopped at the time of impact.
+    #[default]
+    All,
+    /// Only the translational motion is stopped at the time of impact. The rigid-body
+    /// keeps rotating until the end of the timestep.
+    Linear,
+    /// Only the rotational motion is stopped at the time of impact. The rigid-body
+    /// keeps translating until the end of the timestep.
+    Angular,
+}
+
+impl CcdFreezeMode {
+    /// Is the translational motion stopped at the time of impact?
+    pub fn freezes_linear(self) -> bool {
+        self != CcdFreezeMode::Angular
+    }
+
+    /// Is the rotational motion stopped at the time of impact?
+    pub fn freezes_angular(self) -> bool {
+        self != CcdFreezeMode::Linear
+    }
+
+    /// The velocities of a rigid-body after it was frozen with this mode.
+    pub fn frozen_velocity(self, vels: &RigidBodyVelocity<Real>) -> RigidBodyVelocity<Real> {
+        RigidBodyVelocity {
+            linvel: if self.freezes_linear() {
+                na::zero()
+            } else {
+                vels.linvel
+            },
+            angvel: if self.freezes_angular() {
+                na::zero()
+            } else {
+                vels.angvel
+            },
+        }
+    }
+
+    /// Integrates the velocities `vels` from `init_pos` up to `end_time`, taking into account
+    /// that the components selected by this mode are frozen at `freeze_time`.
+    pub fn integrate_frozen(
+        self,
+        vels: &RigidBodyVelocity<Real>,
+        freeze_time: Real,
+        end_time: Real,
+        init_pos: &Isometry<Real>,
+        local_com: &Point<Real>,
+    ) -> Isometry<Real> {
+        if self == CcdFreezeMode::All {
+            return vels.integrate(freeze_time, init_pos, local_com);
+        }
+
+        let lin_time = if self.freezes_linear() {
+            freeze_time
+        } else {
+            end_time
+        };
+        let ang_time = if self.freezes_angular() {
+            freeze_time
+        } else {
+            end_time
+        };
+        RigidBodyVelocity::new(vels.linvel * lin_time, vels.angvel * ang_time)
+            .integrate(1.0, init_pos, local_com)
+    }
+}
+
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Copy, PartialEq)]
 /// Information used for Continuous-Collision-Detection.
@@ -964,10 +1131,15 @@ pub struct RigidBodyCcd {
     /// `true` when the CCD solver detects that the rigid-body is moving fast
     /// enough to potential cause a tunneling problem.
     pub ccd_active: bool,
+    /// Was the motion of this rigid-body clamped by the CCD solver at a time of impact during
+    /// the last timestep?
+    pub ccd_clamped: bool,
     /// Is CCD enabled for this rigid-body?
     pub ccd_enabled: bool,
     /// The soft-CCD prediction distance for this rigid-body.
     pub soft_ccd_prediction: Real,
+    /// The components of this rigid-body’s motion stopped by the CCD solver at a time of impact.
+    pub ccd_freeze_mode: CcdFreezeMode,
 }
 
 impl Default for RigidBodyCcd {
@@ -976,8 +1148,10 @@ impl Default for RigidBodyCcd {
             ccd_thickness: Real::MAX,
             ccd_max_dist: 0.0,
             ccd_active: false,
+            ccd_clamped: false,
             ccd_enabled: false,
             soft_ccd_prediction: 0.0,
+            ccd_freeze_mode: CcdFreezeMode::All,
         }
     }
 }
@@ -1031,6 +1205,9 @@ pub struct RigidBodyIds {
     pub(crate) active_set_id: usize,
     pub(crate) active_set_offset: u32,
     pub(crate) active_set_timestamp: u32,
+    pub(crate) sleeping_island_id: usize,
+    // Whether the last sleep event emitted for this body was a sleeping one.
+    pub(crate) reported_sleeping: bool,
 }
 
 impl Default for RigidBodyIds {
@@ -1040,6 +1217,8 @@ impl Default for RigidBodyIds {
             active_set_id: usize::MAX,
             active_set_offset: u32::MAX,
             active_set_timestamp: 0,
+            sleeping_island_id: usize::MAX,
+            reported_sleeping: false,
         }
     }
 }
@@ -1082,6 +1261,29 @@ impl RigidBodyColliders {
         co_parent: &ColliderParent,
         co_shape: &ColliderShape,
         co_mprops: &ColliderMassProps,
+    ) {
+        self.attach_collider_without_world_mprops(
+            rb_changes, rb_ccd, rb_mprops, rb_pos, co_handle, co_pos, co_parent, co_shape,
+            co_mprops,
+        );
+        rb_mprops.update_world_mass_properties(rb_type, &rb_pos.position);
+    }
+
+    /// Attach a collider to this rigid-body without updating its world-space mass properties.
+    ///
+    /// This allows attaching several colliders before updating the world-space mass
+    /// properties only once.
+    pub(crate) fn attach_collider_without_world_mprops(
+        &mut self,
+        rb_changes: &mut RigidBodyChanges,
+        rb_ccd: &mut RigidBodyCcd,
+        rb_mprops: &mut RigidBodyMassProps,
+        rb_pos: &RigidBodyPosition,
+        co_handle: ColliderHandle,
+        co_pos: &mut ColliderPosition,
+        co_parent: &ColliderParent,
+        co_shape: &ColliderShape,
+        co_mprops: &ColliderMassProps,
     ) {
         rb_changes.set(
             RigidBodyChanges::MODIFIED | RigidBodyChanges::COLLIDERS,
@@ -1100,8 +1302,33 @@ impl RigidBodyColliders {
             .mass_properties(&**co_shape)
             .transform_by(&co_parent.pos_wrt_parent);
         self.0.push(co_handle);
+
+        // Accumulate the mass properties with the computed center-of-mass, and apply the
+        // override afterward, like `RigidBodyMassProps::recompute_mass_properties_from_colliders`.
+        if rb_mprops.local_com_override.is_some() {
+            rb_mprops.local_mprops.local_com = rb_mprops.natural_local_com;
+        }
         rb_mprops.local_mprops += mass_properties;
-        rb_mprops.update_world_mass_properties(rb_type, &rb_pos.position);
+        rb_mprops.natural_local_com = rb_mprops.local_mprops.local_com;
+        if let Some(local_com) = rb_mprops.local_com_override {
+            rb_mprops.local_mprops.local_com = local_com;
+        }
+    }
+
+    /// The mass-properties of each enabled attached collider, in the local-space of the rigid-body.
+    pub(crate) fn mass_properties_contributions<'a>(
+        &'a self,
+        colliders: &'a ColliderSet,
+    ) -> impl Iterator<Item = (ColliderHandle, MassProperties)> + 'a {
+        self.0.iter().filter_map(|handle| {
+            let co = colliders.get(*handle)?;
+            let co_parent = co.parent.filter(|_| co.is_enabled())?;
+            let mprops = co
+                .mprops
+                .mass_properties(&*co.shape)
+                .transform_by(&co_parent.pos_wrt_parent);
+            Some((*handle, mprops))
+        })
     }
 
     /// Update the positions of all the colliders attached to this rigid-body.
@@ -1318,4 +1545,129 @@ mod tests {
             approx::assert_relative_eq!(interp_pos, next_pos, epsilon = 1.0e-5);
         }
     }
+
+    #[test]
+    fn quadratic_damping_grows_with_speed() {
+        let dt = 0.1;
+        let damping = RigidBodyDamping {
+            linear_damping: 0.5,
+            linear_damping_quadratic: 2.0,
+            ..Default::default()
+        };
+
+        let slow = RigidBodyVelocity::<Real> {
+            linvel: Vector::x(),
+            angvel: na::zero(),
+        };
+        let fast = RigidBodyVelocity::<Real> {
+            linvel: Vector::x() * 10.0,
+            angvel: na::zero(),
+        };
+
+        // v / (1 + (c_lin + c_quad * |v|) * dt)
+        let slow_damped = slow.apply_damping(dt, &damping);
+        let fast_damped = fast.apply_damping(dt, &damping);
+        approx::assert_relative_eq!(slow_damped.linvel.x, 1.0 / 1.25);
+        approx::assert_relative_eq!(fast_damped.linvel.x, 10.0 / 3.05);
+
+        // Without quadratic damping, the behavior of the linear damping is unchanged.
+        let linear_only = RigidBodyDamping {
+            linear_damping: 0.5,
+            ..Default::default()
+        };
+        approx::assert_relative_eq!(fast.apply_damping(dt, &linear_only).linvel.x, 10.0 / 1.05);
+    }
+
+    #[test]
+    fn local_com_override_survives_collider_changes() {
+        use crate::dynamics::{RigidBodyBuilder, RigidBodySet};
+        use crate::geometry::{ColliderBuilder, ColliderSet};
+
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let handle = bodies.insert(RigidBodyBuilder::dynamic());
+        colliders.insert_with_parent(ColliderBuilder::ball(0.5), handle, &mut bodies);
+
+        let local_com = Point::from(Vector::x() * 2.0);
+        bodies[handle].set_local_center_of_mass(local_com);
+        bodies[handle].recompute_mass_properties_from_colliders(&colliders);
+        let ball_mass = bodies[handle].mass();
+        assert_eq!(*bodies[handle].local_center_of_mass(), local_com);
+
+        // Adding a collider changes the mass, but not the center-of-mass.
+        let collider = ColliderBuilder::ball(0.5).translation(-Vector::x() * 3.0);
+        colliders.insert_with_parent(collider, handle, &mut bodies);
+        bodies[handle].recompute_mass_properties_from_colliders(&colliders);
+        approx::assert_relative_eq!(bodies[handle].mass(), ball_mass * 2.0);
+        assert_eq!(*bodies[handle].local_center_of_mass(), local_com);
+
+        bodies[handle].clear_local_center_of_mass_override();
+        bodies[handle].recompute_mass_properties_from_colliders(&colliders);
+        approx::assert_relative_eq!(
+            *bodies[handle].local_center_of_mass(),
+            Point::from(-Vector::x() * 1.5),
+            epsilon = 1.0e-5
+        );
+    }
+
+    #[test]
+    fn local_com_override_is_applied_after_attaching_colliders() {
+        use crate::dynamics::{RigidBodyBuilder, RigidBodySet};
+        use crate::geometry::{ColliderBuilder, ColliderSet};
+
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let handle = bodies.insert(RigidBodyBuilder::dynamic());
+        colliders.insert_with_parent(ColliderBuilder::ball(0.5), handle, &mut bodies);
+        bodies[handle].set_local_center_of_mass(Point::from(Vector::x() * 2.0));
+        bodies[handle].recompute_mass_properties_from_colliders(&colliders);
+
+        // Attaching colliders updates the mass properties incrementally.
+        for x in [-3.0, 1.0] {
+            let collider = ColliderBuilder::ball(0.25).translation(Vector::x() * x);
+            colliders.insert_with_parent(collider, handle, &mut bodies);
+        }
+        let incremental = bodies[handle].mass_properties().local_mprops;
+
+        bodies[handle].recompute_mass_properties_from_colliders(&colliders);
+        let recomputed = bodies[handle].mass_properties().local_mprops;
+
+        approx::assert_relative_eq!(incremental.inv_mass, recomputed.inv_mass);
+        assert_eq!(incremental.local_com, recomputed.local_com);
+        approx::assert_relative_eq!(
+            incremental.reconstruct_inertia_matrix(),
+            recomputed.reconstruct_inertia_matrix(),
+            max_relative = 1.0e-5
+        );
+    }
+
+    #[test]
+    fn mass_properties_breakdown_detects_conflicts() {
+        use crate::dynamics::{RigidBodyBuilder, RigidBodySet};
+        use crate::geometry::{ColliderBuilder, ColliderSet};
+
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let handle = bodies.insert(RigidBodyBuilder::dynamic());
+        let collider = ColliderBuilder::ball(0.5).translation(Vector::x() * 2.0);
+        let co_handle = colliders.insert_with_parent(collider, handle, &mut bodies);
+
+        let breakdown = bodies[handle].mass_properties_breakdown(&colliders);
+        assert_eq!(breakdown.colliders.len(), 1);
+        assert_eq!(breakdown.colliders[0].0, co_handle);
+        assert_eq!(
+            breakdown.colliders[0].1.local_com,
+            Point::from(Vector::x() * 2.0)
+        );
+        assert!(breakdown.additional.is_none());
+        assert!(!breakdown.has_mass_conflict());
+
+        bodies[handle].set_additional_mass(5.0, true);
+        let breakdown = bodies[handle].mass_properties_breakdown(&colliders);
+        assert_eq!(
+            breakdown.additional,
+            Some(RigidBodyAdditionalMassProps::Mass(5.0))
+        );
+        assert!(breakdown.has_mass_conflict());
+    }
 }
@@ -4,6 +4,7 @@ use crate::dynamics::{
     RigidBodyChanges, RigidBodyHandle,
 };
 use crate::geometry::ColliderSet;
+use crate::math::Real;
 use std::ops::{Index, IndexMut};
 
 #[cfg(doc)]
@@ -403,6 +404,14 @@ impl RigidBodySet {
         })
     }
 
+    /// The sum of the kinetic energies of all the rigid bodies in this set.
+    ///
+    /// See [`RigidBody::kinetic_energy`]. Monitoring this value is a convenient way of detecting
+    /// unstable simulations that inject energy into the system.
+    pub fn total_kinetic_energy(&self) -> Real {
+        self.bodies.iter().map(|(_, rb)| rb.kinetic_energy()).sum()
+    }
+
     /// Updates the positions of all colliders attached to bodies that have moved.
     ///
     /// Normally you don't need to call this - it's automatically handled by [`PhysicsPipeline::step`].
@@ -46,6 +46,134 @@ impl<N: SimdRealCopy> ContactConstraintTwistPart<N> {
     }
 }
 
+/// Angular constraint opposing the relative rolling motion of two bodies in contact.
+///
+/// Its impulse is bounded by the rolling resistance coefficient multiplied by the normal
+/// impulses of the contact manifold.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct ContactConstraintRollingPart<N: SimdRealCopy> {
+    // NOTE: the rolling directions are the tangents in 3D and 1.0 in 2D.
+    pub ii_rolling_dir1: [AngVector<N>; DIM - 1],
+    pub ii_rolling_dir2: [AngVector<N>; DIM - 1],
+    pub impulse: TangentImpulse<N>,
+    #[cfg(feature = "dim2")]
+    pub r: [N; 1],
+    #[cfg(feature = "dim3")]
+    pub r: [N; DIM],
+}
+
+impl<N: SimdRealCopy> ContactConstraintRollingPart<N> {
+    pub fn zero() -> Self {
+        Self {
+            ii_rolling_dir1: [na::zero(); DIM - 1],
+            ii_rolling_dir2: [na::zero(); DIM - 1],
+            impulse: na::zero(),
+            #[cfg(feature = "dim2")]
+            r: [na::zero(); 1],
+            #[cfg(feature = "dim3")]
+            r: [na::zero(); DIM],
+        }
+    }
+
+    /// Initializes the projected masses of this constraint from the rolling directions
+    /// and their images by the inverse angular inertia of each body.
+    #[inline]
+    pub fn init(
+        &mut self,
+        rolling_dirs1: [AngVector<N>; DIM - 1],
+        ii_rolling_dir1: [AngVector<N>; DIM - 1],
+        ii_rolling_dir2: [AngVector<N>; DIM - 1],
+    ) where
+        AngVector<N>: SimdDot<AngVector<N>, Result = N>,
+    {
+        self.ii_rolling_dir1 = ii_rolling_dir1;
+        self.ii_rolling_dir2 = ii_rolling_dir2;
+        self.impulse = na::zero();
+
+        #[cfg(feature = "dim2")]
+        {
+            self.r[0] = crate::utils::simd_inv(
+                ii_rolling_dir1[0].gdot(rolling_dirs1[0])
+                    + ii_rolling_dir2[0].gdot(-rolling_dirs1[0]),
+            );
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            for j in 0..DIM - 1 {
+                self.r[j] = ii_rolling_dir1[j].gdot(rolling_dirs1[j])
+                    + ii_rolling_dir2[j].gdot(-rolling_dirs1[j]);
+            }
+
+            self.r[2] = N::splat(2.0)
+                * (ii_rolling_dir1[0].gdot(rolling_dirs1[1])
+                    + ii_rolling_dir2[0].gdot(-rolling_dirs1[1]));
+        }
+    }
+
+    #[inline]
+    pub fn warmstart(&mut self, solver_vel1: &mut SolverVel<N>, solver_vel2: &mut SolverVel<N>) {
+        for j in 0..DIM - 1 {
+            solver_vel1.angular += self.ii_rolling_dir1[j] * self.impulse[j];
+            solver_vel2.angular += self.ii_rolling_dir2[j] * self.impulse[j];
+        }
+    }
+
+    #[inline]
+    pub fn solve(
+        &mut self,
+        rolling_dirs1: [&AngVector<N>; DIM - 1],
+        limit: N,
+        solver_vel1: &mut SolverVel<N>,
+        solver_vel2: &mut SolverVel<N>,
+    ) where
+        AngVector<N>: SimdDot<AngVector<N>, Result = N>,
+    {
+        let dangvel = solver_vel1.angular - solver_vel2.angular;
+
+        #[cfg(feature = "dim2")]
+        {
+            let dvel = rolling_dirs1[0].gdot(dangvel);
+            let new_impulse = (self.impulse[0] - self.r[0] * dvel).simd_clamp(-limit, limit);
+            let dlambda = new_impulse - self.impulse[0];
+            self.impulse[0] = new_impulse;
+
+            solver_vel1.angular += self.ii_rolling_dir1[0] * dlambda;
+            solver_vel2.angular += self.ii_rolling_dir2[0] * dlambda;
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            let dvel_0 = rolling_dirs1[0].gdot(dangvel);
+            let dvel_1 = rolling_dirs1[1].gdot(dangvel);
+
+            let dvel_00 = dvel_0 * dvel_0;
+            let dvel_11 = dvel_1 * dvel_1;
+            let dvel_01 = dvel_0 * dvel_1;
+            let inv_lhs = (dvel_00 + dvel_11)
+                * crate::utils::simd_inv(
+                    dvel_00 * self.r[0] + dvel_11 * self.r[1] + dvel_01 * self.r[2],
+                );
+            let delta_impulse = na::vector![inv_lhs * dvel_0, inv_lhs * dvel_1];
+            let new_impulse = self.impulse - delta_impulse;
+            let new_impulse = {
+                let _disable_fe_except =
+                        crate::utils::DisableFloatingPointExceptionsFlags::
+                        disable_floating_point_exceptions();
+                new_impulse.simd_cap_magnitude(limit)
+            };
+
+            let dlambda = new_impulse - self.impulse;
+            self.impulse = new_impulse;
+
+            solver_vel1.angular +=
+                self.ii_rolling_dir1[0] * dlambda[0] + self.ii_rolling_dir1[1] * dlambda[1];
+            solver_vel2.angular +=
+                self.ii_rolling_dir2[0] * dlambda[0] + self.ii_rolling_dir2[1] * dlambda[1];
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub(crate) struct ContactConstraintTangentPart<N: SimdRealCopy> {
     pub torque_dir1: [AngVector<N>; DIM - 1],
@@ -1,4 +1,6 @@
-use super::{ContactConstraintNormalPart, ContactConstraintTangentPart};
+use super::{
+    ContactConstraintNormalPart, ContactConstraintRollingPart, ContactConstraintTangentPart,
+};
 use crate::dynamics::integration_parameters::BLOCK_SOLVER_ENABLED;
 use crate::dynamics::solver::solver_body::SolverBodies;
 use crate::dynamics::{IntegrationParameters, MultibodyJointSet, RigidBodySet};
@@ -9,7 +11,7 @@ use crate::utils::SimdBasis;
 use crate::utils::{self, SimdAngularInertia, SimdCross, SimdDot, SimdRealCopy};
 use num::Zero;
 use parry::utils::SdpMatrix2;
-use simba::simd::{SimdPartialOrd, SimdValue};
+use simba::simd::{SimdBool, SimdPartialOrd, SimdValue};
 
 #[derive(Copy, Clone, Debug)]
 pub struct CoulombContactPointInfos<N: SimdRealCopy> {
@@ -75,6 +77,8 @@ impl ContactWithCoulombFrictionBuilder {
 
         // TODO PERF: implement SIMD gather
         let force_dir1 = -Vector::<SimdReal>::from(gather![|ii| manifolds[ii].data.normal]);
+        let rolling_resistance =
+            SimdReal::from(gather![|ii| manifolds[ii].data.rolling_resistance]);
         let num_active_contacts = manifolds[0].data.num_active_contacts();
 
         #[cfg(feature = "dim2")]
@@ -197,6 +201,18 @@ impl ContactWithCoulombFrictionBuilder {
                 builder.infos[k].normal_vel = normal_rhs_wo_bias;
             }
 
+            // Rolling part.
+            #[cfg(feature = "dim2")]
+            let rolling_dirs1 = [SimdReal::splat(1.0)];
+            #[cfg(feature = "dim3")]
+            let rolling_dirs1 = tangents1;
+            constraint.rolling_resistance = rolling_resistance;
+            constraint.rolling_part.init(
+                rolling_dirs1,
+                rolling_dirs1.map(|dir| poses1.ii.transform_vector(dir)),
+                rolling_dirs1.map(|dir| poses2.ii.transform_vector(-dir)),
+            );
+
             if BLOCK_SOLVER_ENABLED {
                 // Coupling between consecutive pairs.
                 for k in 0..num_points / 2 {
@@ -312,6 +328,7 @@ impl ContactWithCoulombFrictionBuilder {
             }
         }
 
+        constraint.rolling_part.impulse *= warmstart_coeff;
         constraint.cfm_factor = cfm_factor;
     }
 }
@@ -329,6 +346,8 @@ pub(crate) struct ContactWithCoulombFriction {
     pub tangent1: Vector<SimdReal>, // One of the friction force directions.
     pub normal_part: [ContactConstraintNormalPart<SimdReal>; MAX_MANIFOLD_POINTS],
     pub tangent_part: [ContactConstraintTangentPart<SimdReal>; MAX_MANIFOLD_POINTS],
+    pub rolling_resistance: SimdReal,
+    pub rolling_part: ContactConstraintRollingPart<SimdReal>,
     pub solver_vel1: [u32; SIMD_WIDTH],
     pub solver_vel2: [u32; SIMD_WIDTH],
     pub manifold_id: [ContactManifoldIndex; SIMD_WIDTH],
@@ -375,6 +394,9 @@ impl ContactWithCoulombFriction {
             );
         }
 
+        self.rolling_part
+            .warmstart(&mut solver_vel1, &mut solver_vel2);
+
         bodies.scatter_vels(self.solver_vel1, solver_vel1);
         bodies.scatter_vels(self.solver_vel2, solver_vel2);
     }
@@ -459,6 +481,25 @@ impl ContactWithCoulombFriction {
                     &mut solver_vel2,
                 );
             }
+
+            if self.rolling_resistance.simd_gt(SimdReal::zero()).any() {
+                #[cfg(feature = "dim2")]
+                let rolling_dirs1 = [&SimdReal::splat(1.0)];
+                #[cfg(feature = "dim3")]
+                let rolling_dirs1 = tangents1;
+
+                let mut rolling_limit = SimdReal::zero();
+                for normal_part in normal_parts.iter() {
+                    rolling_limit += normal_part.impulse;
+                }
+
+                self.rolling_part.solve(
+                    rolling_dirs1,
+                    rolling_limit * self.rolling_resistance,
+                    &mut solver_vel1,
+                    &mut solver_vel2,
+                );
+            }
         }
 
         bodies.scatter_vels(self.solver_vel1, solver_vel1);
@@ -488,6 +529,10 @@ impl ContactWithCoulombFriction {
                         warmstart_tangent_impulses.extract(ii);
                     active_contact.data.impulse = impulses[ii];
                     active_contact.data.tangent_impulse = tangent_impulses.extract(ii);
+                    #[cfg(feature = "dim3")]
+                    {
+                        manifold.data.tangent1 = self.tangent1.extract(ii);
+                    }
                 }
             }
         }
@@ -1,5 +1,6 @@
 use super::{
-    ContactConstraintNormalPart, ContactConstraintTangentPart, ContactConstraintTwistPart,
+    ContactConstraintNormalPart, ContactConstraintRollingPart, ContactConstraintTangentPart,
+    ContactConstraintTwistPart,
 };
 use crate::dynamics::solver::solver_body::SolverBodies;
 use crate::dynamics::{IntegrationParameters, MultibodyJointSet, RigidBodySet};
@@ -9,7 +10,7 @@ use crate::math::{DIM, MAX_MANIFOLD_POINTS, Point, Real, SIMD_WIDTH, SimdReal, V
 use crate::utils::SimdBasis;
 use crate::utils::{self, SimdAngularInertia, SimdCross, SimdDot, SimdRealCopy};
 use num::Zero;
-use simba::simd::{SimdPartialOrd, SimdValue};
+use simba::simd::{SimdBool, SimdPartialOrd, SimdValue};
 
 #[derive(Copy, Clone, Debug)]
 pub struct TwistContactPointInfos<N: SimdRealCopy> {
@@ -82,6 +83,8 @@ impl ContactWithTwistFrictionBuilder {
 
         // TODO PERF: implement SIMD gather
         let force_dir1 = -Vector::<SimdReal>::from(gather![|ii| manifolds[ii].data.normal]);
+        let rolling_resistance =
+            SimdReal::from(gather![|ii| manifolds[ii].data.rolling_resistance]);
         let num_active_contacts = manifolds[0].data.num_active_contacts();
 
         #[cfg(feature = "dim2")]
@@ -176,6 +179,18 @@ impl ContactWithTwistFrictionBuilder {
                 builder.infos[k].normal_vel = normal_rhs_wo_bias;
             }
 
+            // Rolling part.
+            #[cfg(feature = "dim2")]
+            let rolling_dirs1 = [SimdReal::splat(1.0)];
+            #[cfg(feature = "dim3")]
+            let rolling_dirs1 = tangents1;
+            constraint.rolling_resistance = rolling_resistance;
+            constraint.rolling_part.init(
+                rolling_dirs1,
+                rolling_dirs1.map(|dir| poses1.ii.transform_vector(dir)),
+                rolling_dirs1.map(|dir| poses2.ii.transform_vector(-dir)),
+            );
+
             /*
              * Tangent/twist part
              */
@@ -321,6 +336,8 @@ impl ContactWithTwistFrictionBuilder {
             twist_part.impulse *= warmstart_coeff;
         }
 
+        constraint.rolling_part.impulse *= warmstart_coeff;
+
         constraint.cfm_factor = cfm_factor;
     }
 }
@@ -344,6 +361,8 @@ pub(crate) struct ContactWithTwistFriction {
     pub twist_part: ContactConstraintTwistPart<SimdReal>,
     // Distances between the friction center and the contact point.
     pub twist_dists: [SimdReal; MAX_MANIFOLD_POINTS],
+    pub rolling_resistance: SimdReal,
+    pub rolling_part: ContactConstraintRollingPart<SimdReal>,
 
     pub solver_vel1: [u32; SIMD_WIDTH],
     pub solver_vel2: [u32; SIMD_WIDTH],
@@ -389,6 +408,8 @@ impl ContactWithTwistFriction {
         );
         self.twist_part
             .warmstart(&mut solver_vel1, &mut solver_vel2);
+        self.rolling_part
+            .warmstart(&mut solver_vel1, &mut solver_vel2);
 
         bodies.scatter_vels(self.solver_vel1, solver_vel1);
         bodies.scatter_vels(self.solver_vel2, solver_vel2);
@@ -459,6 +480,25 @@ impl ContactWithTwistFriction {
                 self.twist_part
                     .solve(&self.dir1, twist_limit, &mut solver_vel1, &mut solver_vel2);
             }
+
+            if self.rolling_resistance.simd_gt(SimdReal::zero()).any() {
+                #[cfg(feature = "dim2")]
+                let rolling_dirs1 = [&SimdReal::splat(1.0)];
+                #[cfg(feature = "dim3")]
+                let rolling_dirs1 = tangents1;
+
+                let mut rolling_limit = SimdReal::zero();
+                for normal_part in normal_parts.iter() {
+                    rolling_limit += normal_part.impulse;
+                }
+
+                self.rolling_part.solve(
+                    rolling_dirs1,
+                    rolling_limit * self.rolling_resistance,
+                    &mut solver_vel1,
+                    &mut solver_vel2,
+                );
+            }
         }
 
         bodies.scatter_vels(self.solver_vel1, solver_vel1);
@@ -580,6 +580,11 @@ impl GenericContactConstraint {
             active_contact.data.impulse = self.normal_part[k].total_impulse();
             active_contact.data.tangent_impulse = self.tangent_part[k].total_impulse();
         }
+
+        #[cfg(feature = "dim3")]
+        {
+            manifold.data.tangent1 = self.tangent1;
+        }
     }
 
     pub fn remove_cfm_and_bias_from_rhs(&mut self) {
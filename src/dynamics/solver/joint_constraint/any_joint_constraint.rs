@@ -20,7 +20,7 @@ pub enum AnyJointConstraintMut<'a> {
 impl AnyJointConstraintMut<'_> {
     pub fn remove_bias(&mut self) {
         match self {
-            Self::Rigid(c) => c.remove_bias_from_rhs(),
+            Self::Rigid(c) => c.remove_bias_and_compliance_from_rhs(),
             Self::Generic(c) => c.remove_bias_from_rhs(),
             #[cfg(feature = "simd-is-enabled")]
             Self::SimdRigid(c) => c.remove_bias_from_rhs(),
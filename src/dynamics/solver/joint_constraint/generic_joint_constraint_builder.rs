@@ -525,6 +525,10 @@ impl JointConstraintHelper<Real> {
         }
 
         rhs_wo_bias += -motor_params.target_vel;
+        // NOTE: don’t use `clamp`, it panics if `max_vel` is negative or NaN.
+        rhs_wo_bias = rhs_wo_bias
+            .max(-motor_params.max_vel)
+            .min(motor_params.max_vel);
 
         constraint.impulse_bounds = [-motor_params.max_impulse, motor_params.max_impulse];
         constraint.rhs = rhs_wo_bias;
@@ -673,6 +677,9 @@ impl JointConstraintHelper<Real> {
         }
 
         rhs_wo_bias += -motor_params.target_vel;
+        rhs_wo_bias = rhs_wo_bias
+            .max(-motor_params.max_vel)
+            .min(motor_params.max_vel);
 
         constraint.rhs_wo_bias = rhs_wo_bias;
         constraint.rhs = rhs_wo_bias;
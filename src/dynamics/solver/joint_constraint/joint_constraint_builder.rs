@@ -332,24 +332,41 @@ impl<N: SimdRealCopy> JointConstraintHelper<N> {
             }
         }
 
-        // FIXME: handle min limit too.
-
         let dist = lin_jac.norm();
         let inv_dist = crate::utils::simd_inv(dist);
         lin_jac *= inv_dist;
         ang_jac1 *= inv_dist;
         ang_jac2 *= inv_dist;
 
-        let rhs_wo_bias = (dist - limits[1]).simd_min(zero) * N::splat(params.inv_dt());
-
-        let ii_ang_jac1 = body1.ii * ang_jac1;
-        let ii_ang_jac2 = body2.ii * ang_jac2;
-
+        // Only one of the limits can be active at a time: the one closest to the current
+        // distance. The min limit is ignored if it isn’t positive since the distance can’t
+        // be negative. If both limits are equal, the distance is locked.
+        let inv_dt = N::splat(params.inv_dt());
         let erp_inv_dt = N::splat(params.joint_erp_inv_dt());
         let cfm_coeff = N::splat(params.joint_cfm_coeff());
-        let rhs_bias = (dist - limits[1]).simd_max(zero) * erp_inv_dt;
+        let infinity = N::splat(Real::INFINITY);
+        let mid = (limits[0] + limits[1]) * N::splat(0.5);
+        let use_max = mid.simd_le(dist) | limits[0].simd_le(zero);
+        let locked = limits[0].simd_eq(limits[1]);
+
+        let max_err = dist - limits[1];
+        let min_err = dist - limits[0];
+        let rhs_wo_bias = (max_err.simd_min(zero) * inv_dt)
+            .select(use_max, min_err.simd_max(zero) * inv_dt)
+            .select(!locked, zero);
+        let rhs_bias = max_err
+            .simd_max(zero)
+            .select(use_max, min_err.simd_min(zero))
+            .select(!locked, max_err)
+            * erp_inv_dt;
         let rhs = rhs_wo_bias + rhs_bias;
-        let impulse_bounds = [N::zero(), N::splat(Real::INFINITY)];
+        let impulse_bounds = [
+            zero.select(use_max & !locked, -infinity),
+            infinity.select(use_max | locked, zero),
+        ];
+
+        let ii_ang_jac1 = body1.ii * ang_jac1;
+        let ii_ang_jac2 = body2.ii * ang_jac2;
 
         JointConstraint {
             joint_id,
@@ -367,6 +384,7 @@ impl<N: SimdRealCopy> JointConstraintHelper<N> {
             inv_lhs: N::zero(), // Will be set during orthogonalization.
             cfm_coeff,
             cfm_gain: N::zero(),
+            compliance: N::zero(),
             rhs,
             rhs_wo_bias,
             writeback_id,
@@ -402,6 +420,7 @@ impl<N: SimdRealCopy> JointConstraintHelper<N> {
         };
 
         rhs_wo_bias += -target_vel;
+        rhs_wo_bias = rhs_wo_bias.simd_clamp(-motor_params.max_vel, motor_params.max_vel);
 
         constraint.cfm_coeff = motor_params.cfm_coeff;
         constraint.cfm_gain = motor_params.cfm_gain;
@@ -463,6 +482,7 @@ impl<N: SimdRealCopy> JointConstraintHelper<N> {
         };
 
         rhs_wo_bias += -target_vel;
+        rhs_wo_bias = rhs_wo_bias.simd_clamp(-motor_params.max_vel, motor_params.max_vel);
 
         let ii_ang_jac1 = body1.ii * ang_jac1;
         let ii_ang_jac2 = body2.ii * ang_jac2;
@@ -483,6 +503,7 @@ impl<N: SimdRealCopy> JointConstraintHelper<N> {
             inv_lhs: N::zero(), // Will be set during orthogonalization.
             cfm_coeff: motor_params.cfm_coeff,
             cfm_gain: motor_params.cfm_gain,
+            compliance: N::zero(),
             rhs: rhs_wo_bias,
             rhs_wo_bias,
             writeback_id,
@@ -532,6 +553,7 @@ impl<N: SimdRealCopy> JointConstraintHelper<N> {
             inv_lhs: N::zero(), // Will be set during orthogonalization.
             cfm_coeff,
             cfm_gain: N::zero(),
+            compliance: N::zero(),
             rhs: rhs_wo_bias + rhs_bias,
             rhs_wo_bias,
             writeback_id,
@@ -548,13 +570,38 @@ impl<N: SimdRealCopy> JointConstraintHelper<N> {
         limits: [N; 2],
         writeback_id: WritebackId,
     ) -> JointConstraint<N, LANES> {
-        let zero = N::zero();
-        let half = N::splat(0.5);
-        let s_limits = [(limits[0] * half).simd_sin(), (limits[1] * half).simd_sin()];
         #[cfg(feature = "dim2")]
-        let s_ang = (self.ang_err.angle() * half).simd_sin();
+        let s_ang = (self.ang_err.angle() * N::splat(0.5)).simd_sin();
         #[cfg(feature = "dim3")]
         let s_ang = self.ang_err.imag()[_limited_axis];
+        self.limit_angular_with_sin(
+            params,
+            joint_id,
+            body1,
+            body2,
+            _limited_axis,
+            s_ang,
+            limits,
+            writeback_id,
+        )
+    }
+
+    /// Angular limit constraint, given the sine of the half of the current angle along the
+    /// limited axis.
+    fn limit_angular_with_sin<const LANES: usize>(
+        &self,
+        params: &IntegrationParameters,
+        joint_id: [JointIndex; LANES],
+        body1: &JointSolverBody<N, LANES>,
+        body2: &JointSolverBody<N, LANES>,
+        _limited_axis: usize,
+        s_ang: N,
+        limits: [N; 2],
+        writeback_id: WritebackId,
+    ) -> JointConstraint<N, LANES> {
+        let zero = N::zero();
+        let half = N::splat(0.5);
+        let s_limits = [(limits[0] * half).simd_sin(), (limits[1] * half).simd_sin()];
         let min_enabled = s_ang.simd_le(s_limits[0]);
         let max_enabled = s_limits[1].simd_le(s_ang);
 
@@ -593,6 +640,7 @@ impl<N: SimdRealCopy> JointConstraintHelper<N> {
             inv_lhs: N::zero(), // Will be set during orthogonalization.
             cfm_coeff,
             cfm_gain: N::zero(),
+            compliance: N::zero(),
             rhs: rhs_wo_bias + rhs_bias,
             rhs_wo_bias,
             writeback_id,
@@ -635,6 +683,7 @@ impl<N: SimdRealCopy> JointConstraintHelper<N> {
         }
 
         rhs_wo_bias += -motor_params.target_vel;
+        rhs_wo_bias = rhs_wo_bias.simd_clamp(-motor_params.max_vel, motor_params.max_vel);
 
         let ii_ang_jac1 = body1.ii * ang_jac;
         let ii_ang_jac2 = body2.ii * ang_jac;
@@ -655,6 +704,7 @@ impl<N: SimdRealCopy> JointConstraintHelper<N> {
             inv_lhs: N::zero(), // Will be set during orthogonalization.
             cfm_coeff: motor_params.cfm_coeff,
             cfm_gain: motor_params.cfm_gain,
+            compliance: N::zero(),
             rhs: rhs_wo_bias,
             rhs_wo_bias,
             writeback_id,
@@ -702,6 +752,7 @@ impl<N: SimdRealCopy> JointConstraintHelper<N> {
             inv_lhs: N::zero(), // Will be set during orthogonalization.
             cfm_coeff,
             cfm_gain: N::zero(),
+            compliance: N::zero(),
             rhs: rhs_wo_bias + rhs_bias,
             rhs_wo_bias,
             writeback_id,
@@ -757,6 +808,38 @@ impl<N: SimdRealCopy> JointConstraintHelper<N> {
 }
 
 impl JointConstraintHelper<Real> {
+    /// Limits the twist angle along the X axis, after decomposing the relative rotation
+    /// into a swing followed by a twist along X.
+    ///
+    /// Unlike [`Self::limit_angular`], this measures the twist angle independently from the
+    /// swing, which is needed for limiting the twist of joints with a cone limit.
+    #[cfg(feature = "dim3")]
+    pub fn limit_twist(
+        &self,
+        params: &IntegrationParameters,
+        joint_id: [JointIndex; 1],
+        body1: &JointSolverBody<Real, 1>,
+        body2: &JointSolverBody<Real, 1>,
+        limits: [Real; 2],
+        writeback_id: WritebackId,
+    ) -> JointConstraint<Real, 1> {
+        // The twist part of the rotation `q` along X is the normalized `(q.w, q.i, 0, 0)`.
+        // It is undefined when the swing is a half-turn, in which case we pick a zero twist.
+        let (x, w) = (self.ang_err.imag()[0], self.ang_err.scalar());
+        let norm = (x * x + w * w).sqrt();
+        let s_twist = if norm > 1.0e-6 { x / norm } else { 0.0 };
+        self.limit_angular_with_sin(
+            params,
+            joint_id,
+            body1,
+            body2,
+            0,
+            s_twist,
+            limits,
+            writeback_id,
+        )
+    }
+
     #[cfg(feature = "dim3")]
     pub fn limit_angular_coupled(
         &self,
@@ -775,11 +858,19 @@ impl JointConstraintHelper<Real> {
         let axis1 = self.basis.column(not_coupled_index).into_owned();
         let axis2 = self.basis2.column(not_coupled_index).into_owned();
 
-        let rot = Rotation::rotation_between(&axis1, &axis2).unwrap_or_else(Rotation::identity);
-        let (ang_jac, angle) = rot
-            .axis_angle()
+        let (ang_jac, angle) = Rotation::rotation_between(&axis1, &axis2)
+            .and_then(|rot| rot.axis_angle())
             .map(|(axis, angle)| (axis.into_inner(), angle))
-            .unwrap_or_else(|| (axis1.orthonormal_basis()[0], 0.0));
+            .unwrap_or_else(|| {
+                // The axes are either aligned, or opposite (in which case the rotation
+                // between them is undefined, and any orthogonal axis will do).
+                let angle = if axis1.dot(&axis2) < 0.0 {
+                    <Real as na::RealField>::pi()
+                } else {
+                    0.0
+                };
+                (axis1.orthonormal_basis()[0], angle)
+            });
 
         let min_enabled = angle <= limits[0];
         let max_enabled = limits[1] <= angle;
@@ -814,6 +905,7 @@ impl JointConstraintHelper<Real> {
             inv_lhs: 0.0, // Will be set during orthogonalization.
             cfm_coeff,
             cfm_gain: 0.0,
+            compliance: 0.0,
             rhs: rhs_wo_bias + rhs_bias,
             rhs_wo_bias,
             writeback_id,
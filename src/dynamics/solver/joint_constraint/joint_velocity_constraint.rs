@@ -4,7 +4,7 @@ use crate::dynamics::{
     GenericJoint, IntegrationParameters, JointAxesMask, JointGraphEdge, JointIndex,
 };
 use crate::math::{AngVector, AngularInertia, DIM, Isometry, Point, Real, SPATIAL_DIM, Vector};
-use crate::utils::{SimdDot, SimdRealCopy};
+use crate::utils::{self, SimdDot, SimdRealCopy};
 
 use crate::dynamics::solver::solver_body::SolverBodies;
 #[cfg(feature = "simd-is-enabled")]
@@ -19,6 +19,7 @@ pub struct MotorParameters<N: SimdRealCopy> {
     pub cfm_gain: N,
     pub target_pos: N,
     pub target_vel: N,
+    pub max_vel: N,
     pub max_impulse: N,
 }
 
@@ -30,6 +31,7 @@ impl<N: SimdRealCopy> Default for MotorParameters<N> {
             cfm_gain: N::zero(),
             target_pos: N::zero(),
             target_vel: N::zero(),
+            max_vel: N::splat(Real::MAX),
             max_impulse: N::zero(),
         }
     }
@@ -86,6 +88,9 @@ pub struct JointConstraint<N: SimdRealCopy, const LANES: usize> {
     pub rhs_wo_bias: N,
     pub cfm_gain: N,
     pub cfm_coeff: N,
+    // The part of `cfm_gain` coming from the joint’s compliance. It is only applied while
+    // solving with bias, like the contacts’ cfm.
+    pub compliance: N,
 
     pub im1: Vector<N>,
     pub im2: Vector<N>,
@@ -248,7 +253,27 @@ impl JointConstraint<Real, 1> {
             }
         }
 
+        // Joints with a cone limit (coupled Y and Z angular axes) limit their twist
+        // independently from their swing.
+        #[cfg(feature = "dim3")]
+        let has_cone_limit = coupled_axes & JointAxesMask::ANG_AXES.bits()
+            == (JointAxesMask::ANG_Y | JointAxesMask::ANG_Z).bits();
+
         for i in DIM..SPATIAL_DIM {
+            #[cfg(feature = "dim3")]
+            if i == DIM && has_cone_limit && limit_axes & (1 << i) != 0 {
+                out[len] = builder.limit_twist(
+                    params,
+                    [joint_id],
+                    body1,
+                    body2,
+                    [joint.limits[i].min, joint.limits[i].max],
+                    WritebackId::Limit(i),
+                );
+                len += 1;
+                continue;
+            }
+
             if (limit_axes & !coupled_axes) & (1 << i) != 0 {
                 out[len] = builder.limit_angular(
                     params,
@@ -309,11 +334,46 @@ impl JointConstraint<Real, 1> {
             );
             len += 1;
         }
+        if joint.compliance != 0.0 {
+            for constraint in &mut out[start..len] {
+                constraint.apply_compliance(params, joint.compliance);
+            }
+        }
         JointConstraintHelper::finalize_constraints(&mut out[start..len]);
 
         len
     }
 
+    /// Replaces the global joint softness of this constraint by an XPBD-style compliance.
+    ///
+    /// Must be called before the constraint is finalized.
+    fn apply_compliance(&mut self, params: &IntegrationParameters, compliance: Real) {
+        let inv_dt = params.inv_dt();
+        let erp_inv_dt = params.joint_erp_inv_dt();
+
+        // Correct the whole positional error at once: the compliance alone keeps it
+        // from being fully corrected.
+        if erp_inv_dt != 0.0 {
+            let rhs_bias = self.rhs - self.rhs_wo_bias;
+            self.rhs = self.rhs_wo_bias + rhs_bias * inv_dt / erp_inv_dt;
+        }
+
+        self.cfm_coeff = 0.0;
+        self.cfm_gain = compliance * inv_dt * inv_dt;
+        self.compliance = self.cfm_gain;
+    }
+
+    pub fn remove_bias_and_compliance_from_rhs(&mut self) {
+        self.remove_bias_from_rhs();
+
+        if self.compliance != 0.0 {
+            let dot_jj = utils::inv(self.inv_lhs) - self.cfm_gain;
+            self.cfm_gain -= self.compliance;
+            self.inv_lhs = utils::inv(dot_jj + self.cfm_gain);
+            self.compliance = 0.0;
+        }
+    }
+
     pub fn solve(&mut self, solver_vels: &mut SolverBodies) {
         let mut solver_vel1 = solver_vels.get_vel(self.solver_vel1[0]);
         let mut solver_vel2 = solver_vels.get_vel(self.solver_vel2[0]);
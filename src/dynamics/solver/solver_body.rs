@@ -108,6 +108,14 @@ impl SolverBodies {
             } else {
                 vels.angular = *rb.angvel();
             }
+
+            // Project out the angular velocity components that are not along the
+            // locked rotation axis.
+            if let Some(axis) = rb.mprops.locked_rotation_axis {
+                if rb.is_dynamic() {
+                    vels.angular = *axis * axis.dot(&vels.angular);
+                }
+            }
         }
         vels.linear = rb.vels.linvel;
         poses.pose = rb.pos.position * Translation::from(rb.mprops.local_mprops.local_com);
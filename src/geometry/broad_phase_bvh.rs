@@ -3,6 +3,13 @@ use crate::geometry::{Aabb, BroadPhasePairEvent, ColliderHandle, ColliderPair, C
 use crate::math::Real;
 use parry::partitioning::{Bvh, BvhWorkspace};
 use parry::utils::hashmap::{Entry, HashMap};
+#[cfg(feature = "debug-render")]
+use {
+    crate::math::{DIM, Point, Vector},
+    crate::pipeline::{DebugRenderBackend, DebugRenderObject},
+    parry::partitioning::BvhNode,
+    std::cell::RefCell,
+};
 
 /// The broad-phase collision detector that quickly filters out distant object pairs.
 ///
@@ -22,6 +29,7 @@ pub struct BroadPhaseBvh {
     workspace: BvhWorkspace,
     pairs: HashMap<(ColliderHandle, ColliderHandle), u32>,
     frame_index: u32,
+    modification_generation: u32,
     optimization_strategy: BvhOptimizationStrategy,
 }
 
@@ -102,6 +110,8 @@ impl BroadPhaseBvh {
             self.tree.remove(handle.into_raw_parts().0);
         }
 
+        let mut tree_modified = !removed_colliders.is_empty();
+
         // if modified_colliders.is_empty() {
         //     return;
         // }
@@ -128,9 +138,14 @@ impl BroadPhaseBvh {
                     modified.into_raw_parts().0,
                     change_detection_skin,
                 );
+                tree_modified = true;
             }
         }
 
+        if tree_modified {
+            self.modification_generation = self.modification_generation.wrapping_add(1);
+        }
+
         if ENABLE_TREE_VALIDITY_CHECK {
             if first_pass {
                 self.tree.assert_well_formed();
@@ -259,6 +274,17 @@ impl BroadPhaseBvh {
         // );
     }
 
+    /// Calls `f` on every pair of colliders currently overlapping in the broad-phase.
+    ///
+    /// These are all the pairs reported by a [`BroadPhasePairEvent::AddPair`] event that were
+    /// not removed by a [`BroadPhasePairEvent::DeletePair`] event since. Each unordered pair is
+    /// reported exactly once, i.e., `(a, b)` and `(b, a)` are never both reported.
+    pub fn for_each_pair(&self, mut f: impl FnMut(ColliderHandle, ColliderHandle)) {
+        for (handle1, handle2) in self.pairs.keys() {
+            f(*handle1, *handle2);
+        }
+    }
+
     /// Sets the AABB associated to the given collider.
     ///
     /// The AABB change will be immediately applied and propagated through the underlying BVH.
@@ -274,5 +300,78 @@ impl BroadPhaseBvh {
             handle.into_raw_parts().0,
             change_detection_skin,
         );
+        self.modification_generation = self.modification_generation.wrapping_add(1);
+    }
+
+    /// The AABB of the given collider, as stored in this broad-phase.
+    ///
+    /// This is the AABB set during the last broad-phase update, enlarged by the prediction
+    /// distance. Returns `None` if the collider isn’t part of the broad-phase.
+    pub(crate) fn collider_aabb(&self, handle: ColliderHandle) -> Option<Aabb> {
+        self.tree
+            .leaf_node(handle.into_raw_parts().0)
+            .map(|node| node.aabb())
+    }
+
+    /// A counter incremented whenever a collider AABB is inserted, modified, or removed from
+    /// this broad-phase.
+    ///
+    /// If the generation didn’t change between two calls, the results of any AABB query on the
+    /// broad-phase didn’t change either. See [`BroadPhaseQueryCursor`](crate::geometry::BroadPhaseQueryCursor)
+    /// for incremental queries relying on this.
+    pub fn modification_generation(&self) -> u32 {
+        self.modification_generation
+    }
+}
+
+#[cfg(feature = "debug-render")]
+impl BroadPhaseBvh {
+    /// Renders the AABB of every node of the underlying BVH as a wireframe box.
+    ///
+    /// This helps visualizing how the broad-phase partitions space, e.g., to debug why some
+    /// collider pairs are, or aren’t, generated. The BVH doesn’t store the depth of its nodes, so
+    /// they are colored according to their size instead: nodes at the same depth of a balanced
+    /// tree generally have similar sizes.
+    pub fn debug_render(&self, backend: &mut impl DebugRenderBackend) {
+        // NOTE: the BVH traversal predicate can’t borrow the backend mutably, so the
+        //       nodes are collected first.
+        let nodes = RefCell::new(vec![]);
+        self.tree
+            .leaves(|node: &BvhNode| {
+                nodes.borrow_mut().push((node.aabb(), node.is_leaf()));
+                true
+            })
+            .for_each(drop);
+
+        for (aabb, is_leaf) in nodes.into_inner() {
+            let object = DebugRenderObject::BroadPhaseNode(&aabb, is_leaf);
+
+            if !backend.filter_object(object) {
+                continue;
+            }
+
+            let extent = aabb.half_extents().max().max(Real::EPSILON);
+            let level = (-extent.log2()).round() as i32;
+            let hue = (level * 40).rem_euclid(360) as f32;
+            let color = [hue, 1.0, 0.5, 1.0];
+            let corner = |id: usize| {
+                Point::from(Vector::from_fn(|k, _| {
+                    if id & (1 << k) == 0 {
+                        aabb.mins[k]
+                    } else {
+                        aabb.maxs[k]
+                    }
+                }))
+            };
+
+            // Link each corner to the corners with exactly one greater coordinate.
+            for id in 0..(1 << DIM) {
+                for k in 0..DIM {
+                    if id & (1 << k) == 0 {
+                        backend.draw_line(object, corner(id), corner(id | (1 << k)), color);
+                    }
+                }
+            }
+        }
     }
 }
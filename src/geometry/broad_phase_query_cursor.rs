@@ -0,0 +1,174 @@
+use crate::geometry::{Aabb, BroadPhaseBvh, ColliderHandle, ColliderSet};
+use parry::bounding_volume::BoundingVolume;
+use parry::partitioning::BvhNode;
+use std::cmp::Ordering;
+
+/// An event emitted by a [`BroadPhaseQueryCursor`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BroadPhaseQueryEvent {
+    /// The collider started overlapping the queried AABB.
+    Entered(ColliderHandle),
+    /// The collider stopped overlapping the queried AABB, or it was removed.
+    Left(ColliderHandle),
+}
+
+/// A persistent AABB query on the broad-phase, reporting which colliders entered or left the
+/// queried AABB since its last update.
+///
+/// This is useful for queries running every frame over a mostly static scene (e.g. the selection
+/// rectangle of an editor). The cursor caches the set of overlapped colliders and only traverses
+/// the broad-phase again if either the queried AABB, or the broad-phase content (as tracked by
+/// [`BroadPhaseBvh::modification_generation`]), changed since its last update.
+///
+/// Just like [`QueryPipeline::intersect_aabb_conservative`](crate::pipeline::QueryPipeline::intersect_aabb_conservative),
+/// this tests the AABBs stored in the broad-phase, which are slightly enlarged compared to the
+/// colliders’ actual AABBs.
+#[derive(Clone, Debug, Default)]
+pub struct BroadPhaseQueryCursor {
+    aabb: Option<Aabb>,
+    generation: Option<u32>,
+    overlaps: Vec<ColliderHandle>,
+    workspace: Vec<ColliderHandle>,
+}
+
+impl BroadPhaseQueryCursor {
+    /// Creates a new cursor that didn’t overlap any collider yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The colliders overlapping the queried AABB as of the last call to [`Self::update`].
+    ///
+    /// They are sorted by increasing handle index.
+    pub fn overlaps(&self) -> &[ColliderHandle] {
+        &self.overlaps
+    }
+
+    /// Forces the next call to [`Self::update`] to traverse the broad-phase again.
+    ///
+    /// This is needed if the cursor is used with a different broad-phase than before.
+    pub fn invalidate(&mut self) {
+        self.generation = None;
+    }
+
+    /// Updates the set of colliders overlapping `aabb`.
+    ///
+    /// The colliders that started, or stopped, overlapping `aabb` since the last update are
+    /// pushed into `events`.
+    pub fn update(
+        &mut self,
+        broad_phase: &BroadPhaseBvh,
+        colliders: &ColliderSet,
+        aabb: Aabb,
+        events: &mut Vec<BroadPhaseQueryEvent>,
+    ) {
+        let generation = broad_phase.modification_generation();
+
+        if self.aabb == Some(aabb) && self.generation == Some(generation) {
+            return;
+        }
+
+        self.aabb = Some(aabb);
+        self.generation = Some(generation);
+
+        self.workspace.clear();
+        self.workspace.extend(
+            broad_phase
+                .tree
+                .leaves(|node: &BvhNode| node.aabb().intersects(&aabb))
+                .filter_map(|leaf| colliders.get_unknown_gen(leaf).map(|(_, handle)| handle)),
+        );
+        self.workspace
+            .sort_unstable_by_key(|handle| handle.into_raw_parts());
+
+        // Both lists are sorted, so the differences are found by merging them.
+        let (mut i, mut j) = (0, 0);
+
+        loop {
+            let ordering = match (self.overlaps.get(i), self.workspace.get(j)) {
+                (Some(old), Some(new)) => old.into_raw_parts().cmp(&new.into_raw_parts()),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break,
+            };
+
+            match ordering {
+                Ordering::Less => {
+                    events.push(BroadPhaseQueryEvent::Left(self.overlaps[i]));
+                    i += 1;
+                }
+                Ordering::Greater => {
+                    events.push(BroadPhaseQueryEvent::Entered(self.workspace[j]));
+                    j += 1;
+                }
+                Ordering::Equal => {
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+
+        std::mem::swap(&mut self.overlaps, &mut self.workspace);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BroadPhaseQueryCursor, BroadPhaseQueryEvent};
+    use crate::dynamics::IntegrationParameters;
+    use crate::geometry::{Aabb, BroadPhaseBvh, ColliderBuilder, ColliderSet};
+    use crate::math::{Point, Vector};
+
+    #[test]
+    fn query_cursor_reports_incremental_changes() {
+        let params = IntegrationParameters::default();
+        let mut colliders = ColliderSet::new();
+        let mut broad_phase = BroadPhaseBvh::new();
+
+        let handles: Vec<_> = [0.0, 2.0, 10.0]
+            .into_iter()
+            .map(|x| {
+                let collider = ColliderBuilder::ball(0.5).translation(Vector::x() * x);
+                let handle = colliders.insert(collider);
+                broad_phase.set_aabb(&params, handle, colliders[handle].compute_aabb());
+                handle
+            })
+            .collect();
+
+        let query = Aabb::new(
+            Point::origin() - Vector::repeat(1.0),
+            Point::origin() + Vector::repeat(3.0),
+        );
+        let mut cursor = BroadPhaseQueryCursor::new();
+        let mut events = vec![];
+
+        cursor.update(&broad_phase, &colliders, query, &mut events);
+        assert_eq!(
+            events,
+            vec![
+                BroadPhaseQueryEvent::Entered(handles[0]),
+                BroadPhaseQueryEvent::Entered(handles[1])
+            ]
+        );
+        assert_eq!(cursor.overlaps(), &handles[..2]);
+
+        // Nothing changed.
+        events.clear();
+        cursor.update(&broad_phase, &colliders, query, &mut events);
+        assert!(events.is_empty());
+
+        // Move the first collider away, and the third one into the queried AABB.
+        let aabb0 = colliders[handles[0]].compute_aabb();
+        let aabb2 = colliders[handles[2]].compute_aabb();
+        broad_phase.set_aabb(&params, handles[0], aabb2);
+        broad_phase.set_aabb(&params, handles[2], aabb0);
+        cursor.update(&broad_phase, &colliders, query, &mut events);
+        assert_eq!(
+            events,
+            vec![
+                BroadPhaseQueryEvent::Left(handles[0]),
+                BroadPhaseQueryEvent::Entered(handles[2])
+            ]
+        );
+    }
+}
@@ -3,8 +3,9 @@ use crate::dynamics::{CoefficientCombineRule, MassProperties, RigidBodyHandle, R
 use crate::geometry::HeightFieldFlags;
 use crate::geometry::{
     ActiveCollisionTypes, ColliderChanges, ColliderFlags, ColliderMassProps, ColliderMaterial,
-    ColliderParent, ColliderPosition, ColliderShape, ColliderType, InteractionGroups,
-    MeshConverter, MeshConverterError, SharedShape,
+    ColliderParent, ColliderPosition, ColliderPositionSync, ColliderShape, ColliderType,
+    InteractionGroups, MeshConverter, MeshConverterError, OneWayPlatform, SharedShape,
+    SubshapeMaterials,
 };
 use crate::math::{AngVector, DIM, Isometry, Point, Real, Rotation, Vector};
 use crate::parry::transformation::vhacd::VHACDParameters;
@@ -56,6 +57,14 @@ pub struct Collider {
     pub(crate) flags: ColliderFlags,
     contact_skin: Real,
     contact_force_event_threshold: Real,
+    ccd_thickness_override: Option<Real>,
+    subshape_collision_groups: Vec<Option<InteractionGroups>>,
+    subshape_materials: Option<Box<SubshapeMaterials>>,
+    one_way_platform: Option<OneWayPlatform>,
+    position_sync: ColliderPositionSync,
+    /// Set if this collider must be ignored by the CCD during the current timestep because
+    /// it was teleported relative to its parent.
+    pub(crate) ccd_teleported: bool,
     /// User-defined data associated to this collider.
     pub user_data: u128,
 }
@@ -65,16 +74,22 @@ impl Collider {
         self.changes = ColliderChanges::all();
     }
 
-    pub(crate) fn effective_contact_force_event_threshold(&self) -> Real {
-        if self
-            .flags
+    pub(crate) fn effective_contact_force_event_threshold(&self) -> Option<Real> {
+        self.flags
             .active_events
             .contains(ActiveEvents::CONTACT_FORCE_EVENTS)
-        {
-            self.contact_force_event_threshold
-        } else {
-            Real::MAX
-        }
+            .then_some(self.contact_force_event_threshold)
+    }
+
+    /// The thickness used by the CCD solver for this collider.
+    ///
+    /// This is the [`Self::ccd_thickness_override`] if it is set, or the thickness derived from
+    /// the collider’s shape otherwise. The CCD solver only looks for impacts if the colliders move
+    /// by more than their thickness during a timestep, so thin shapes (e.g. a sword modeled as a
+    /// flat cuboid) are the ones most prone to tunneling.
+    pub fn ccd_thickness(&self) -> Real {
+        self.ccd_thickness_override
+            .unwrap_or_else(|| self.shape.ccd_thickness())
     }
 
     /// The rigid body this collider is attached to, if any.
@@ -127,6 +142,12 @@ impl Collider {
             contact_force_event_threshold,
             user_data,
             contact_skin,
+            ccd_thickness_override,
+            subshape_collision_groups,
+            subshape_materials,
+            one_way_platform,
+            position_sync,
+            ccd_teleported: _ccd_teleported, // Only relevant to the current timestep.
         } = other;
 
         if self.parent.is_none() {
@@ -142,6 +163,11 @@ impl Collider {
         self.flags = *flags;
         self.changes = ColliderChanges::all();
         self.contact_skin = *contact_skin;
+        self.ccd_thickness_override = *ccd_thickness_override;
+        self.subshape_collision_groups = subshape_collision_groups.clone();
+        self.subshape_materials = subshape_materials.clone();
+        self.one_way_platform = *one_way_platform;
+        self.position_sync = *position_sync;
     }
 
     /// Which physics hooks are enabled for this collider.
@@ -179,6 +205,10 @@ impl Collider {
     }
 
     /// Sets the collision types enabled for this collider.
+    ///
+    /// This controls which combinations of rigid-body types (dynamic, kinematic, fixed) generate
+    /// contacts (or intersection events for sensors) with this collider. The default only
+    /// enables pairs involving at least one dynamic body. See [`ActiveCollisionTypes`] for details.
     pub fn set_active_collision_types(&mut self, active_collision_types: ActiveCollisionTypes) {
         self.flags.active_collision_types = active_collision_types;
     }
@@ -197,6 +227,54 @@ impl Collider {
         self.contact_skin = skin_thickness;
     }
 
+    /// The thickness overriding the shape-derived CCD thickness of this collider, if any.
+    ///
+    /// See the documentation of [`ColliderBuilder::ccd_thickness_override`] for details.
+    pub fn ccd_thickness_override(&self) -> Option<Real> {
+        self.ccd_thickness_override
+    }
+
+    /// Sets the thickness overriding the shape-derived CCD thickness of this collider.
+    ///
+    /// Negative values are clamped to zero. Set to `None` to use the thickness derived from
+    /// the collider’s shape. See the documentation of [`ColliderBuilder::ccd_thickness_override`]
+    /// for details.
+    pub fn set_ccd_thickness_override(&mut self, thickness: Option<Real>) {
+        self.ccd_thickness_override = thickness.map(|t| t.max(0.0));
+    }
+
+    /// The one-way collision mode of this collider, if any.
+    ///
+    /// See the documentation of [`ColliderBuilder::one_way_platform`] for details.
+    pub fn one_way_platform(&self) -> Option<&OneWayPlatform> {
+        self.one_way_platform.as_ref()
+    }
+
+    /// Sets the one-way collision mode of this collider.
+    ///
+    /// Set to `None` to make this collider collide from all sides again. See the documentation
+    /// of [`ColliderBuilder::one_way_platform`] for details.
+    pub fn set_one_way_platform(&mut self, one_way_platform: Option<OneWayPlatform>) {
+        self.one_way_platform = one_way_platform;
+    }
+
+    /// How the CCD solver handles changes of this collider’s position relative to its parent.
+    ///
+    /// See [`ColliderPositionSync`] for details.
+    pub fn position_sync(&self) -> ColliderPositionSync {
+        self.position_sync
+    }
+
+    /// Sets how the CCD solver handles changes of this collider’s position relative to its
+    /// parent, e.g., with [`Self::set_position_wrt_parent`].
+    ///
+    /// With [`ColliderPositionSync::Teleport`], repositioning this collider on its parent makes
+    /// the CCD solver ignore it during the next timestep, so that the jump isn’t mistaken for an
+    /// impact. See [`ColliderPositionSync`] for details.
+    pub fn set_position_sync(&mut self, position_sync: ColliderPositionSync) {
+        self.position_sync = position_sync;
+    }
+
     /// The friction coefficient of this collider (how "slippery" it is).
     ///
     /// - `0.0` = perfectly slippery (ice)
@@ -214,6 +292,35 @@ impl Collider {
         self.material.friction = coefficient
     }
 
+    /// The materials assigned to the subshapes of this collider, if any.
+    pub fn subshape_materials(&self) -> Option<&SubshapeMaterials> {
+        self.subshape_materials.as_deref()
+    }
+
+    /// Assigns materials to the individual subshapes of this collider’s composite shape.
+    ///
+    /// When generating contacts, the narrow-phase uses the friction and restitution of the
+    /// subshape (e.g. the trimesh triangle) involved in each contact manifold. Subshapes without
+    /// a valid material in the table use the collider’s own material. Setting `None` removes all
+    /// the per-subshape materials.
+    ///
+    /// The subshape index is the one reported by contact manifolds (for a trimesh, the index of
+    /// the triangle).
+    pub fn set_subshape_materials(&mut self, materials: Option<SubshapeMaterials>) {
+        self.subshape_materials = materials.map(Box::new);
+    }
+
+    /// The material used for contacts involving the given subshape of this collider.
+    ///
+    /// Returns the subshape’s material from [`Self::subshape_materials`], or the collider’s own
+    /// material if there is none.
+    pub fn subshape_material(&self, subshape: u32) -> &ColliderMaterial {
+        self.subshape_materials
+            .as_ref()
+            .and_then(|materials| materials.material(subshape))
+            .unwrap_or(&self.material)
+    }
+
     /// The combine rule used by this collider to combine its friction
     /// coefficient with the friction coefficient of the other collider it
     /// is in contact with.
@@ -260,7 +367,48 @@ impl Collider {
         self.material.restitution_combine_rule = rule;
     }
 
+    /// The rolling resistance coefficient of this collider.
+    ///
+    /// See [`Self::set_rolling_resistance`] for details.
+    pub fn rolling_resistance(&self) -> Real {
+        self.material.rolling_resistance
+    }
+
+    /// Sets the rolling resistance coefficient of this collider.
+    ///
+    /// While in contact, the relative rolling motion of the bodies is opposed by a torque bounded
+    /// by this coefficient multiplied by the contact normal force. Unlike angular damping, it only
+    /// acts during contacts and scales with the load, so balls rolling on a flat floor stop in
+    /// finite time. Default is `0.0`.
+    ///
+    /// It is combined with the coefficient of the other collider using the
+    /// [`Self::rolling_resistance_combine_rule`]. It is ignored for contacts involving multibody
+    /// links.
+    ///
+    /// It is expressed in meters (the lever arm of the resisting torque): a few percent of the
+    /// ball’s radius are typical values.
+    pub fn set_rolling_resistance(&mut self, coefficient: Real) {
+        self.material.rolling_resistance = coefficient
+    }
+
+    /// The combine rule used by this collider to combine its rolling resistance
+    /// coefficient with the rolling resistance coefficient of the other collider it
+    /// is in contact with.
+    pub fn rolling_resistance_combine_rule(&self) -> CoefficientCombineRule {
+        self.material.rolling_resistance_combine_rule
+    }
+
+    /// Sets the combine rule used by this collider to combine its rolling resistance
+    /// coefficient with the rolling resistance coefficient of the other collider it
+    /// is in contact with.
+    pub fn set_rolling_resistance_combine_rule(&mut self, rule: CoefficientCombineRule) {
+        self.material.rolling_resistance_combine_rule = rule;
+    }
+
     /// Sets the total force magnitude beyond which a contact force event can be emitted.
+    ///
+    /// If both colliders of a contact pair have contact force events enabled, the largest of
+    /// their two thresholds is used for that pair.
     pub fn set_contact_force_event_threshold(&mut self, threshold: Real) {
         self.contact_force_event_threshold = threshold;
     }
@@ -365,6 +513,7 @@ impl Collider {
     pub fn set_translation_wrt_parent(&mut self, translation: Vector<Real>) {
         if let Some(parent) = self.parent.as_mut() {
             self.changes.insert(ColliderChanges::PARENT);
+            self.ccd_teleported = self.position_sync == ColliderPositionSync::Teleport;
             parent.pos_wrt_parent.translation.vector = translation;
         }
     }
@@ -375,16 +524,19 @@ impl Collider {
     pub fn set_rotation_wrt_parent(&mut self, rotation: AngVector<Real>) {
         if let Some(parent) = self.parent.as_mut() {
             self.changes.insert(ColliderChanges::PARENT);
+            self.ccd_teleported = self.position_sync == ColliderPositionSync::Teleport;
             parent.pos_wrt_parent.rotation = Rotation::new(rotation);
         }
     }
 
     /// Changes this collider's full pose (position + rotation) relative to its parent.
     ///
-    /// Does nothing if the collider is not attached to a rigid-body.
+    /// Does nothing if the collider is not attached to a rigid-body. See
+    /// [`Self::set_position_sync`] to control how this change is seen by the CCD solver.
     pub fn set_position_wrt_parent(&mut self, pos_wrt_parent: Isometry<Real>) {
         if let Some(parent) = self.parent.as_mut() {
             self.changes.insert(ColliderChanges::PARENT);
+            self.ccd_teleported = self.position_sync == ColliderPositionSync::Teleport;
             parent.pos_wrt_parent = pos_wrt_parent;
         }
     }
@@ -399,6 +551,12 @@ impl Collider {
     /// Changes which collision groups this collider belongs to and can interact with.
     ///
     /// Use to control collision filtering (like changing layers).
+    ///
+    /// The change is taken into account at the next timestep: the contacts and intersections
+    /// with colliders that are no longer compatible are removed (emitting the corresponding
+    /// [`CollisionEvent::Stopped`](crate::geometry::CollisionEvent::Stopped) events), and the
+    /// bodies involved are woken up. The broad-phase keeps tracking these pairs as long as their
+    /// AABBs overlap, so contacts resume immediately if the groups become compatible again.
     pub fn set_collision_groups(&mut self, groups: InteractionGroups) {
         if self.flags.collision_groups != groups {
             self.changes.insert(ColliderChanges::GROUPS);
@@ -406,6 +564,55 @@ impl Collider {
         }
     }
 
+    /// The collision groups of the given subshape of this collider’s composite shape.
+    ///
+    /// Returns the groups set with [`Self::set_subshape_collision_groups`], or
+    /// [`Self::collision_groups`] if the subshape doesn’t override them.
+    pub fn subshape_collision_groups(&self, subshape: u32) -> InteractionGroups {
+        self.subshape_collision_groups
+            .get(subshape as usize)
+            .copied()
+            .flatten()
+            .unwrap_or(self.flags.collision_groups)
+    }
+
+    /// Overrides the collision groups of one subshape of this collider’s composite shape
+    /// (e.g. a compound shape).
+    ///
+    /// The narrow-phase tests the collision groups of each pair of subshapes before generating
+    /// their contacts, which makes it possible, e.g., for the bumper of a vehicle modeled as a
+    /// single compound collider to interact with different colliders than its chassis. A
+    /// subshape without override (`None`) uses the collider’s [`Self::collision_groups`].
+    ///
+    /// The subshape index is the one reported by contact manifolds (for a compound shape, the
+    /// index of the part in [`Compound::shapes`](parry::shape::Compound::shapes)). These overrides
+    /// only affect contacts: intersections involving sensors still use the collider’s groups.
+    pub fn set_subshape_collision_groups(
+        &mut self,
+        subshape: u32,
+        groups: Option<InteractionGroups>,
+    ) {
+        let subshape = subshape as usize;
+
+        if self.subshape_collision_groups.len() <= subshape {
+            if groups.is_none() {
+                return;
+            }
+
+            self.subshape_collision_groups.resize(subshape + 1, None);
+        }
+
+        if self.subshape_collision_groups[subshape] != groups {
+            self.changes.insert(ColliderChanges::GROUPS);
+            self.subshape_collision_groups[subshape] = groups;
+        }
+    }
+
+    /// Does any subshape of this collider override its collision groups?
+    pub(crate) fn has_subshape_collision_groups(&self) -> bool {
+        self.subshape_collision_groups.iter().any(|g| g.is_some())
+    }
+
     /// The solver groups for this collider (advanced collision filtering).
     ///
     /// Most users should use `collision_groups()` instead.
@@ -524,11 +731,75 @@ impl Collider {
     }
 
     /// Sets the shape of this collider.
+    ///
+    /// The shape is replaced in-place so the collider keeps its handle. At the next physics step,
+    /// its bounding volume in the broad-phase is updated, its contact manifolds are regenerated,
+    /// and the mass-properties of its parent rigid-body are recomputed (unless the collider’s
+    /// mass-properties don’t depend on its shape). This is useful, e.g., for switching a
+    /// character between a standing and a crouching capsule.
     pub fn set_shape(&mut self, shape: SharedShape) {
         self.changes.insert(ColliderChanges::SHAPE);
         self.shape = shape;
     }
 
+    /// Modifies the heights of this collider’s heightfield in-place.
+    ///
+    /// The closure `f` receives the heights of the heightfield (one value per vertex) and can
+    /// modify any number of them at once, which is useful for deformable terrains. The cell
+    /// statuses (removed cells) and flags of the heightfield are preserved, and the collider keeps
+    /// its handle. At the next physics step, its bounding volume in the broad-phase is updated and
+    /// its contact manifolds are regenerated.
+    ///
+    /// Returns `false` (and doesn’t call `f`) if the shape of this collider isn’t a heightfield.
+    #[cfg(feature = "dim2")]
+    pub fn modify_heightfield(&mut self, f: impl FnOnce(&mut na::DVector<Real>)) -> bool {
+        let Some(heightfield) = self.shape.as_heightfield() else {
+            return false;
+        };
+
+        let mut heights = heightfield.heights().clone();
+        f(&mut heights);
+
+        let mut new_heightfield = crate::geometry::HeightField::new(heights, *heightfield.scale());
+        for i in 0..heightfield.num_cells() {
+            new_heightfield.set_segment_removed(i, heightfield.is_segment_removed(i));
+        }
+
+        self.set_shape(SharedShape::new(new_heightfield));
+        true
+    }
+
+    /// Modifies the heights of this collider’s heightfield in-place.
+    ///
+    /// The closure `f` receives the heights of the heightfield (one value per vertex) and can
+    /// modify any number of them at once, which is useful for deformable terrains. The cell
+    /// statuses (removed cells) and flags of the heightfield are preserved, and the collider keeps
+    /// its handle. At the next physics step, its bounding volume in the broad-phase is updated and
+    /// its contact manifolds are regenerated.
+    ///
+    /// Returns `false` (and doesn’t call `f`) if the shape of this collider isn’t a heightfield.
+    #[cfg(feature = "dim3")]
+    pub fn modify_heightfield(&mut self, f: impl FnOnce(&mut na::DMatrix<Real>)) -> bool {
+        let Some(heightfield) = self.shape.as_heightfield() else {
+            return false;
+        };
+
+        let mut heights = heightfield.heights().clone();
+        f(&mut heights);
+
+        let mut new_heightfield = crate::geometry::HeightField::with_flags(
+            heights,
+            *heightfield.scale(),
+            heightfield.flags(),
+        );
+        new_heightfield
+            .cells_statuses_mut()
+            .copy_from(heightfield.cells_statuses());
+
+        self.set_shape(SharedShape::new(new_heightfield));
+        true
+    }
+
     /// Returns the shape as a `SharedShape` (reference-counted shape).
     ///
     /// Use `shape()` for the trait object, this for the concrete type.
@@ -565,7 +836,9 @@ impl Collider {
     //       We should group them somehow.
     /// Computes the collider’s AABB for usage in a broad-phase.
     ///
-    /// It takes into account soft-ccd, the contact skin, and the contact prediction.
+    /// It takes into account soft-ccd, the contact skin, and the contact prediction. Sensors
+    /// are loosened by [`IntegrationParameters::sensor_prediction_distance`] instead of
+    /// [`IntegrationParameters::prediction_distance`].
     pub fn compute_broad_phase_aabb(
         &self,
         params: &IntegrationParameters,
@@ -582,7 +855,11 @@ impl Collider {
             })
         });
 
-        let prediction_distance = params.prediction_distance();
+        let prediction_distance = if self.is_sensor() {
+            params.sensor_prediction_distance()
+        } else {
+            params.prediction_distance()
+        };
         let mut aabb = self.compute_collision_aabb(prediction_distance / 2.0);
         if let Some(next_pose) = next_pose {
             let next_aabb = self
@@ -650,6 +927,10 @@ pub struct ColliderBuilder {
     pub restitution: Real,
     /// The rule used to combine two restitution coefficients.
     pub restitution_combine_rule: CoefficientCombineRule,
+    /// The rolling resistance coefficient of the collider to be built.
+    pub rolling_resistance: Real,
+    /// The rule used to combine two rolling resistance coefficients.
+    pub rolling_resistance_combine_rule: CoefficientCombineRule,
     /// The position of this collider.
     pub position: Isometry<Real>,
     /// Is this collider a sensor?
@@ -672,6 +953,22 @@ pub struct ColliderBuilder {
     pub contact_force_event_threshold: Real,
     /// An extra thickness around the collider shape to keep them further apart when colliding.
     pub contact_skin: Real,
+    /// The thickness overriding the shape-derived thickness used by the CCD solver.
+    pub ccd_thickness_override: Option<Real>,
+    /// The collision groups overriding the collider’s collision groups for each subshape.
+    ///
+    /// See [`Collider::set_subshape_collision_groups`].
+    pub subshape_collision_groups: Vec<Option<InteractionGroups>>,
+    /// The materials assigned to the subshapes of the collider being built.
+    ///
+    /// See [`Collider::set_subshape_materials`].
+    pub subshape_materials: Option<SubshapeMaterials>,
+    /// The one-way collision mode of the collider being built.
+    ///
+    /// See [`ColliderBuilder::one_way_platform`].
+    pub one_way_platform: Option<OneWayPlatform>,
+    /// How the CCD solver handles changes of the position of the collider relative to its parent.
+    pub position_sync: ColliderPositionSync,
 }
 
 impl Default for ColliderBuilder {
@@ -695,12 +992,19 @@ impl ColliderBuilder {
             solver_groups: InteractionGroups::all(),
             friction_combine_rule: CoefficientCombineRule::Average,
             restitution_combine_rule: CoefficientCombineRule::Average,
+            rolling_resistance: 0.0,
+            rolling_resistance_combine_rule: CoefficientCombineRule::Average,
             active_collision_types: ActiveCollisionTypes::default(),
             active_hooks: ActiveHooks::empty(),
             active_events: ActiveEvents::empty(),
             enabled: true,
             contact_force_event_threshold: 0.0,
             contact_skin: 0.0,
+            ccd_thickness_override: None,
+            subshape_collision_groups: Vec::new(),
+            subshape_materials: None,
+            one_way_platform: None,
+            position_sync: ColliderPositionSync::default(),
         }
     }
 
@@ -1149,6 +1453,26 @@ impl ColliderBuilder {
         self
     }
 
+    /// Overrides the collision groups of one subshape of the collider’s composite shape.
+    ///
+    /// See [`Collider::set_subshape_collision_groups`] for details.
+    pub fn subshape_collision_groups(mut self, subshape: u32, groups: InteractionGroups) -> Self {
+        let subshape = subshape as usize;
+        if self.subshape_collision_groups.len() <= subshape {
+            self.subshape_collision_groups.resize(subshape + 1, None);
+        }
+        self.subshape_collision_groups[subshape] = Some(groups);
+        self
+    }
+
+    /// Assigns materials to the individual subshapes of the collider’s composite shape.
+    ///
+    /// See [`Collider::set_subshape_materials`] for details.
+    pub fn subshape_materials(mut self, materials: SubshapeMaterials) -> Self {
+        self.subshape_materials = Some(materials);
+        self
+    }
+
     /// Sets solver groups (advanced collision filtering for contact resolution).
     ///
     /// Similar to collision_groups but specifically for the contact solver.
@@ -1253,6 +1577,20 @@ impl ColliderBuilder {
         self
     }
 
+    /// Sets the rolling resistance coefficient of this collider.
+    ///
+    /// See [`Collider::set_rolling_resistance`] for details. Default is `0.0`.
+    pub fn rolling_resistance(mut self, coefficient: Real) -> Self {
+        self.rolling_resistance = coefficient;
+        self
+    }
+
+    /// Sets the rule to be used to combine two rolling resistance coefficients in a contact.
+    pub fn rolling_resistance_combine_rule(mut self, rule: CoefficientCombineRule) -> Self {
+        self.rolling_resistance_combine_rule = rule;
+        self
+    }
+
     /// Sets the density (mass per unit volume) of this collider.
     ///
     /// Mass will be computed as: `density × volume`. Common densities:
@@ -1260,22 +1598,34 @@ impl ColliderBuilder {
     /// - `2700.0` = aluminum
     /// - `7850.0` = steel
     ///
-    /// ⚠️ Use either `density()` OR `mass()`, not both (last call wins).
+    /// ⚠️ This is ignored if a mass was already set with [`Self::mass`]: the mass always takes
+    /// precedence over the density, and a warning is logged in debug builds.
     ///
     /// # Example
     /// ```ignore
     /// let steel_ball = ColliderBuilder::ball(0.5).density(7850.0).build();
     /// ```
     pub fn density(mut self, density: Real) -> Self {
-        self.mass_properties = ColliderMassProps::Density(density);
+        if let ColliderMassProps::Mass(mass) = self.mass_properties {
+            if cfg!(debug_assertions) {
+                log::warn!(
+                    "Ignoring the collider density {density} since its mass was already set to {mass}."
+                );
+            }
+        } else {
+            self.mass_properties = ColliderMassProps::Density(density);
+        }
         self
     }
 
     /// Sets the total mass of this collider directly.
     ///
-    /// Angular inertia is computed automatically from the shape and mass.
+    /// The center of mass and angular inertia are computed automatically from the shape, as if it
+    /// had a uniform density, and scaled to match this mass. Shapes without volume (like polylines)
+    /// are given a point mass at their center of mass instead.
     ///
-    /// ⚠️ Use either `mass()` OR `density()`, not both (last call wins).
+    /// ⚠️ The mass takes precedence over any density set with [`Self::density`], before or after
+    /// this call. A warning is logged in debug builds if both are set.
     ///
     /// # Example
     /// ```ignore
@@ -1283,6 +1633,15 @@ impl ColliderBuilder {
     /// let collider = ColliderBuilder::ball(0.5).mass(10.0).build();
     /// ```
     pub fn mass(mut self, mass: Real) -> Self {
+        if let ColliderMassProps::Density(density) = self.mass_properties {
+            // NOTE: we can’t distinguish an explicit density of 1.0 from the default one, but
+            //       ignoring it doesn’t deserve a warning either way.
+            if cfg!(debug_assertions) && self.mass_properties != ColliderMassProps::default() {
+                log::warn!(
+                    "Ignoring the collider density {density} since its mass is set to {mass}."
+                );
+            }
+        }
         self.mass_properties = ColliderMassProps::Mass(mass);
         self
     }
@@ -1299,7 +1658,8 @@ impl ColliderBuilder {
     /// Sets the force threshold for triggering contact force events.
     ///
     /// When total contact force exceeds this value, a `ContactForceEvent` is generated
-    /// (if `ActiveEvents::CONTACT_FORCE_EVENTS` is enabled).
+    /// (if `ActiveEvents::CONTACT_FORCE_EVENTS` is enabled). If both colliders of a contact
+    /// pair have contact force events enabled, the largest of their two thresholds is used.
     ///
     /// Use for detecting hard impacts, breaking objects, or damage systems.
     ///
@@ -1379,6 +1739,49 @@ impl ColliderBuilder {
         self
     }
 
+    /// Overrides the thickness used by the CCD solver for this collider.
+    ///
+    /// By default, the CCD solver derives this thickness from the collider’s shape, and skips
+    /// time-of-impact computations for pairs of colliders moving by less than their combined
+    /// thickness during a timestep. Setting a larger thickness for thin-but-fast colliders
+    /// (like blades) makes this test more conservative without affecting the actual shape.
+    ///
+    /// This only affects CCD, not the contacts generated by the narrow-phase. Negative values
+    /// are clamped to zero.
+    pub fn ccd_thickness_override(mut self, thickness: Real) -> Self {
+        self.ccd_thickness_override = Some(thickness.max(0.0));
+        self
+    }
+
+    /// Makes this collider a one-way platform that can only be collided with from the side
+    /// `local_normal` points to.
+    ///
+    /// This is typically used for 2D platformers where characters can jump through a platform
+    /// from below and land on top of it. Each contact point with this collider is kept only if:
+    /// - its normal points toward the side of `local_normal` (expressed in the collider’s local-space),
+    /// - its penetration depth doesn’t exceed `tolerance`,
+    /// - and, if it is penetrating, the other collider isn’t moving away along `local_normal`
+    ///   (i.e. it isn’t currently crossing the platform from the other side).
+    ///
+    /// Other contact points are discarded by the narrow-phase before reaching the constraints
+    /// solver. The CCD solver only resolves impacts hitting the allowed side, so fast bodies
+    /// falling onto the platform still land on it instead of tunneling through.
+    ///
+    /// The `tolerance` should be small but larger than the typical resting penetration depth,
+    /// e.g., a few percent of the size of the objects landing on the platform.
+    pub fn one_way_platform(mut self, local_normal: Unit<Vector<Real>>, tolerance: Real) -> Self {
+        self.one_way_platform = Some(OneWayPlatform::new(local_normal, tolerance));
+        self
+    }
+
+    /// Sets how the CCD solver handles changes of the collider’s position relative to its parent.
+    ///
+    /// See [`Collider::set_position_sync`].
+    pub fn position_sync(mut self, position_sync: ColliderPositionSync) -> Self {
+        self.position_sync = position_sync;
+        self
+    }
+
     /// Sets whether this collider starts enabled or disabled.
     ///
     /// Default is `true` (enabled). Set to `false` to create a disabled collider.
@@ -1403,6 +1806,8 @@ impl ColliderBuilder {
             restitution: self.restitution,
             friction_combine_rule: self.friction_combine_rule,
             restitution_combine_rule: self.restitution_combine_rule,
+            rolling_resistance: self.rolling_resistance,
+            rolling_resistance_combine_rule: self.rolling_resistance_combine_rule,
         };
         let flags = ColliderFlags {
             collision_groups: self.collision_groups,
@@ -1435,6 +1840,12 @@ impl ColliderBuilder {
             coll_type,
             contact_force_event_threshold: self.contact_force_event_threshold,
             contact_skin: self.contact_skin,
+            ccd_thickness_override: self.ccd_thickness_override.map(|t| t.max(0.0)),
+            subshape_collision_groups: self.subshape_collision_groups.clone(),
+            subshape_materials: self.subshape_materials.clone().map(Box::new),
+            one_way_platform: self.one_way_platform,
+            position_sync: self.position_sync,
+            ccd_teleported: false,
             user_data: self.user_data,
         }
     }
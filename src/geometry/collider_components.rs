@@ -1,7 +1,8 @@
 use crate::dynamics::{CoefficientCombineRule, MassProperties, RigidBodyHandle, RigidBodyType};
 use crate::geometry::{InteractionGroups, Shape, SharedShape};
-use crate::math::{Isometry, Real};
+use crate::math::{Isometry, Real, Vector};
 use crate::pipeline::{ActiveEvents, ActiveHooks};
+use na::Unit;
 use std::ops::{Deref, DerefMut};
 
 /// The unique identifier of a collider added to a collider set.
@@ -118,7 +119,8 @@ pub enum ColliderMassProps {
     Density(Real),
     /// The collider is given a mass.
     ///
-    /// Its angular inertia will be computed automatically based on this mass.
+    /// Its center of mass and angular inertia are computed from the shape with a uniform density,
+    /// scaled to match this mass.
     Mass(Real),
     /// The collider is given explicit mass-properties.
     MassProperties(Box<MassProperties>),
@@ -140,7 +142,8 @@ impl ColliderMassProps {
     /// The mass-properties of this collider.
     ///
     /// If `self` is the `Density` variant, then this computes the mass-properties based
-    /// on the given shape.
+    /// on the given shape. If it is the `Mass` variant, then the mass-properties of the shape with
+    /// a unit density are scaled to the given mass.
     ///
     /// If `self` is the `MassProperties` variant, then this returns the stored mass-properties.
     pub fn mass_properties(&self, shape: &dyn Shape) -> MassProperties {
@@ -155,6 +158,10 @@ impl ColliderMassProps {
             ColliderMassProps::Mass(mass) => {
                 if *mass != 0.0 {
                     let mut mprops = shape.mass_properties(1.0);
+                    if mprops.mass() == 0.0 {
+                        // The shape has no volume, so its inertia can’t be scaled to `mass`.
+                        return MassProperties::new(mprops.local_com, *mass, na::zero());
+                    }
                     mprops.set_mass(*mass, true);
                     mprops
                 } else {
@@ -176,6 +183,28 @@ pub struct ColliderParent {
     pub pos_wrt_parent: Isometry<Real>,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// How the CCD solver handles a change of the position of a collider relative to its parent.
+///
+/// See [`Collider::set_position_sync`](crate::geometry::Collider::set_position_sync).
+pub enum ColliderPositionSync {
+    /// The collider keeps being swept along with its parent’s motion by the CCD solver, starting
+    /// from its new relative position.
+    ///
+    /// The change of relative position itself isn’t swept: if the collider is moved inside of
+    /// another object, the CCD solver may report an impact at the start of the timestep.
+    #[default]
+    Swept,
+    /// The collider is ignored by the CCD solver during the timestep following a change of its
+    /// relative position (or of its parent).
+    ///
+    /// This avoids spurious impacts when a collider is repositioned on a fast-moving parent,
+    /// at the cost of possibly missing an impact during that single timestep. Contacts are still
+    /// generated by the narrow-phase.
+    Teleport,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 /// The position of a collider.
@@ -250,6 +279,14 @@ pub struct ColliderMaterial {
     pub friction_combine_rule: CoefficientCombineRule,
     /// The rule applied to combine the restitution coefficients of two colliders.
     pub restitution_combine_rule: CoefficientCombineRule,
+    /// The rolling resistance coefficient of this collider.
+    ///
+    /// It is the length of the lever arm of the torque resisting the rolling of this collider:
+    /// the resisting torque is this coefficient multiplied by the contact normal force.
+    /// Should be `>= 0`.
+    pub rolling_resistance: Real,
+    /// The rule applied to combine the rolling resistance coefficients of two colliders.
+    pub rolling_resistance_combine_rule: CoefficientCombineRule,
 }
 
 impl ColliderMaterial {
@@ -270,10 +307,105 @@ impl Default for ColliderMaterial {
             restitution: 0.0,
             friction_combine_rule: CoefficientCombineRule::default(),
             restitution_combine_rule: CoefficientCombineRule::default(),
+            rolling_resistance: 0.0,
+            rolling_resistance_combine_rule: CoefficientCombineRule::default(),
         }
     }
 }
 
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// Materials assigned to the individual subshapes of a composite collider.
+///
+/// This is typically used to give different friction and restitution coefficients to the
+/// triangles of a single trimesh (e.g. the tarmac and grass of a racing track). Each subshape
+/// references one entry of `materials` through its index in `material_ids`.
+pub struct SubshapeMaterials {
+    /// The table of materials referenced by `material_ids`.
+    pub materials: Vec<ColliderMaterial>,
+    /// For each subshape (e.g. each triangle of a trimesh), the index of its material in
+    /// `materials`.
+    pub material_ids: Vec<u32>,
+}
+
+impl SubshapeMaterials {
+    /// Creates a table of subshape materials.
+    pub fn new(materials: Vec<ColliderMaterial>, material_ids: Vec<u32>) -> Self {
+        Self {
+            materials,
+            material_ids,
+        }
+    }
+
+    /// The material of the given subshape.
+    ///
+    /// Returns `None` if the subshape or its material index are out of bounds.
+    pub fn material(&self, subshape: u32) -> Option<&ColliderMaterial> {
+        let id = *self.material_ids.get(subshape as usize)?;
+        self.materials.get(id as usize)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// The one-way collision mode of a collider, e.g., a platform that can be jumped through from below.
+///
+/// Other colliders can only collide with a one-way collider from the side its `local_normal`
+/// points to. Contacts on the other side, or contacts with colliders moving through the
+/// platform, are discarded by the narrow-phase before reaching the constraints solver.
+pub struct OneWayPlatform {
+    /// The direction, in the local-space of the collider, from which other colliders can land
+    /// on it.
+    pub local_normal: Unit<Vector<Real>>,
+    /// The penetration depth beyond which a contact is considered as passing through the platform.
+    ///
+    /// Contact points penetrating deeper than this tolerance are always discarded. Should be `>= 0`.
+    pub tolerance: Real,
+}
+
+impl OneWayPlatform {
+    /// Creates a one-way collision mode allowing contacts from the side pointed to by `local_normal`.
+    pub fn new(local_normal: Unit<Vector<Real>>, tolerance: Real) -> Self {
+        Self {
+            local_normal,
+            tolerance: tolerance.max(0.0),
+        }
+    }
+
+    /// Checks if a contact point with this platform should be kept.
+    ///
+    /// The `outward_normal` is the world-space contact normal pointing outward the platform,
+    /// `dist` is the contact distance (negative if penetrating), and `relative_velocity` is the
+    /// velocity of the other collider at the contact point, relative to the platform.
+    pub(crate) fn allows_contact(
+        &self,
+        platform_pos: &Isometry<Real>,
+        outward_normal: &Vector<Real>,
+        dist: Real,
+        relative_velocity: &Vector<Real>,
+    ) -> bool {
+        let allowed_normal = platform_pos * self.local_normal;
+
+        // Reject contacts on the wrong side, and colliders that are already deep into the
+        // platform (they are passing through it).
+        if outward_normal.dot(&allowed_normal) <= 0.0 || dist < -self.tolerance {
+            return false;
+        }
+
+        // A penetrating collider moving away along the allowed normal is still crossing the
+        // platform from the other side: it must not be snapped on top of it.
+        dist >= 0.0 || relative_velocity.dot(&allowed_normal) <= 0.0
+    }
+
+    /// Checks if an impact found by the CCD solver with this platform should be resolved.
+    ///
+    /// The `local_normal1` is the impact normal pointing outward the platform, expressed in
+    /// its local-space.
+    pub(crate) fn allows_impact(&self, local_normal1: &Vector<Real>) -> bool {
+        local_normal1.dot(&self.local_normal) > 0.0
+    }
+}
+
 bitflags::bitflags! {
     #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
     #[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
@@ -292,6 +424,22 @@ bitflags::bitflags! {
     /// - ❌ Kinematic ↔ Kinematic (platforms don't collide - they're user-controlled)
     /// - ❌ Kinematic ↔ Fixed (platforms don't collide with walls)
     ///
+    /// A pair of colliders is considered by the narrow-phase if **at least one** of the two
+    /// colliders enables the combination of body types involved. Colliders without a parent
+    /// rigid-body are treated as attached to a fixed body.
+    ///
+    /// Enabling contacts between two non-dynamic bodies (e.g. an elevator and a kinematic door)
+    /// generates contact manifolds and collision events, but the constraints solver can't move
+    /// any of them: it is up to the user to react to these contacts.
+    ///
+    /// ## Interaction with sensors
+    ///
+    /// The active collision types are orthogonal to the sensor flag: they decide whether a pair
+    /// of colliders is considered at all, while the sensor flag decides whether that pair
+    /// generates contacts or only intersection events. In particular, a sensor attached to a
+    /// fixed body (or to no body) won't detect kinematic bodies unless
+    /// [`ActiveCollisionTypes::KINEMATIC_FIXED`] is enabled.
+    ///
     /// # Example
     /// ```
     /// # use rapier3d::prelude::*;
@@ -1,12 +1,55 @@
 use crate::data::arena::Arena;
-use crate::data::{HasModifiedFlag, ModifiedObjects};
-use crate::dynamics::{IslandManager, RigidBodyHandle, RigidBodySet};
+use crate::data::{Coarena, HasModifiedFlag, ModifiedObjects};
+use crate::dynamics::{IslandManager, RigidBody, RigidBodyHandle, RigidBodySet};
 use crate::geometry::{Collider, ColliderChanges, ColliderHandle, ColliderParent};
 use crate::math::Isometry;
+use parry::utils::hashmap::{Entry, HashMap};
 use std::ops::{Index, IndexMut};
 
 pub(crate) type ModifiedColliders = ModifiedObjects<ColliderHandle, Collider>;
 
+/// Reverse mapping from the (nonzero) `user_data` of colliders to their handles.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Default, Debug)]
+struct UserDataIndex {
+    #[cfg_attr(
+        feature = "serde-serialize",
+        serde(
+            serialize_with = "crate::utils::serde::serialize_to_vec_tuple",
+            deserialize_with = "crate::utils::serde::deserialize_from_vec_tuple"
+        )
+    )]
+    handles: HashMap<u128, Vec<ColliderHandle>>,
+    // The user-data each collider is currently indexed with.
+    keys: Coarena<u128>,
+}
+
+impl UserDataIndex {
+    fn update(&mut self, handle: ColliderHandle, user_data: u128) {
+        if self.keys.get(handle.0) == Some(&user_data) {
+            return;
+        }
+
+        self.remove(handle);
+
+        if user_data != 0 {
+            self.handles.entry(user_data).or_default().push(handle);
+            self.keys.insert(handle.0, user_data);
+        }
+    }
+
+    fn remove(&mut self, handle: ColliderHandle) {
+        if let Some(user_data) = self.keys.remove(handle.0, 0) {
+            if let Entry::Occupied(mut entry) = self.handles.entry(user_data) {
+                entry.get_mut().retain(|h| *h != handle);
+                if entry.get().is_empty() {
+                    entry.remove();
+                }
+            }
+        }
+    }
+}
+
 impl HasModifiedFlag for Collider {
     #[inline]
     fn has_modified_flag(&self) -> bool {
@@ -48,6 +91,7 @@ pub struct ColliderSet {
     pub(crate) colliders: Arena<Collider>,
     pub(crate) modified_colliders: ModifiedColliders,
     pub(crate) removed_colliders: Vec<ColliderHandle>,
+    user_data_index: UserDataIndex,
 }
 
 impl ColliderSet {
@@ -57,6 +101,7 @@ impl ColliderSet {
             colliders: Arena::new(),
             modified_colliders: Default::default(),
             removed_colliders: Vec::new(),
+            user_data_index: UserDataIndex::default(),
         }
     }
 
@@ -68,10 +113,17 @@ impl ColliderSet {
             colliders: Arena::with_capacity(capacity),
             modified_colliders: ModifiedColliders::with_capacity(capacity),
             removed_colliders: Vec::new(),
+            user_data_index: UserDataIndex::default(),
         }
     }
 
     pub(crate) fn take_modified(&mut self) -> ModifiedColliders {
+        // The user-data of the modified colliders may have changed.
+        for handle in self.modified_colliders.iter() {
+            if let Some(co) = self.colliders.get(handle.0) {
+                self.user_data_index.update(*handle, co.user_data);
+            }
+        }
         std::mem::take(&mut self.modified_colliders)
     }
 
@@ -157,6 +209,8 @@ impl ColliderSet {
         //       the set yet.
         self.modified_colliders
             .push_unchecked(handle, &mut self.colliders[handle.0]);
+        self.user_data_index
+            .update(handle, self.colliders[handle.0].user_data);
         handle
     }
 
@@ -184,7 +238,83 @@ impl ColliderSet {
         parent_handle: RigidBodyHandle,
         bodies: &mut RigidBodySet,
     ) -> ColliderHandle {
-        let mut coll = coll.into();
+        let parent = bodies
+            .get_mut_internal_with_modification_tracking(parent_handle)
+            .expect("Parent rigid body not found.");
+        let handle =
+            self.insert_with_parent_without_world_mprops(coll.into(), parent_handle, parent);
+        parent
+            .mprops
+            .update_world_mass_properties(parent.body_type, &parent.pos.position);
+        handle
+    }
+
+    /// Adds several standalone colliders (not attached to any body) at once.
+    ///
+    /// Returns the handles of the new colliders, in the same order as `colliders`. Like for
+    /// [`Self::insert`], the colliders are registered into the broad-phase all at once at the
+    /// next simulation step.
+    pub fn insert_batch<C: Into<Collider>>(
+        &mut self,
+        colliders: impl IntoIterator<Item = C>,
+    ) -> Vec<ColliderHandle> {
+        let colliders = colliders.into_iter();
+        self.colliders.reserve(colliders.size_hint().0);
+        colliders.map(|coll| self.insert(coll)).collect()
+    }
+
+    /// Adds several colliders attached to the same rigid-body at once.
+    ///
+    /// Returns the handles of the new colliders, in the same order as `colliders`. This is
+    /// equivalent to calling [`Self::insert_with_parent`] for each collider, except that the
+    /// world-space mass properties of the parent rigid-body are only updated once after all
+    /// the colliders are attached. This is useful when attaching a large number of colliders
+    /// to a single body.
+    ///
+    /// # Example
+    /// ```
+    /// # use rapier3d::prelude::*;
+    /// # let mut colliders = ColliderSet::new();
+    /// # let mut bodies = RigidBodySet::new();
+    /// let body = bodies.insert(RigidBodyBuilder::dynamic());
+    /// let handles = colliders.insert_batch_with_parent(
+    ///     (0..1000).map(|i| ColliderBuilder::ball(0.1).translation(vector![i as Real, 0.0, 0.0])),
+    ///     body,
+    ///     &mut bodies,
+    /// );
+    /// assert_eq!(handles.len(), 1000);
+    /// ```
+    pub fn insert_batch_with_parent<C: Into<Collider>>(
+        &mut self,
+        colliders: impl IntoIterator<Item = C>,
+        parent_handle: RigidBodyHandle,
+        bodies: &mut RigidBodySet,
+    ) -> Vec<ColliderHandle> {
+        let colliders = colliders.into_iter();
+        self.colliders.reserve(colliders.size_hint().0);
+
+        let parent = bodies
+            .get_mut_internal_with_modification_tracking(parent_handle)
+            .expect("Parent rigid body not found.");
+        let handles = colliders
+            .map(|coll| {
+                self.insert_with_parent_without_world_mprops(coll.into(), parent_handle, parent)
+            })
+            .collect();
+        parent
+            .mprops
+            .update_world_mass_properties(parent.body_type, &parent.pos.position);
+        handles
+    }
+
+    /// Inserts a collider attached to `parent`, without updating the world-space mass
+    /// properties of `parent`.
+    fn insert_with_parent_without_world_mprops(
+        &mut self,
+        mut coll: Collider,
+        parent_handle: RigidBodyHandle,
+        parent: &mut RigidBody,
+    ) -> ColliderHandle {
         // Make sure the internal links are reset, they may not be
         // if this collider was obtained by cloning another one.
         coll.reset_internal_references();
@@ -198,22 +328,22 @@ impl ColliderSet {
             });
         }
 
-        // NOTE: we use `get_mut` instead of `get_mut_internal` so that the
-        // modification flag is updated properly.
-        let parent = bodies
-            .get_mut_internal_with_modification_tracking(parent_handle)
-            .expect("Parent rigid body not found.");
         let handle = ColliderHandle(self.colliders.insert(coll));
         let coll = self.colliders.get_mut(handle.0).unwrap();
         // NOTE: we push unchecked because this is a brand-new collider
         //       so it was initialized with the changed flag but isn’t in
         //       the set yet.
         self.modified_colliders.push_unchecked(handle, coll);
+        self.user_data_index.update(handle, coll.user_data);
 
-        parent.add_collider_internal(
+        parent.colliders.attach_collider_without_world_mprops(
+            &mut parent.changes,
+            &mut parent.ccd,
+            &mut parent.mprops,
+            &parent.pos,
             handle,
-            coll.parent.as_mut().unwrap(),
             &mut coll.pos,
+            coll.parent.as_ref().unwrap(),
             &coll.shape,
             &coll.mprops,
         );
@@ -321,6 +451,7 @@ impl ColliderSet {
         wake_up: bool,
     ) -> Option<Collider> {
         let collider = self.colliders.remove(handle.0)?;
+        self.user_data_index.remove(handle);
 
         /*
          * Delete the collider from its parent body.
@@ -347,6 +478,54 @@ impl ColliderSet {
         Some(collider)
     }
 
+    /// Sets the [`Collider::user_data`] of the collider with the given handle.
+    ///
+    /// Unlike writing [`Collider::user_data`] directly, this immediately updates the index used
+    /// by [`Self::by_user_data`]. Does nothing if the collider doesn’t exist.
+    pub fn set_user_data(&mut self, handle: ColliderHandle, user_data: u128) {
+        if let Some(co) = self.colliders.get_mut(handle.0) {
+            co.user_data = user_data;
+            self.user_data_index.update(handle, user_data);
+        }
+    }
+
+    /// Iterates through the handles of all the colliders with the given `user_data`.
+    ///
+    /// This is a constant-time lookup that can be used to map stable external identifiers stored
+    /// in [`Collider::user_data`] back to collider handles, e.g., after deserialization. Colliders
+    /// with a `user_data` of zero (the default) are not indexed so nothing is returned for
+    /// `user_data == 0`.
+    ///
+    /// The index is updated immediately by [`Self::set_user_data`], insertions and removals.
+    /// Direct writes to [`Collider::user_data`] are only indexed at the next timestep: until
+    /// then, such a collider isn’t returned for either its old or its new `user_data`.
+    ///
+    /// # Example
+    /// ```
+    /// # use rapier3d::prelude::*;
+    /// let mut colliders = ColliderSet::new();
+    /// let handle = colliders.insert(ColliderBuilder::ball(0.5).user_data(42));
+    /// assert_eq!(colliders.by_user_data(42).collect::<Vec<_>>(), vec![handle]);
+    ///
+    /// colliders.set_user_data(handle, 7);
+    /// assert_eq!(colliders.by_user_data(42).count(), 0);
+    /// assert_eq!(colliders.by_user_data(7).collect::<Vec<_>>(), vec![handle]);
+    /// ```
+    pub fn by_user_data(&self, user_data: u128) -> impl Iterator<Item = ColliderHandle> + '_ {
+        self.user_data_index
+            .handles
+            .get(&user_data)
+            .into_iter()
+            .flatten()
+            // Skip the colliders whose user-data was written directly since the last timestep.
+            .filter(move |handle| {
+                self.colliders
+                    .get(handle.0)
+                    .is_some_and(|co| co.user_data == user_data)
+            })
+            .copied()
+    }
+
     /// Gets a collider by its index without knowing the generation number.
     ///
     /// ⚠️ **Advanced/unsafe usage** - prefer [`get()`](Self::get) instead! See [`RigidBodySet::get_unknown_gen`] for details.
@@ -455,3 +634,131 @@ impl IndexMut<ColliderHandle> for ColliderSet {
         collider
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::ColliderSet;
+    use crate::dynamics::{IslandManager, RigidBodyBuilder, RigidBodySet};
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Point, Real, Vector};
+
+    #[test]
+    fn insert_batch_with_parent_matches_individual_inserts() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let batched = bodies.insert(RigidBodyBuilder::dynamic());
+        let individual = bodies.insert(RigidBodyBuilder::dynamic());
+        let builders: Vec<_> = (0..10)
+            .map(|i| ColliderBuilder::ball(0.5).translation(Vector::x() * i as Real))
+            .collect();
+
+        let handles = colliders.insert_batch_with_parent(builders.clone(), batched, &mut bodies);
+        for builder in builders {
+            colliders.insert_with_parent(builder, individual, &mut bodies);
+        }
+
+        assert_eq!(handles.len(), 10);
+        assert_eq!(bodies[batched].colliders(), &handles[..]);
+        for (i, handle) in handles.iter().enumerate() {
+            assert_eq!(colliders[*handle].parent(), Some(batched));
+            assert_eq!(
+                colliders[*handle]
+                    .position_wrt_parent()
+                    .unwrap()
+                    .translation
+                    .x,
+                i as Real
+            );
+        }
+
+        assert!((bodies[batched].mass() - bodies[individual].mass()).abs() < 1.0e-3);
+        assert!(
+            (bodies[batched].center_of_mass() - bodies[individual].center_of_mass()).norm()
+                < 1.0e-3
+        );
+
+        let standalone = colliders.insert_batch((0..5).map(|_| ColliderBuilder::ball(0.5)));
+        assert_eq!(standalone.len(), 5);
+        assert!(standalone.iter().all(|h| colliders[*h].parent().is_none()));
+        assert_eq!(colliders.len(), 25);
+    }
+
+    #[test]
+    fn collider_mass_takes_precedence_over_density() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let mass = 10.0;
+        let builder = ColliderBuilder::ball(0.5).translation(Vector::x());
+        let volume = builder.shape.mass_properties(1.0).mass();
+        let expected = builder.shape.mass_properties(mass / volume);
+
+        for builder in [
+            builder.clone().mass(mass).density(50.0),
+            builder.clone().density(50.0).mass(mass),
+        ] {
+            let body = bodies.insert(RigidBodyBuilder::dynamic());
+            let handle = colliders.insert_with_parent(builder, body, &mut bodies);
+            assert_eq!(colliders[handle].mass(), mass);
+
+            let mprops = bodies[body].mass_properties().local_mprops;
+            approx::assert_relative_eq!(mprops.mass(), mass, epsilon = 1.0e-3);
+            approx::assert_relative_eq!(
+                mprops.local_com,
+                Point::from(Vector::x()),
+                epsilon = 1.0e-5
+            );
+            approx::assert_relative_eq!(
+                mprops.principal_inertia(),
+                expected.principal_inertia(),
+                epsilon = 1.0e-3
+            );
+        }
+    }
+
+    #[test]
+    fn by_user_data_tracks_insertions_modifications_and_removals() {
+        let mut islands = IslandManager::new();
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let body = bodies.insert(RigidBodyBuilder::dynamic());
+        let co1 = colliders.insert(ColliderBuilder::ball(0.5).user_data(1));
+        let co2 = colliders.insert_with_parent(
+            ColliderBuilder::ball(0.5).user_data(2),
+            body,
+            &mut bodies,
+        );
+        let co3 = colliders.insert(ColliderBuilder::ball(0.5));
+
+        let lookup = |colliders: &ColliderSet, user_data| {
+            let mut handles: Vec<_> = colliders.by_user_data(user_data).collect();
+            handles.sort_by_key(|h| h.into_raw_parts());
+            handles
+        };
+
+        assert_eq!(lookup(&colliders, 1), vec![co1]);
+        assert_eq!(lookup(&colliders, 2), vec![co2]);
+        assert!(lookup(&colliders, 0).is_empty());
+
+        // Modifications through the set are visible immediately.
+        colliders.set_user_data(co3, 1);
+        colliders.set_user_data(co2, 3);
+        assert_eq!(lookup(&colliders, 1), vec![co1, co3]);
+        assert!(lookup(&colliders, 2).is_empty());
+        assert_eq!(lookup(&colliders, 3), vec![co2]);
+
+        // Direct modifications are only visible once the index is refreshed.
+        colliders[co2].user_data = 4;
+        assert!(lookup(&colliders, 3).is_empty());
+        assert!(lookup(&colliders, 4).is_empty());
+        let _ = colliders.take_modified();
+        assert_eq!(lookup(&colliders, 4), vec![co2]);
+
+        colliders.remove(co1, &mut islands, &mut bodies, false);
+        assert_eq!(lookup(&colliders, 1), vec![co3]);
+
+        // The handle of the removed collider is reused without being indexed.
+        let co4 = colliders.insert(ColliderBuilder::ball(0.5));
+        assert_eq!(co4.into_raw_parts().0, co1.into_raw_parts().0);
+        assert_eq!(lookup(&colliders, 1), vec![co3]);
+    }
+}
@@ -1,14 +1,13 @@
-#[cfg(doc)]
-use super::Collider;
-use super::CollisionEvent;
+use super::{Collider, CollisionEvent};
 use crate::dynamics::{RigidBodyHandle, RigidBodySet};
 use crate::geometry::{ColliderHandle, ColliderSet, Contact, ContactManifold};
-use crate::math::{Point, Real, TangentImpulse, Vector};
+use crate::math::{Isometry, Point, Real, TangentImpulse, Vector};
 use crate::pipeline::EventHandler;
 use crate::prelude::CollisionEventFlags;
 use crate::utils::SimdRealCopy;
 use parry::math::{SIMD_WIDTH, SimdReal};
 use parry::query::ContactManifoldsWorkspace;
+use parry::shape::PackedFeatureId;
 
 bitflags::bitflags! {
     #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -66,6 +65,11 @@ impl Default for ContactData {
 pub struct IntersectionPair {
     /// Are the colliders intersecting?
     pub intersecting: bool,
+    /// For how long the colliders have been intersecting without interruption.
+    ///
+    /// The timestep length is added to this duration at each timestep where the colliders
+    /// are intersecting. It is reset to zero when they stop intersecting.
+    pub duration: Real,
     /// Was a `CollisionEvent::Started` emitted for this collider?
     pub(crate) start_event_emitted: bool,
 }
@@ -74,6 +78,7 @@ impl IntersectionPair {
     pub(crate) fn new() -> Self {
         Self {
             intersecting: false,
+            duration: 0.0,
             start_event_emitted: false,
         }
     }
@@ -162,6 +167,100 @@ pub struct ContactPair {
     pub(crate) workspace: Option<ContactManifoldsWorkspace>,
 }
 
+/// An identifier of a contact point, stable across timesteps.
+///
+/// It is made of the sub-shapes of the contact manifold, and of the features (vertices, edges,
+/// faces) of these sub-shapes touching at the contact point. These are the same identifiers the
+/// narrow-phase relies on to transfer the solver impulses of a contact from one timestep to the
+/// next for warmstarting.
+///
+/// The points of a contact manifold may be reordered from one timestep to the next, but a contact
+/// keeps the same id for as long as the same pair of features stays in contact. When a contact is
+/// lost, or when it slides onto another feature (e.g. from a face to one of its edges), it is
+/// replaced by a contact with a different id.
+///
+/// Shapes that don’t track their features, like custom shapes, report
+/// [`PackedFeatureId::UNKNOWN`]. Several contacts of the same manifold may then share the same id.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ContactId {
+    /// The sub-shape of the first collider’s shape involved in the contact.
+    ///
+    /// This is always zero if the shape isn’t composite.
+    pub subshape1: u32,
+    /// The sub-shape of the second collider’s shape involved in the contact.
+    ///
+    /// This is always zero if the shape isn’t composite.
+    pub subshape2: u32,
+    /// The feature of the first sub-shape touching at the contact point.
+    pub fid1: PackedFeatureId,
+    /// The feature of the second sub-shape touching at the contact point.
+    pub fid2: PackedFeatureId,
+}
+
+impl ContactId {
+    /// The identifier of the `contact` point of the given `manifold`.
+    pub fn new(manifold: &ContactManifold, contact: &Contact) -> Self {
+        Self {
+            subshape1: manifold.subshape1,
+            subshape2: manifold.subshape2,
+            fid1: contact.fid1,
+            fid2: contact.fid2,
+        }
+    }
+}
+
+/// The frames in which the local data of a [`ContactManifold`] are expressed.
+///
+/// The normals (`local_n1`, `local_n2`) and contact points (`local_p1`, `local_p2`) of a contact
+/// manifold are expressed in the local-space of the sub-shapes in contact. These frames map them
+/// to the local-space of the colliders’ parent rigid-bodies (or to world-space for colliders
+/// without a parent), which remain valid as the bodies move. Unlike world-space contact points,
+/// no precision is lost when mapping them back onto the bodies at a later time.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ContactManifoldFrames {
+    /// The frame of the manifold’s first sub-shape, relative to the first collider’s parent.
+    pub local_frame1: Isometry<Real>,
+    /// The frame of the manifold’s second sub-shape, relative to the second collider’s parent.
+    pub local_frame2: Isometry<Real>,
+}
+
+impl ContactManifoldFrames {
+    /// The frames of `manifold`, computed between the colliders `co1` and `co2`.
+    pub fn new(manifold: &ContactManifold, co1: &Collider, co2: &Collider) -> Self {
+        let frame = |co: &Collider, subshape_pos: Option<Isometry<Real>>| {
+            let co_frame = co.position_wrt_parent().unwrap_or(co.position());
+            subshape_pos.map(|pos| co_frame * pos).unwrap_or(*co_frame)
+        };
+
+        Self {
+            local_frame1: frame(co1, manifold.subshape_pos1),
+            local_frame2: frame(co2, manifold.subshape_pos2),
+        }
+    }
+
+    /// The contact normal, pointing from the first to the second collider, in the local-space of
+    /// the first collider’s parent.
+    pub fn local_normal1(&self, manifold: &ContactManifold) -> Vector<Real> {
+        self.local_frame1 * manifold.local_n1
+    }
+
+    /// The contact normal, pointing from the second to the first collider, in the local-space of
+    /// the second collider’s parent.
+    pub fn local_normal2(&self, manifold: &ContactManifold) -> Vector<Real> {
+        self.local_frame2 * manifold.local_n2
+    }
+
+    /// The contact point on the first collider, in the local-space of its parent.
+    pub fn local_anchor1(&self, contact: &Contact) -> Point<Real> {
+        self.local_frame1 * contact.local_p1
+    }
+
+    /// The contact point on the second collider, in the local-space of its parent.
+    pub fn local_anchor2(&self, contact: &Contact) -> Point<Real> {
+        self.local_frame2 * contact.local_p2
+    }
+}
+
 impl Default for ContactPair {
     fn default() -> Self {
         Self::new(ColliderHandle::invalid(), ColliderHandle::invalid())
@@ -224,6 +323,75 @@ impl ContactPair {
         result
     }
 
+    /// Iterates through all the contact points of this pair, alongside their stable identifier.
+    ///
+    /// See [`ContactId`] for details on the guarantees of the identifiers.
+    pub fn contacts_with_ids(
+        &self,
+    ) -> impl Iterator<Item = (ContactId, &ContactManifold, &Contact)> {
+        self.manifolds.iter().flat_map(|manifold| {
+            manifold
+                .points
+                .iter()
+                .map(move |contact| (ContactId::new(manifold, contact), manifold, contact))
+        })
+    }
+
+    /// Iterates through the contact manifolds of this pair, alongside the frames their local
+    /// contact data are expressed in.
+    ///
+    /// Returns `None` if any of the colliders of this pair no longer exists.
+    ///
+    /// # Example
+    /// ```
+    /// # use rapier3d::prelude::*;
+    /// # use rapier3d::geometry::ContactPair;
+    /// # let colliders = ColliderSet::new();
+    /// # let pair = ContactPair::default();
+    /// for (manifold, frames) in pair.manifolds_with_frames(&colliders).into_iter().flatten() {
+    ///     for contact in &manifold.points {
+    ///         let anchor1 = frames.local_anchor1(contact);
+    ///         let anchor2 = frames.local_anchor2(contact);
+    ///         println!("Contact between {:?} and {:?}", anchor1, anchor2);
+    ///     }
+    /// }
+    /// ```
+    pub fn manifolds_with_frames<'a>(
+        &'a self,
+        colliders: &'a ColliderSet,
+    ) -> Option<impl Iterator<Item = (&'a ContactManifold, ContactManifoldFrames)> + 'a> {
+        let co1 = colliders.get(self.collider1)?;
+        let co2 = colliders.get(self.collider2)?;
+        Some(
+            self.manifolds
+                .iter()
+                .map(move |manifold| (manifold, ContactManifoldFrames::new(manifold, co1, co2))),
+        )
+    }
+
+    /// Finds the contact point with the given identifier, if it still exists.
+    ///
+    /// This can be used to match the contact points of this pair with the ones found at a
+    /// previous timestep.
+    ///
+    /// # Example
+    /// ```
+    /// # use rapier3d::prelude::*;
+    /// # use rapier3d::geometry::{ContactId, ContactPair};
+    /// # let pair = ContactPair::default();
+    /// # let previous_ids: Vec<ContactId> = vec![];
+    /// for id in &previous_ids {
+    ///     if let Some((_manifold, contact)) = pair.find_contact(*id) {
+    ///         println!("Contact {:?} persisted, distance: {}", id, contact.dist);
+    ///     }
+    /// }
+    /// ```
+    pub fn find_contact(&self, id: ContactId) -> Option<(&ContactManifold, &Contact)> {
+        self.contacts_with_ids()
+            .find(|(contact_id, _, _)| *contact_id == id)
+            .map(|(_, manifold, contact)| (manifold, contact))
+    }
+
     /// Finds the contact point with the deepest penetration.
     ///
     /// When objects overlap, this returns the contact point that's penetrating the most.
@@ -338,8 +506,20 @@ pub struct ContactManifoldData {
     // So right now it is best to just serialize this field and keep it that way until it
     // is proven to be actually problematic in real applications (in terms of snapshot size for example).
     pub solver_contacts: Vec<SolverContact>,
+    /// The first friction direction used by the constraints solver during the last timestep.
+    ///
+    /// Together with `normal`, this forms the basis in which the
+    /// [`ContactData::tangent_impulse`] of each contact are expressed: the second friction
+    /// direction is `-normal.cross(&tangent1)`.
+    #[cfg(feature = "dim3")]
+    pub tangent1: Vector<Real>,
     /// The relative dominance of the bodies involved in this contact manifold.
     pub relative_dominance: i16,
+    /// The effective rolling resistance coefficient of this contact manifold.
+    ///
+    /// The torque opposing the relative rolling motion of the two bodies is bounded by this
+    /// coefficient multiplied by the normal force of this manifold.
+    pub rolling_resistance: Real,
     /// A user-defined piece of data.
     pub user_data: u32,
 }
@@ -597,7 +777,10 @@ impl ContactManifoldData {
             solver_flags,
             normal: Vector::zeros(),
             solver_contacts: Vec::new(),
+            #[cfg(feature = "dim3")]
+            tangent1: Vector::zeros(),
             relative_dominance: 0,
+            rolling_resistance: 0.0,
             user_data: 0,
         }
     }
@@ -9,6 +9,7 @@
 /// Two colliders interact only if:
 /// 1. Collider A's memberships overlap with Collider B's filter, AND
 /// 2. Collider B's memberships overlap with Collider A's filter
+/// 3. Neither collider's memberships overlap with the other collider's exclusion mask
 ///
 /// # Common use cases
 ///
@@ -36,6 +37,15 @@
 /// // - Player's membership (GROUP_1) is in enemy's filter (GROUP_1) ✓
 /// // - Enemy's membership (GROUP_2) is in player's filter (GROUP_2) ✓
 /// assert!(player_groups.test(enemy_groups));
+///
+/// // Ragdoll part: collides with everything except the other parts of the same ragdoll.
+/// let ragdoll_groups = InteractionGroups::with_exclusion(
+///     Group::GROUP_4,  // I am in group 4
+///     Group::ALL,      // I collide with all groups…
+///     Group::GROUP_4,  // …except group 4
+/// );
+/// assert!(!ragdoll_groups.test(ragdoll_groups));
+/// assert!(ragdoll_groups.test(enemy_groups.with_filter(Group::ALL)));
 /// ```
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
@@ -45,6 +55,9 @@ pub struct InteractionGroups {
     pub memberships: Group,
     /// Groups filter.
     pub filter: Group,
+    /// Groups excluded from interactions, even if they are part of the filter.
+    #[cfg_attr(feature = "serde-serialize", serde(default = "Group::empty"))]
+    pub exclude: Group,
 }
 
 impl InteractionGroups {
@@ -53,6 +66,20 @@ impl InteractionGroups {
         Self {
             memberships,
             filter,
+            exclude: Group::NONE,
+        }
+    }
+
+    /// Initializes with the given interaction groups, interaction mask, and exclusion mask.
+    ///
+    /// This allows interactions with the groups of `filter` that are not part of `exclude`. For
+    /// example, `filter = Group::ALL` and `exclude = Group::GROUP_2` allows interactions with
+    /// everything except group 2.
+    pub const fn with_exclusion(memberships: Group, filter: Group, exclude: Group) -> Self {
+        Self {
+            memberships,
+            filter,
+            exclude,
         }
     }
 
@@ -82,16 +109,25 @@ impl InteractionGroups {
         self
     }
 
+    /// Sets the exclusion mask of this filter.
+    pub const fn with_exclude(mut self, exclude: Group) -> Self {
+        self.exclude = exclude;
+        self
+    }
+
     /// Check if interactions should be allowed based on the interaction memberships and filter.
     ///
     /// An interaction is allowed iff. the memberships of `self` contain at least one bit set to 1 in common
-    /// with the filter of `rhs`, and vice-versa.
+    /// with the filter of `rhs`, and vice-versa, and the memberships of `self` have no bit set to 1 in
+    /// common with the exclusion mask of `rhs`, and vice-versa.
     #[inline]
     pub const fn test(self, rhs: Self) -> bool {
         // NOTE: since const ops is not stable, we have to convert `Group` into u32
         // to use & operator in const context.
         (self.memberships.bits() & rhs.filter.bits()) != 0
             && (rhs.memberships.bits() & self.filter.bits()) != 0
+            && (self.memberships.bits() & rhs.exclude.bits()) == 0
+            && (rhs.memberships.bits() & self.exclude.bits()) == 0
     }
 }
 
@@ -100,6 +136,7 @@ impl Default for InteractionGroups {
         Self {
             memberships: Group::GROUP_1,
             filter: Group::ALL,
+            exclude: Group::NONE,
         }
     }
 }
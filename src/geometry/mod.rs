@@ -2,12 +2,13 @@
 
 pub use self::broad_phase_bvh::{BroadPhaseBvh, BvhOptimizationStrategy};
 pub use self::broad_phase_pair_event::{BroadPhasePairEvent, ColliderPair};
+pub use self::broad_phase_query_cursor::{BroadPhaseQueryCursor, BroadPhaseQueryEvent};
 pub use self::collider::{Collider, ColliderBuilder};
 pub use self::collider_components::*;
 pub use self::collider_set::ColliderSet;
 pub use self::contact_pair::{
-    ContactData, ContactManifoldData, ContactPair, IntersectionPair, SimdSolverContact,
-    SolverContact, SolverFlags,
+    ContactData, ContactId, ContactManifoldData, ContactManifoldFrames, ContactPair,
+    IntersectionPair, SimdSolverContact, SolverContact, SolverFlags,
 };
 pub use self::interaction_graph::{
     ColliderGraphIndex, InteractionGraph, RigidBodyGraphIndex, TemporaryInteractionIndex,
@@ -21,9 +22,13 @@ pub use parry::partitioning::{Bvh, BvhBuildStrategy};
 pub use parry::query::{PointQuery, PointQueryWithLocation, RayCast, TrackedContact};
 pub use parry::shape::{SharedShape, VoxelState, VoxelType, Voxels};
 
-use crate::math::{Real, Vector};
+use crate::math::{Point, Real, Vector};
+use na::Unit;
 
 /// A contact between two colliders.
+///
+/// Its `fid1` and `fid2` fields identify the features of the shapes touching at this contact.
+/// Combined with the sub-shapes of its manifold, they form a [`ContactId`] stable across timesteps.
 pub type Contact = parry::query::TrackedContact<ContactData>;
 /// A contact manifold between two colliders.
 pub type ContactManifold = parry::query::ContactManifold<ContactManifoldData, ContactData>;
@@ -211,6 +216,47 @@ impl ContactForceEvent {
     }
 }
 
+#[derive(Copy, Clone, PartialEq, Debug)]
+/// The force applied at a single contact point during the last timestep.
+///
+/// See [`NarrowPhase::contact_points_with_forces`].
+pub struct ContactPointForce {
+    /// The world-space contact point on the first collider.
+    pub point1: Point<Real>,
+    /// The world-space contact point on the second collider.
+    pub point2: Point<Real>,
+    /// The world-space (unit) contact normal, pointing outward the first collider.
+    pub normal: Vector<Real>,
+    /// The magnitude of the force applied at this contact point along `self.normal`.
+    pub force_magnitude: Real,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+/// Event occurring when the CCD solver stops a rigid-body at a time of impact.
+///
+/// These events are only generated if enabled with
+/// [`CCDSolver::set_impact_events_enabled`](crate::dynamics::CCDSolver::set_impact_events_enabled).
+/// They are emitted during the same step as the impact, before any contact is generated by the
+/// narrow-phase, making them suitable for playing high-speed impact sounds or spawning effects.
+pub struct CcdImpactEvent {
+    /// The first collider involved in the impact.
+    pub collider1: ColliderHandle,
+    /// The second collider involved in the impact.
+    pub collider2: ColliderHandle,
+    /// The time of impact, relative to the start of the current CCD substep.
+    pub toi: Real,
+    /// The world-space velocity of the impact point on the second collider, relative to the
+    /// velocity of the impact point on the first collider, right before the impact.
+    pub relative_velocity: Vector<Real>,
+    /// The contact point on the first collider, expressed in the local frame of the first collider.
+    pub witness1: Point<Real>,
+    /// The contact point on the second collider, expressed in the local frame of the second collider.
+    pub witness2: Point<Real>,
+    /// The contact normal pointing outward the first collider, expressed in the local frame of
+    /// the first collider.
+    pub normal1: Unit<Vector<Real>>,
+}
+
 pub(crate) use self::collider_set::ModifiedColliders;
 pub(crate) use self::narrow_phase::ContactManifoldIndex;
 pub use parry::shape::*;
@@ -229,6 +275,7 @@ mod narrow_phase;
 
 mod broad_phase_bvh;
 mod broad_phase_pair_event;
+mod broad_phase_query_cursor;
 mod collider;
 mod collider_set;
 mod mesh_converter;
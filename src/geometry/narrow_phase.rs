@@ -10,7 +10,7 @@ use crate::dynamics::{
 use crate::geometry::{
     BoundingVolume, BroadPhasePairEvent, ColliderChanges, ColliderGraphIndex, ColliderHandle,
     ColliderPair, ColliderSet, CollisionEvent, ContactData, ContactManifold, ContactManifoldData,
-    ContactPair, InteractionGraph, IntersectionPair, SolverContact, SolverFlags,
+    ContactPair, ContactPointForce, InteractionGraph, IntersectionPair, SolverContact, SolverFlags,
     TemporaryInteractionIndex,
 };
 use crate::math::{Real, Vector};
@@ -228,6 +228,46 @@ impl NarrowPhase {
             .map(|c| c.2)
     }
 
+    /// The individual contact points between two colliders, with the force applied at each of
+    /// them during the last timestep.
+    ///
+    /// The forces are derived from the impulses computed by the constraints solver, divided by
+    /// the timestep length `dt`. The contact points are expressed in world-space, using the
+    /// current positions of the colliders.
+    ///
+    /// Contact points are only yielded if at least one of the colliders has the
+    /// [`ActiveEvents::CONTACT_FORCE_EVENTS`] flag set, consistently with the emission of
+    /// [`ContactForceEvent`](crate::geometry::ContactForceEvent)s.
+    pub fn contact_points_with_forces<'a>(
+        &'a self,
+        colliders: &'a ColliderSet,
+        collider1: ColliderHandle,
+        collider2: ColliderHandle,
+        dt: Real,
+    ) -> impl Iterator<Item = ContactPointForce> + 'a {
+        let inv_dt = crate::utils::inv(dt);
+        let pair = self.contact_pair(collider1, collider2).and_then(|pair| {
+            let co1 = colliders.get(pair.collider1)?;
+            let co2 = colliders.get(pair.collider2)?;
+            let enabled = (co1.active_events() | co2.active_events())
+                .contains(ActiveEvents::CONTACT_FORCE_EVENTS);
+            enabled.then_some((pair, co1, co2))
+        });
+
+        pair.into_iter().flat_map(move |(pair, co1, co2)| {
+            pair.manifolds.iter().flat_map(move |manifold| {
+                let world_pos1 = manifold.subshape_pos1.prepend_to(co1.position());
+                let world_pos2 = manifold.subshape_pos2.prepend_to(co2.position());
+                manifold.points.iter().map(move |pt| ContactPointForce {
+                    point1: world_pos1 * pt.local_p1,
+                    point2: world_pos2 * pt.local_p2,
+                    normal: manifold.data.normal,
+                    force_magnitude: pt.data.impulse * inv_dt,
+                })
+            })
+        })
+    }
+
     /// The intersection pair involving two specific colliders.
     ///
     /// It is strongly recommended to use the [`NarrowPhase::intersection_pair`] method instead. This
@@ -259,6 +299,24 @@ impl NarrowPhase {
             .map(|c| c.2.intersecting)
     }
 
+    /// For how long two colliders have been intersecting without interruption.
+    ///
+    /// This is the sum of the timestep lengths of all the consecutive timesteps where the
+    /// colliders were detected as intersecting. Returns `None` if the colliders aren’t
+    /// intersecting, or if none of them is a sensor.
+    pub fn intersection_duration(
+        &self,
+        collider1: ColliderHandle,
+        collider2: ColliderHandle,
+    ) -> Option<Real> {
+        let id1 = self.graph_indices.get(collider1.0)?;
+        let id2 = self.graph_indices.get(collider2.0)?;
+        self.intersection_graph
+            .interaction_pair(id1.intersection_graph_index, id2.intersection_graph_index)
+            .filter(|c| c.2.intersecting)
+            .map(|c| c.2.duration)
+    }
+
     /// All the contact pairs maintained by this narrow-phase.
     pub fn contact_pairs(&self) -> impl Iterator<Item = &ContactPair> {
         self.contact_graph.interactions()
@@ -706,6 +764,7 @@ impl NarrowPhase {
     #[profiling::function]
     pub(crate) fn compute_intersections(
         &mut self,
+        dt: Real,
         bodies: &RigidBodySet,
         colliders: &ColliderSet,
         hooks: &dyn PhysicsHooks,
@@ -727,6 +786,9 @@ impl NarrowPhase {
                     && !co2.changes.needs_narrow_phase_update()
                 {
                     // No update needed for these colliders.
+                    if edge.weight.intersecting {
+                        edge.weight.duration += dt;
+                    }
                     return;
                 }
                 if co1.parent.map(|p| p.handle) == co2.parent.map(|p| p.handle)
@@ -787,6 +849,12 @@ impl NarrowPhase {
                     .unwrap_or(false);
             }
 
+            edge.weight.duration = match (had_intersection, edge.weight.intersecting) {
+                (true, true) => edge.weight.duration + dt,
+                (false, true) => dt,
+                (_, false) => 0.0,
+            };
+
             let active_events = co1.flags.active_events | co2.flags.active_events;
 
             if active_events.contains(ActiveEvents::COLLISION_EVENTS)
@@ -891,8 +959,13 @@ impl NarrowPhase {
                     break 'emit_events;
                 }
 
-                // Filter based on collision groups.
-                if !co1.flags.collision_groups.test(co2.flags.collision_groups) {
+                // Filter based on collision groups. If any subshape overrides its collision groups,
+                // the groups are tested for each manifold instead.
+                let has_subshape_groups =
+                    co1.has_subshape_collision_groups() || co2.has_subshape_collision_groups();
+                if !has_subshape_groups
+                    && !co1.flags.collision_groups.test(co2.flags.collision_groups)
+                {
                     pair.clear();
                     break 'emit_events;
                 }
@@ -979,6 +1052,15 @@ impl NarrowPhase {
                     co1.material.restitution_combine_rule,
                     co2.material.restitution_combine_rule,
                 );
+                let rolling_resistance = CoefficientCombineRule::combine(
+                    co1.material.rolling_resistance,
+                    co2.material.rolling_resistance,
+                    co1.material.rolling_resistance_combine_rule,
+                    co2.material.rolling_resistance_combine_rule,
+                );
+
+                let has_subshape_materials =
+                    co1.subshape_materials().is_some() || co2.subshape_materials().is_some();
 
                 let zero = RigidBodyDominance(0); // The value doesn't matter, it will be MAX because of the effective groups.
                 let dominance1 = rb1.map(|rb| rb.dominance).unwrap_or(zero);
@@ -986,6 +1068,19 @@ impl NarrowPhase {
 
                 pair.has_any_active_contact = false;
 
+                if has_subshape_groups {
+                    // NOTE: the manifolds are emptied instead of removed because their indices
+                    //       are referenced by the contact manifold workspace.
+                    for manifold in &mut pair.manifolds {
+                        if !co1
+                            .subshape_collision_groups(manifold.subshape1)
+                            .test(co2.subshape_collision_groups(manifold.subshape2))
+                        {
+                            manifold.points.clear();
+                        }
+                    }
+                }
+
                 for manifold in &mut pair.manifolds {
                     let world_pos1 = manifold.subshape_pos1.prepend_to(&co1.pos);
                     let world_pos2 = manifold.subshape_pos2.prepend_to(&co2.pos);
@@ -997,6 +1092,34 @@ impl NarrowPhase {
                         - dominance2.effective_group(&rb_type2);
                     manifold.data.normal = world_pos1 * manifold.local_n1;
 
+                    let (friction, restitution, rolling_resistance) = if has_subshape_materials {
+                        let material1 = co1.subshape_material(manifold.subshape1);
+                        let material2 = co2.subshape_material(manifold.subshape2);
+                        (
+                            CoefficientCombineRule::combine(
+                                material1.friction,
+                                material2.friction,
+                                material1.friction_combine_rule,
+                                material2.friction_combine_rule,
+                            ),
+                            CoefficientCombineRule::combine(
+                                material1.restitution,
+                                material2.restitution,
+                                material1.restitution_combine_rule,
+                                material2.restitution_combine_rule,
+                            ),
+                            CoefficientCombineRule::combine(
+                                material1.rolling_resistance,
+                                material2.rolling_resistance,
+                                material1.rolling_resistance_combine_rule,
+                                material2.rolling_resistance_combine_rule,
+                            ),
+                        )
+                    } else {
+                        (friction, restitution, rolling_resistance)
+                    };
+                    manifold.data.rolling_resistance = rolling_resistance;
+
                     // Generate solver contacts.
                     for (contact_id, contact) in manifold.points.iter().enumerate() {
                         if contact_id > u8::MAX as usize {
@@ -1006,18 +1129,38 @@ impl NarrowPhase {
 
                         let effective_contact_dist = contact.dist - co1.contact_skin() - co2.contact_skin();
 
-                        let keep_solver_contact = effective_contact_dist < prediction_distance || {
-                            let world_pt1 = world_pos1 * contact.local_p1;
-                            let world_pt2 = world_pos2 * contact.local_p2;
+                        let world_pt1 = world_pos1 * contact.local_p1;
+                        let world_pt2 = world_pos2 * contact.local_p2;
+                        let relative_velocity = || {
                             let vel1 = rb1.map(|rb| rb.velocity_at_point(&world_pt1)).unwrap_or_default();
                             let vel2 = rb2.map(|rb| rb.velocity_at_point(&world_pt2)).unwrap_or_default();
-                            effective_contact_dist + (vel2 - vel1).dot(&manifold.data.normal) * dt < prediction_distance
+                            vel2 - vel1
                         };
 
+                        let keep_solver_contact = effective_contact_dist < prediction_distance
+                            || effective_contact_dist + relative_velocity().dot(&manifold.data.normal) * dt < prediction_distance;
+
+                        // Discard the contacts with one-way platforms coming from the wrong side.
+                        let keep_solver_contact = keep_solver_contact
+                            && co1.one_way_platform().is_none_or(|platform| {
+                                platform.allows_contact(
+                                    &co1.pos,
+                                    &manifold.data.normal,
+                                    effective_contact_dist,
+                                    &relative_velocity(),
+                                )
+                            })
+                            && co2.one_way_platform().is_none_or(|platform| {
+                                platform.allows_contact(
+                                    &co2.pos,
+                                    &-manifold.data.normal,
+                                    effective_contact_dist,
+                                    &-relative_velocity(),
+                                )
+                            });
+
                         if keep_solver_contact {
                             // Generate the solver contact.
-                            let world_pt1 = world_pos1 * contact.local_p1;
-                            let world_pt2 = world_pos2 * contact.local_p2;
                             let effective_point = na::center(&world_pt1, &world_pt2);
 
                             let solver_contact = SolverContact {
@@ -1196,7 +1339,7 @@ mod test {
 
     use crate::prelude::{
         CCDSolver, ColliderBuilder, DefaultBroadPhase, IntegrationParameters, PhysicsPipeline,
-        RigidBodyBuilder,
+        RigidBodyBuilder, SharedShape,
     };
 
     use super::*;
@@ -1475,4 +1618,530 @@ mod test {
             "There should be a contact manifold."
         );
     }
+
+    #[test]
+    pub fn contact_points_with_forces_support_weight() {
+        let mut rigid_body_set = RigidBodySet::new();
+        let mut collider_set = ColliderSet::new();
+
+        let ground = ColliderBuilder::cuboid(10.0, 0.1, 10.0).build();
+        let ground_handle = collider_set.insert(ground);
+
+        let rigid_body = RigidBodyBuilder::dynamic()
+            .translation(vector![0.0, 0.6, 0.0])
+            .build();
+        let body_handle = rigid_body_set.insert(rigid_body);
+        let collider = ColliderBuilder::cuboid(0.5, 0.5, 0.5).mass(2.0).build();
+        let collider_handle =
+            collider_set.insert_with_parent(collider, body_handle, &mut rigid_body_set);
+
+        let gravity = vector![0.0, -9.81, 0.0];
+        let integration_parameters = IntegrationParameters::default();
+        let mut physics_pipeline = PhysicsPipeline::new();
+        let mut island_manager = IslandManager::new();
+        let mut broad_phase = DefaultBroadPhase::new();
+        let mut narrow_phase = NarrowPhase::new();
+        let mut impulse_joint_set = ImpulseJointSet::new();
+        let mut multibody_joint_set = MultibodyJointSet::new();
+        let mut ccd_solver = CCDSolver::new();
+
+        let mut step = |collider_set: &mut ColliderSet, narrow_phase: &mut NarrowPhase| {
+            physics_pipeline.step(
+                &gravity,
+                &integration_parameters,
+                &mut island_manager,
+                &mut broad_phase,
+                narrow_phase,
+                &mut rigid_body_set,
+                collider_set,
+                &mut impulse_joint_set,
+                &mut multibody_joint_set,
+                &mut ccd_solver,
+                &(),
+                &(),
+            );
+        };
+
+        for _ in 0..60 {
+            step(&mut collider_set, &mut narrow_phase);
+        }
+
+        // Contact force events aren’t enabled, so no point is yielded.
+        let dt = integration_parameters.dt;
+        assert_eq!(
+            narrow_phase
+                .contact_points_with_forces(&collider_set, ground_handle, collider_handle, dt)
+                .count(),
+            0
+        );
+
+        collider_set[collider_handle].set_active_events(ActiveEvents::CONTACT_FORCE_EVENTS);
+        step(&mut collider_set, &mut narrow_phase);
+
+        let points: Vec<_> = narrow_phase
+            .contact_points_with_forces(&collider_set, ground_handle, collider_handle, dt)
+            .collect();
+        assert!(points.len() > 1);
+
+        let total_force: Real = points.iter().map(|pt| pt.force_magnitude).sum();
+        let weight = 2.0 * 9.81;
+        assert!(
+            (total_force - weight).abs() < weight * 0.05,
+            "total contact force {total_force} should match the weight {weight}"
+        );
+
+        for pt in &points {
+            assert!(pt.point1.y.abs() < 0.2);
+        }
+    }
+
+    #[test]
+    pub fn collider_set_shape_updates_contacts_and_mass() {
+        let mut rigid_body_set = RigidBodySet::new();
+        let mut collider_set = ColliderSet::new();
+
+        let body_1_handle = rigid_body_set.insert(RigidBodyBuilder::fixed());
+        let collider_1_handle = collider_set.insert_with_parent(
+            ColliderBuilder::ball(0.5),
+            body_1_handle,
+            &mut rigid_body_set,
+        );
+
+        let rigid_body_2 = RigidBodyBuilder::dynamic()
+            .translation(vector![3.0, 0.0, 0.0])
+            .build();
+        let body_2_handle = rigid_body_set.insert(rigid_body_2);
+        let collider_2_handle = collider_set.insert_with_parent(
+            ColliderBuilder::ball(0.5),
+            body_2_handle,
+            &mut rigid_body_set,
+        );
+
+        let gravity = vector![0.0, 0.0, 0.0];
+        let integration_parameters = IntegrationParameters::default();
+        let mut physics_pipeline = PhysicsPipeline::new();
+        let mut island_manager = IslandManager::new();
+        let mut broad_phase = DefaultBroadPhase::new();
+        let mut narrow_phase = NarrowPhase::new();
+        let mut impulse_joint_set = ImpulseJointSet::new();
+        let mut multibody_joint_set = MultibodyJointSet::new();
+        let mut ccd_solver = CCDSolver::new();
+
+        let mut step = |rigid_body_set: &mut RigidBodySet,
+                        collider_set: &mut ColliderSet,
+                        narrow_phase: &mut NarrowPhase| {
+            physics_pipeline.step(
+                &gravity,
+                &integration_parameters,
+                &mut island_manager,
+                &mut broad_phase,
+                narrow_phase,
+                rigid_body_set,
+                collider_set,
+                &mut impulse_joint_set,
+                &mut multibody_joint_set,
+                &mut ccd_solver,
+                &(),
+                &(),
+            );
+        };
+
+        step(&mut rigid_body_set, &mut collider_set, &mut narrow_phase);
+        assert!(
+            narrow_phase
+                .contact_pair(collider_1_handle, collider_2_handle)
+                .is_none()
+        );
+        let small_mass = rigid_body_set[body_2_handle].mass();
+
+        // Grow the second ball so it overlaps with the first one.
+        collider_set[collider_2_handle].set_shape(SharedShape::ball(2.8));
+        step(&mut rigid_body_set, &mut collider_set, &mut narrow_phase);
+
+        let contact_pair = narrow_phase
+            .contact_pair(collider_1_handle, collider_2_handle)
+            .expect("The contact pair should exist after growing the shape.");
+        assert!(contact_pair.has_any_active_contact);
+        assert!(rigid_body_set[body_2_handle].mass() > small_mass);
+    }
+
+    #[test]
+    pub fn compound_subshape_collision_groups() {
+        use crate::geometry::{Group, InteractionGroups};
+        use na::Isometry3;
+
+        let mut rigid_body_set = RigidBodySet::new();
+        let mut collider_set = ColliderSet::new();
+
+        // A compound made of two balls, the second one being in a different group.
+        let compound = ColliderBuilder::compound(vec![
+            (
+                Isometry3::translation(-2.0, 0.0, 0.0),
+                SharedShape::ball(0.5),
+            ),
+            (
+                Isometry3::translation(2.0, 0.0, 0.0),
+                SharedShape::ball(0.5),
+            ),
+        ])
+        .collision_groups(InteractionGroups::new(Group::GROUP_1, Group::ALL))
+        .subshape_collision_groups(1, InteractionGroups::new(Group::GROUP_2, Group::ALL));
+        let compound_body = rigid_body_set.insert(RigidBodyBuilder::fixed());
+        let compound_handle =
+            collider_set.insert_with_parent(compound, compound_body, &mut rigid_body_set);
+
+        // Two balls touching each part of the compound, only interacting with group 1.
+        let mut ball_handles = vec![];
+        for x in [-2.0, 2.0] {
+            let body = rigid_body_set.insert(
+                RigidBodyBuilder::dynamic()
+                    .translation(vector![x, 0.9, 0.0])
+                    .build(),
+            );
+            let collider = ColliderBuilder::ball(0.5)
+                .collision_groups(InteractionGroups::new(Group::GROUP_1, Group::GROUP_1));
+            ball_handles.push(collider_set.insert_with_parent(collider, body, &mut rigid_body_set));
+        }
+
+        let mut physics_pipeline = PhysicsPipeline::new();
+        let mut island_manager = IslandManager::new();
+        let mut broad_phase = DefaultBroadPhase::new();
+        let mut narrow_phase = NarrowPhase::new();
+        let mut impulse_joint_set = ImpulseJointSet::new();
+        let mut multibody_joint_set = MultibodyJointSet::new();
+        let mut ccd_solver = CCDSolver::new();
+
+        physics_pipeline.step(
+            &vector![0.0, 0.0, 0.0],
+            &IntegrationParameters::default(),
+            &mut island_manager,
+            &mut broad_phase,
+            &mut narrow_phase,
+            &mut rigid_body_set,
+            &mut collider_set,
+            &mut impulse_joint_set,
+            &mut multibody_joint_set,
+            &mut ccd_solver,
+            &(),
+            &(),
+        );
+
+        let has_contact = |ball| {
+            narrow_phase
+                .contact_pair(compound_handle, ball)
+                .is_some_and(|pair| pair.has_any_active_contact)
+        };
+        assert!(has_contact(ball_handles[0]));
+        assert!(!has_contact(ball_handles[1]));
+    }
+
+    #[test]
+    pub fn trimesh_subshape_materials() {
+        use crate::geometry::{ColliderMaterial, SubshapeMaterials};
+        use na::point;
+
+        let mut rigid_body_set = RigidBodySet::new();
+        let mut collider_set = ColliderSet::new();
+
+        // Two separate quads, the first one slippery and the second one sticky.
+        let vertices = vec![
+            point![-4.0, 0.0, -1.0],
+            point![-1.0, 0.0, -1.0],
+            point![-1.0, 0.0, 1.0],
+            point![-4.0, 0.0, 1.0],
+            point![1.0, 0.0, -1.0],
+            point![4.0, 0.0, -1.0],
+            point![4.0, 0.0, 1.0],
+            point![1.0, 0.0, 1.0],
+        ];
+        let indices = vec![[0, 2, 1], [0, 3, 2], [4, 6, 5], [4, 7, 6]];
+        let materials = SubshapeMaterials::new(
+            vec![
+                ColliderMaterial::new(0.0, 0.0),
+                ColliderMaterial::new(1.0, 0.0),
+            ],
+            vec![0, 0, 1, 1],
+        );
+        let track = ColliderBuilder::trimesh(vertices, indices)
+            .unwrap()
+            .subshape_materials(materials);
+        let track_handle = collider_set.insert(track);
+
+        let mut ball_handles = vec![];
+        for x in [-2.5, 2.5] {
+            let body = rigid_body_set.insert(
+                RigidBodyBuilder::dynamic()
+                    .translation(vector![x, 0.45, 0.0])
+                    .build(),
+            );
+            ball_handles.push(collider_set.insert_with_parent(
+                ColliderBuilder::ball(0.5),
+                body,
+                &mut rigid_body_set,
+            ));
+        }
+
+        let mut physics_pipeline = PhysicsPipeline::new();
+        let mut island_manager = IslandManager::new();
+        let mut broad_phase = DefaultBroadPhase::new();
+        let mut narrow_phase = NarrowPhase::new();
+        let mut impulse_joint_set = ImpulseJointSet::new();
+        let mut multibody_joint_set = MultibodyJointSet::new();
+        let mut ccd_solver = CCDSolver::new();
+
+        physics_pipeline.step(
+            &vector![0.0, -9.81, 0.0],
+            &IntegrationParameters::default(),
+            &mut island_manager,
+            &mut broad_phase,
+            &mut narrow_phase,
+            &mut rigid_body_set,
+            &mut collider_set,
+            &mut impulse_joint_set,
+            &mut multibody_joint_set,
+            &mut ccd_solver,
+            &(),
+            &(),
+        );
+
+        // The ball’s default friction (0.5) is averaged with each triangle’s friction.
+        for (ball, expected_friction) in ball_handles.into_iter().zip([0.25, 0.75]) {
+            let pair = narrow_phase.contact_pair(track_handle, ball).unwrap();
+            let solver_contacts: Vec<_> = pair
+                .manifolds
+                .iter()
+                .flat_map(|m| m.data.solver_contacts.iter())
+                .collect();
+            assert!(!solver_contacts.is_empty());
+            for contact in solver_contacts {
+                approx::assert_relative_eq!(contact.friction, expected_friction);
+            }
+        }
+    }
+
+    #[test]
+    pub fn kinematic_kinematic_active_collision_types() {
+        use crate::geometry::ActiveCollisionTypes;
+
+        let mut rigid_body_set = RigidBodySet::new();
+        let mut collider_set = ColliderSet::new();
+
+        // An elevator overlapping two kinematic doors, only one of which enables
+        // kinematic ↔ kinematic collisions. The same setup is repeated with sensor doors.
+        let elevator_body = rigid_body_set.insert(RigidBodyBuilder::kinematic_position_based());
+        let elevator = collider_set.insert_with_parent(
+            ColliderBuilder::cuboid(1.0, 1.0, 1.0),
+            elevator_body,
+            &mut rigid_body_set,
+        );
+
+        let mut doors = vec![];
+        for sensor in [false, true] {
+            for active_collision_types in [
+                ActiveCollisionTypes::default(),
+                ActiveCollisionTypes::default() | ActiveCollisionTypes::KINEMATIC_KINEMATIC,
+            ] {
+                let body = rigid_body_set.insert(
+                    RigidBodyBuilder::kinematic_position_based()
+                        .translation(vector![0.0, 0.0, 1.5]),
+                );
+                let collider = ColliderBuilder::cuboid(1.0, 1.0, 1.0)
+                    .sensor(sensor)
+                    .active_collision_types(active_collision_types);
+                doors.push(collider_set.insert_with_parent(collider, body, &mut rigid_body_set));
+            }
+        }
+
+        let mut physics_pipeline = PhysicsPipeline::new();
+        let mut island_manager = IslandManager::new();
+        let mut broad_phase = DefaultBroadPhase::new();
+        let mut narrow_phase = NarrowPhase::new();
+        let mut impulse_joint_set = ImpulseJointSet::new();
+        let mut multibody_joint_set = MultibodyJointSet::new();
+        let mut ccd_solver = CCDSolver::new();
+
+        physics_pipeline.step(
+            &vector![0.0, 0.0, 0.0],
+            &IntegrationParameters::default(),
+            &mut island_manager,
+            &mut broad_phase,
+            &mut narrow_phase,
+            &mut rigid_body_set,
+            &mut collider_set,
+            &mut impulse_joint_set,
+            &mut multibody_joint_set,
+            &mut ccd_solver,
+            &(),
+            &(),
+        );
+
+        let has_contact = |door| {
+            narrow_phase
+                .contact_pair(elevator, door)
+                .is_some_and(|pair| pair.has_any_active_contact)
+        };
+        let intersects = |door| narrow_phase.intersection_pair(elevator, door) == Some(true);
+        assert!(!has_contact(doors[0]));
+        assert!(has_contact(doors[1]));
+        assert!(!intersects(doors[2]));
+        assert!(intersects(doors[3]));
+    }
+
+    #[test]
+    pub fn sensor_intersection_duration() {
+        use crate::pipeline::PhysicsState;
+
+        let mut state = PhysicsState::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let params = IntegrationParameters::default();
+
+        let zone = state
+            .colliders
+            .insert(ColliderBuilder::cuboid(2.0, 2.0, 2.0).sensor(true));
+        let body = state.bodies.insert(RigidBodyBuilder::dynamic());
+        let player =
+            state
+                .colliders
+                .insert_with_parent(ColliderBuilder::ball(0.5), body, &mut state.bodies);
+
+        for i in 1..=10 {
+            state.step(&mut pipeline, &vector![0.0, 0.0, 0.0], &params, &(), &());
+            let duration = state.narrow_phase.intersection_duration(zone, player);
+            approx::assert_relative_eq!(duration.unwrap(), i as Real * params.dt, epsilon = 1.0e-5);
+        }
+
+        // Leaving the zone resets the duration.
+        state.bodies[body].set_translation(vector![10.0, 0.0, 0.0], true);
+        state.step(&mut pipeline, &vector![0.0, 0.0, 0.0], &params, &(), &());
+        assert_eq!(state.narrow_phase.intersection_duration(zone, player), None);
+
+        state.bodies[body].set_translation(vector![0.0, 0.0, 0.0], true);
+        state.step(&mut pipeline, &vector![0.0, 0.0, 0.0], &params, &(), &());
+        approx::assert_relative_eq!(
+            state
+                .narrow_phase
+                .intersection_duration(zone, player)
+                .unwrap(),
+            params.dt
+        );
+    }
+
+    #[test]
+    pub fn contact_ids_persist_across_steps() {
+        use crate::pipeline::PhysicsState;
+
+        let mut state = PhysicsState::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let params = IntegrationParameters::default();
+        let gravity = vector![0.0, -9.81, 0.0];
+
+        let ground = state
+            .colliders
+            .insert(ColliderBuilder::cuboid(10.0, 0.1, 10.0));
+        let body = state
+            .bodies
+            .insert(RigidBodyBuilder::dynamic().translation(vector![0.0, 0.6, 0.0]));
+        let cube = state.colliders.insert_with_parent(
+            ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+            body,
+            &mut state.bodies,
+        );
+
+        let sorted_ids = |state: &PhysicsState| {
+            let pair = state.narrow_phase.contact_pair(ground, cube).unwrap();
+            let mut ids: Vec<_> = pair.contacts_with_ids().map(|(id, _, _)| id).collect();
+            ids.sort_by_key(|id| (id.subshape1, id.subshape2, id.fid1.0, id.fid2.0));
+            ids
+        };
+
+        for _ in 0..10 {
+            state.step(&mut pipeline, &gravity, &params, &(), &());
+        }
+
+        let ids = sorted_ids(&state);
+        assert_eq!(ids.len(), 4);
+        let pair = state.narrow_phase.contact_pair(ground, cube).unwrap();
+        let points: Vec<_> = ids
+            .iter()
+            .map(|id| pair.find_contact(*id).unwrap().1.local_p1)
+            .collect();
+
+        // The cube rests on the same face, so the same contacts persist.
+        for _ in 0..10 {
+            state.step(&mut pipeline, &gravity, &params, &(), &());
+            assert_eq!(sorted_ids(&state), ids);
+
+            let pair = state.narrow_phase.contact_pair(ground, cube).unwrap();
+            for (id, point) in ids.iter().zip(points.iter()) {
+                let contact = pair.find_contact(*id).unwrap().1;
+                assert!((contact.local_p1 - point).norm() < 1.0e-3);
+            }
+        }
+    }
+
+    #[test]
+    pub fn contact_manifold_frames_follow_the_bodies() {
+        use crate::geometry::{Collider, ContactManifoldFrames};
+        use crate::math::Isometry;
+        use crate::pipeline::PhysicsState;
+
+        let mut state = PhysicsState::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let params = IntegrationParameters::default();
+        let gravity = vector![0.0, -9.81, 0.0];
+
+        let ground = state
+            .colliders
+            .insert(ColliderBuilder::cuboid(10.0, 0.1, 10.0));
+        let body = state
+            .bodies
+            .insert(RigidBodyBuilder::dynamic().translation(vector![0.0, 1.6, 0.0]));
+        // The collider is offset from its parent to check the frames aren’t collider-relative.
+        let cube = state.colliders.insert_with_parent(
+            ColliderBuilder::cuboid(0.5, 0.5, 0.5).translation(vector![0.3, -1.0, 0.2]),
+            body,
+            &mut state.bodies,
+        );
+
+        for _ in 0..10 {
+            state.step(&mut pipeline, &gravity, &params, &(), &());
+        }
+
+        let pair = state.narrow_phase.contact_pair(ground, cube).unwrap();
+        let (co1, co2) = (
+            &state.colliders[pair.collider1],
+            &state.colliders[pair.collider2],
+        );
+        let parent_pos = |co: &Collider| {
+            co.parent()
+                .map(|h| *state.bodies[h].position())
+                .unwrap_or_else(Isometry::identity)
+        };
+        let (pos1, pos2) = (parent_pos(co1), parent_pos(co2));
+        let cube_center = state.colliders[cube].position().translation.vector;
+        let mut num_contacts = 0;
+
+        for (manifold, frames) in pair.manifolds_with_frames(&state.colliders).unwrap() {
+            assert_eq!(frames, ContactManifoldFrames::new(manifold, co1, co2));
+            let normal = manifold.data.normal;
+            assert!((pos1 * frames.local_normal1(manifold) - normal).norm() < 1.0e-5);
+            assert!((pos2 * frames.local_normal2(manifold) + normal).norm() < 1.0e-5);
+
+            for contact in &manifold.points {
+                let world1 = pos1 * frames.local_anchor1(contact);
+                let world2 = pos2 * frames.local_anchor2(contact);
+                assert!(((world2 - world1).dot(&normal) - contact.dist).abs() < 1.0e-4);
+
+                // The contact points are at the corners of the cube’s bottom face.
+                for world in [world1, world2] {
+                    let local = world.coords - cube_center;
+                    assert!((local.x.abs() - 0.5).abs() < 1.0e-3);
+                    assert!((local.z.abs() - 0.5).abs() < 1.0e-3);
+                }
+                num_contacts += 1;
+            }
+        }
+
+        assert_eq!(num_contacts, 4);
+    }
 }
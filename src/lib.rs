@@ -9,6 +9,38 @@
 //!   are compliant with the IEEE 754-2008 floating point standard.
 //!
 //! User documentation for Rapier is on [the official Rapier site](https://rapier.rs/docs/).
+//!
+//! ## Cross-platform determinism
+//!
+//! Running the same sequence of operations always gives the same results on a given platform.
+//! Enable the `enhanced-determinism` feature to get bit-identical results across platforms
+//! for the same sequence of operations (and the same float width, i.e., `rapier3d` and
+//! `rapier3d-f64` won’t match each other):
+//! - transcendental functions (`sin`, `cos`, `atan2`, etc.) use a software implementation instead
+//!   of the platform’s intrinsics,
+//! - the internal hash maps and hash sets (e.g. the rigid-bodies to wake up, or the broad-phase
+//!   pairs) iterate in insertion order instead of an order depending on the platform’s hashes,
+//! - the active islands are solved in the order of their smallest rigid-body handle,
+//! - denormal numbers are no longer flushed to zero.
+//!
+//! This doesn’t make the results independent from the history of the simulation: only the
+//! order of the islands is normalized, the order of the rigid-bodies and constraints within an
+//! island still depends on the order in which they were inserted or woken up. Two worlds reaching
+//! the same state through different sequences of operations may still diverge.
+//!
+//! The remaining sources of platform-dependent results are outside of Rapier’s control:
+//! - floating-point operations computed by user code (e.g. forces or velocities computed with the
+//!   standard library’s `sin` or `powf`) before being given to Rapier,
+//! - targets that don’t follow IEEE 754-2008 for basic operations, like 32-bit x86 without SSE2
+//!   (which uses the extended precision of the x87 FPU),
+//! - compiling with fast-math-like flags, or enabling target features through
+//!   `-C target-cpu` that change how dependencies compute `mul_add`,
+//! - the bit patterns of `NaN` values, which are only relevant if the simulation already diverged,
+//! - using different versions of Rapier or of its dependencies (e.g. `parry` or `nalgebra`).
+//!
+//! The order of the events sent to an [`EventHandler`](crate::pipeline::EventHandler) may also
+//! differ when the `parallel` feature is enabled. SIMD features are incompatible with
+//! `enhanced-determinism`.
 
 #![deny(bare_trait_objects)]
 #![warn(missing_docs)]
@@ -105,7 +105,7 @@ impl CollisionPipeline {
             hooks,
             events,
         );
-        narrow_phase.compute_intersections(bodies, colliders, hooks, events);
+        narrow_phase.compute_intersections(0.0, bodies, colliders, hooks, events);
     }
 
     fn clear_modified_colliders(
@@ -22,6 +22,10 @@ pub enum DebugRenderObject<'a> {
     MultibodyJoint(MultibodyJointHandle, &'a Multibody, &'a MultibodyLink),
     /// The contacts of a contact-pair are being rendered.
     ContactPair(&'a ContactPair, &'a Collider, &'a Collider),
+    /// The AABB of a node of the broad-phase BVH is being rendered.
+    ///
+    /// The boolean indicates if the node is a leaf.
+    BroadPhaseNode(&'a Aabb, bool),
 }
 
 /// Trait implemented by graphics backends responsible for rendering the physics scene.
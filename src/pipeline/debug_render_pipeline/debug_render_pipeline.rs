@@ -34,6 +34,9 @@ bitflags::bitflags! {
         const CONTACTS = 1 << 5;
         /// If this flag is set, the Aabbs of colliders will be rendered.
         const COLLIDER_AABBS = 1 << 6;
+        /// If this flag is set, the normal and friction impulses applied by the solver at
+        /// each contact during the last timestep will be rendered as arrows.
+        const CONTACT_IMPULSES = 1 << 7;
     }
 }
 
@@ -166,6 +169,81 @@ impl DebugRenderPipeline {
                 }
             }
         }
+
+        if self.mode.contains(DebugRenderMode::CONTACT_IMPULSES) {
+            for pair in narrow_phase.contact_pairs() {
+                if let (Some(co1), Some(co2)) =
+                    (colliders.get(pair.collider1), colliders.get(pair.collider2))
+                {
+                    let object = DebugRenderObject::ContactPair(pair, co1, co2);
+
+                    if backend.filter_object(object) {
+                        for manifold in &pair.manifolds {
+                            let normal = manifold.data.normal;
+                            #[cfg(feature = "dim2")]
+                            let tangents = (-normal).orthonormal_basis();
+                            #[cfg(feature = "dim3")]
+                            let tangents = [
+                                manifold.data.tangent1,
+                                -normal.cross(&manifold.data.tangent1),
+                            ];
+
+                            for contact in &manifold.data.solver_contacts {
+                                let Some(data) = manifold
+                                    .points
+                                    .get(contact.contact_id[0] as usize)
+                                    .map(|pt| pt.data)
+                                else {
+                                    continue;
+                                };
+
+                                // Render the impulses applied to the second collider’s body.
+                                let scale = self.style.contact_impulse_scale;
+                                let friction_impulse = tangents
+                                    .iter()
+                                    .zip(data.tangent_impulse.iter())
+                                    .map(|(tangent, impulse)| -*tangent * *impulse)
+                                    .fold(Vector::zeros(), |acc, v| acc + v);
+
+                                Self::render_arrow(
+                                    backend,
+                                    object,
+                                    contact.point,
+                                    normal * data.impulse * scale,
+                                    self.style.contact_impulse_color,
+                                );
+                                Self::render_arrow(
+                                    backend,
+                                    object,
+                                    contact.point,
+                                    friction_impulse * scale,
+                                    self.style.contact_friction_impulse_color,
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn render_arrow(
+        backend: &mut impl DebugRenderBackend,
+        object: DebugRenderObject<'_>,
+        origin: Point<Real>,
+        vector: Vector<Real>,
+        color: DebugColor,
+    ) {
+        if vector.norm_squared() == 0.0 {
+            return;
+        }
+
+        let tip = origin + vector;
+        let head = vector * 0.2;
+        let side = vector.orthonormal_vector() * vector.norm() * 0.1;
+        backend.draw_line(object, origin, tip, color);
+        backend.draw_line(object, tip, tip - head + side, color);
+        backend.draw_line(object, tip, tip - head - side, color);
     }
 
     /// Render only the joints from the scene.
@@ -359,7 +437,7 @@ impl DebugRenderPipeline {
     #[profiling::function]
     fn render_shape(
         &mut self,
-        object: DebugRenderObject,
+        object: DebugRenderObject<'_>,
         backend: &mut impl DebugRenderBackend,
         shape: &dyn Shape,
         pos: &Isometry<Real>,
@@ -471,7 +549,7 @@ impl DebugRenderPipeline {
     #[profiling::function]
     fn render_shape(
         &mut self,
-        object: DebugRenderObject,
+        object: DebugRenderObject<'_>,
         backend: &mut impl DebugRenderBackend,
         shape: &dyn Shape,
         pos: &Isometry<Real>,
@@ -625,3 +703,160 @@ impl DebugRenderPipeline {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::IntegrationParameters;
+    use crate::geometry::{Aabb, BoundingVolume, ColliderBuilder};
+    use crate::math::{DIM, Point, Real, Vector};
+    use crate::pipeline::{
+        DebugColor, DebugRenderBackend, DebugRenderObject, PhysicsPipeline, PhysicsState,
+    };
+
+    /// A backend recording the lines it draws, and the broad-phase nodes they belong to.
+    #[derive(Default)]
+    struct LineRecorder {
+        lines: Vec<(Point<Real>, Point<Real>, DebugColor)>,
+        nodes: Vec<(Aabb, bool)>,
+    }
+
+    impl DebugRenderBackend for LineRecorder {
+        fn draw_line(
+            &mut self,
+            object: DebugRenderObject,
+            a: Point<Real>,
+            b: Point<Real>,
+            color: DebugColor,
+        ) {
+            if let DebugRenderObject::BroadPhaseNode(aabb, is_leaf) = object {
+                self.nodes.push((*aabb, is_leaf));
+            }
+            self.lines.push((a, b, color));
+        }
+    }
+
+    #[test]
+    fn broad_phase_nodes_are_rendered_as_boxes() {
+        let mut state = PhysicsState::new();
+        let colliders: Vec<_> = (0..3)
+            .map(|i| {
+                let co = ColliderBuilder::ball(0.5).translation(Vector::x() * i as Real * 10.0);
+                state.colliders.insert(co)
+            })
+            .collect();
+        state.step(
+            &mut PhysicsPipeline::new(),
+            &Vector::zeros(),
+            &IntegrationParameters::default(),
+            &(),
+            &(),
+        );
+
+        let mut backend = LineRecorder::default();
+        state.broad_phase.debug_render(&mut backend);
+
+        // Every box is drawn with all its edges, each ending on two of its corners.
+        let num_edges = DIM << (DIM - 1);
+        assert!(!backend.lines.is_empty());
+        assert_eq!(backend.lines.len(), backend.nodes.len());
+        assert_eq!(backend.lines.len() % num_edges, 0);
+        for ((a, b, _), (aabb, _)) in backend.lines.iter().zip(&backend.nodes) {
+            for pt in [a, b] {
+                assert!((0..DIM).all(|k| pt[k] == aabb.mins[k] || pt[k] == aabb.maxs[k]));
+            }
+            assert_eq!((0..DIM).filter(|k| a[*k] != b[*k]).count(), 1);
+        }
+
+        // Each collider is enclosed by a leaf box.
+        for handle in colliders {
+            let co_aabb = state.colliders[handle].compute_aabb();
+            assert!(
+                backend
+                    .nodes
+                    .iter()
+                    .any(|(aabb, is_leaf)| *is_leaf && aabb.contains(&co_aabb))
+            );
+        }
+    }
+
+    #[test]
+    fn contact_impulses_are_rendered_as_arrows() {
+        use super::{DebugRenderMode, DebugRenderPipeline};
+        use crate::dynamics::RigidBodyBuilder;
+        use crate::pipeline::DebugRenderStyle;
+
+        let mut state = PhysicsState::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let params = IntegrationParameters::default();
+        let gravity = Vector::y() * -9.81;
+
+        // A box sliding along the ground.
+        #[cfg(feature = "dim2")]
+        let (ground, cube) = (
+            ColliderBuilder::cuboid(20.0, 0.5),
+            ColliderBuilder::cuboid(0.5, 0.5),
+        );
+        #[cfg(feature = "dim3")]
+        let (ground, cube) = (
+            ColliderBuilder::cuboid(20.0, 0.5, 20.0),
+            ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+        );
+        let ground = state
+            .colliders
+            .insert(ground.translation(Vector::y() * -0.5).friction(0.5));
+        let rb = RigidBodyBuilder::dynamic()
+            .translation(Vector::y() * 0.5)
+            .linvel(Vector::x() * 5.0);
+        let body = state.bodies.insert(rb);
+        let cube = state
+            .colliders
+            .insert_with_parent(cube.friction(0.5), body, &mut state.bodies);
+
+        for _ in 0..10 {
+            state.step(&mut pipeline, &gravity, &params, &(), &());
+        }
+        assert!(state.bodies[body].linvel().x > 1.0);
+
+        let style = DebugRenderStyle {
+            contact_impulse_scale: 10.0,
+            ..DebugRenderStyle::default()
+        };
+        let mut render_pipeline =
+            DebugRenderPipeline::new(style, DebugRenderMode::CONTACT_IMPULSES);
+        let mut backend = LineRecorder::default();
+        render_pipeline.render(
+            &mut backend,
+            &state.bodies,
+            &state.colliders,
+            &state.impulse_joints,
+            &state.multibody_joints,
+            &state.narrow_phase,
+        );
+
+        // Each arrow is made of three lines, starting with its shaft. The shafts add up to the
+        // total impulse applied to the second collider of the pair.
+        let total_impulse = |color: DebugColor| {
+            let lines: Vec<_> = backend.lines.iter().filter(|l| l.2 == color).collect();
+            assert!(!lines.is_empty());
+            assert_eq!(lines.len() % 3, 0);
+            lines
+                .chunks(3)
+                .map(|arrow| arrow[0].1 - arrow[0].0)
+                .fold(Vector::zeros(), |acc, v| acc + v)
+                / style.contact_impulse_scale
+        };
+        let pair = state.narrow_phase.contact_pair(ground, cube).unwrap();
+        let sign = if pair.collider2 == cube { 1.0 } else { -1.0 };
+        let mass = state.bodies[body].mass();
+
+        // The ground supports the box’s weight…
+        let normal_impulse = total_impulse(style.contact_impulse_color) * sign;
+        let expected = -gravity * mass * params.dt;
+        assert!((normal_impulse - expected).norm() < expected.norm() * 0.1);
+
+        // …and the friction slows it down.
+        let friction_impulse = total_impulse(style.contact_friction_impulse_color) * sign;
+        let expected = -Vector::x() * 0.5 * 9.81 * mass * params.dt;
+        assert!((friction_impulse - expected).norm() < expected.norm() * 0.1);
+    }
+}
@@ -48,6 +48,15 @@ pub struct DebugRenderStyle {
     pub contact_normal_color: DebugColor,
     /// The length of the contact normals.
     pub contact_normal_length: Real,
+    /// The color of the arrows representing the normal impulses applied at each contact.
+    pub contact_impulse_color: DebugColor,
+    /// The color of the arrows representing the friction impulses applied at each contact.
+    pub contact_friction_impulse_color: DebugColor,
+    /// The factor converting a contact impulse magnitude into the length of its arrow.
+    ///
+    /// Impulse magnitudes vary widely depending on the masses and the timestep length, so this
+    /// generally needs to be tuned for each scene.
+    pub contact_impulse_scale: Real,
     /// The color of the colliders' [`Aabb`](crate::geometry::Aabb)s.
     pub collider_aabb_color: DebugColor,
 }
@@ -71,6 +80,9 @@ impl Default for DebugRenderStyle {
             contact_depth_color: [120.0, 1.0, 0.4, 1.0],
             contact_normal_color: [0.0, 1.0, 1.0, 1.0],
             contact_normal_length: 0.3,
+            contact_impulse_color: [60.0, 1.0, 0.5, 1.0],
+            contact_friction_impulse_color: [180.0, 1.0, 0.5, 1.0],
+            contact_impulse_scale: 1.0,
             collider_aabb_color: [124.0, 1.0, 0.4, 1.0],
         }
     }
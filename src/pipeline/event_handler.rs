@@ -1,5 +1,7 @@
-use crate::dynamics::RigidBodySet;
-use crate::geometry::{ColliderSet, CollisionEvent, ContactForceEvent, ContactPair};
+use crate::dynamics::{JointBrokenEvent, RigidBodySet, SleepStateChangedEvent};
+use crate::geometry::{
+    CcdImpactEvent, ColliderSet, CollisionEvent, ContactForceEvent, ContactPair,
+};
 use crate::math::Real;
 use std::sync::mpsc::Sender;
 
@@ -133,6 +135,54 @@ pub trait EventHandler: Send + Sync {
         contact_pair: &ContactPair,
         total_force_magnitude: Real,
     );
+
+    /// Called when the CCD solver stops a rigid-body at a time of impact.
+    ///
+    /// Only triggered if impact events are enabled on the CCD solver with
+    /// [`CCDSolver::set_impact_events_enabled`](crate::dynamics::CCDSolver::set_impact_events_enabled).
+    /// Does nothing by default.
+    ///
+    /// # Use cases
+    /// - Play high-speed impact sounds scaled by the impact velocity
+    /// - Spawn impact effects before the contact is even detected by the narrow-phase
+    fn handle_ccd_impact_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        _event: CcdImpactEvent,
+    ) {
+    }
+
+    /// Called when an impulse joint breaks.
+    ///
+    /// A joint breaks when the force or torque it applies exceeds its
+    /// [`GenericJoint::breaking_force`](crate::dynamics::GenericJoint::breaking_force) or
+    /// [`GenericJoint::breaking_torque`](crate::dynamics::GenericJoint::breaking_torque).
+    /// The joint is already removed from the joint set when this is called. Does nothing by default.
+    fn handle_joint_broken_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        _event: JointBrokenEvent,
+    ) {
+    }
+
+    /// Called when a rigid-body falls asleep or wakes up.
+    ///
+    /// Only triggered if sleep events are enabled on the island manager with
+    /// [`IslandManager::set_sleep_events_enabled`](crate::dynamics::IslandManager::set_sleep_events_enabled).
+    /// Does nothing by default.
+    ///
+    /// # Use cases
+    /// - Detect when a pile of objects settled down
+    /// - Stop ambient effects (e.g. rolling sounds) of objects at rest
+    fn handle_sleep_state_changed_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        _event: SleepStateChangedEvent,
+    ) {
+    }
 }
 
 impl EventHandler for () {
@@ -181,6 +231,9 @@ impl EventHandler for () {
 pub struct ChannelEventCollector {
     collision_event_sender: Sender<CollisionEvent>,
     contact_force_event_sender: Sender<ContactForceEvent>,
+    ccd_impact_event_sender: Option<Sender<CcdImpactEvent>>,
+    joint_broken_event_sender: Option<Sender<JointBrokenEvent>>,
+    sleep_state_changed_event_sender: Option<Sender<SleepStateChangedEvent>>,
 }
 
 impl ChannelEventCollector {
@@ -192,8 +245,44 @@ impl ChannelEventCollector {
         Self {
             collision_event_sender,
             contact_force_event_sender,
+            ccd_impact_event_sender: None,
+            joint_broken_event_sender: None,
+            sleep_state_changed_event_sender: None,
         }
     }
+
+    /// Sets the channel sender receiving the CCD impact events.
+    ///
+    /// Without this sender, CCD impact events are ignored by this collector.
+    pub fn with_ccd_impact_event_sender(
+        mut self,
+        ccd_impact_event_sender: Sender<CcdImpactEvent>,
+    ) -> Self {
+        self.ccd_impact_event_sender = Some(ccd_impact_event_sender);
+        self
+    }
+
+    /// Sets the channel sender receiving the joint broken events.
+    ///
+    /// Without this sender, joint broken events are ignored by this collector.
+    pub fn with_joint_broken_event_sender(
+        mut self,
+        joint_broken_event_sender: Sender<JointBrokenEvent>,
+    ) -> Self {
+        self.joint_broken_event_sender = Some(joint_broken_event_sender);
+        self
+    }
+
+    /// Sets the channel sender receiving the sleep state changed events.
+    ///
+    /// Without this sender, sleep state changed events are ignored by this collector.
+    pub fn with_sleep_state_changed_event_sender(
+        mut self,
+        sleep_state_changed_event_sender: Sender<SleepStateChangedEvent>,
+    ) -> Self {
+        self.sleep_state_changed_event_sender = Some(sleep_state_changed_event_sender);
+        self
+    }
 }
 
 impl EventHandler for ChannelEventCollector {
@@ -218,4 +307,37 @@ impl EventHandler for ChannelEventCollector {
         let result = ContactForceEvent::from_contact_pair(dt, contact_pair, total_force_magnitude);
         let _ = self.contact_force_event_sender.send(result);
     }
+
+    fn handle_ccd_impact_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        event: CcdImpactEvent,
+    ) {
+        if let Some(sender) = &self.ccd_impact_event_sender {
+            let _ = sender.send(event);
+        }
+    }
+
+    fn handle_joint_broken_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        event: JointBrokenEvent,
+    ) {
+        if let Some(sender) = &self.joint_broken_event_sender {
+            let _ = sender.send(event);
+        }
+    }
+
+    fn handle_sleep_state_changed_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        event: SleepStateChangedEvent,
+    ) {
+        if let Some(sender) = &self.sleep_state_changed_event_sender {
+            let _ = sender.send(event);
+        }
+    }
 }
@@ -0,0 +1,172 @@
+//! Force volumes applying forces to the rigid-bodies inside of a sensor collider.
+
+use crate::dynamics::{RigidBody, RigidBodyHandle, RigidBodySet};
+use crate::geometry::{ColliderHandle, ColliderSet, NarrowPhase};
+use crate::math::{Point, Real, Vector};
+use std::sync::Arc;
+
+/// How the vector computed by a [`ForceVolume`] is applied to the rigid-bodies.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub enum ForceVolumeMode {
+    /// The vector is a force, so heavier bodies are less affected by it.
+    #[default]
+    Force,
+    /// The vector is an acceleration, multiplied by the body’s mass to obtain the applied force.
+    ///
+    /// All the bodies are affected the same way, regardless of their mass.
+    Acceleration,
+}
+
+/// A function computing the force (or acceleration) applied by a [`ForceVolume`] to a rigid-body.
+///
+/// It is given the rigid-body and its world-space center of mass.
+pub type ForceVolumeFn = dyn Fn(&RigidBody, Point<Real>) -> Vector<Real> + Send + Sync;
+
+/// A region of space applying a force to every dynamic rigid-body inside of it.
+///
+/// The region is the shape of a sensor collider, registered with
+/// [`PhysicsPipeline::insert_force_volume`](crate::pipeline::PhysicsPipeline::insert_force_volume).
+/// At each timestep, the force is applied to every awake dynamic rigid-body with at least one
+/// collider intersecting the sensor, as detected by the narrow-phase. The contributions of
+/// overlapping volumes are summed.
+///
+/// This can model wind tunnels, water currents, or conveyor-like areas.
+///
+/// # Example
+/// ```
+/// # use rapier3d::prelude::*;
+/// # let mut colliders = ColliderSet::new();
+/// let mut pipeline = PhysicsPipeline::new();
+/// let tunnel = colliders.insert(ColliderBuilder::cuboid(10.0, 2.0, 2.0).sensor(true));
+/// pipeline.insert_force_volume(
+///     tunnel,
+///     ForceVolume::constant(ForceVolumeMode::Acceleration, vector![5.0, 0.0, 0.0]),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct ForceVolume {
+    /// How the computed vector is applied to the rigid-bodies.
+    pub mode: ForceVolumeMode,
+    function: Arc<ForceVolumeFn>,
+}
+
+impl ForceVolume {
+    /// A force volume applying the vector computed by `function` to each rigid-body inside of it.
+    pub fn new(
+        mode: ForceVolumeMode,
+        function: impl Fn(&RigidBody, Point<Real>) -> Vector<Real> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            mode,
+            function: Arc::new(function),
+        }
+    }
+
+    /// A force volume applying the same vector to all the rigid-bodies inside of it.
+    pub fn constant(mode: ForceVolumeMode, vector: Vector<Real>) -> Self {
+        Self::new(mode, move |_, _| vector)
+    }
+
+    /// The function computing the vector applied to each rigid-body inside of this volume.
+    pub fn function(&self) -> &ForceVolumeFn {
+        self.function.as_ref()
+    }
+
+    /// Applies the force of this volume, attached to `collider`, to the rigid-bodies inside of it.
+    ///
+    /// The force must be applied after the effective forces of the rigid-bodies are computed
+    /// for the current timestep. `workspace` is used to apply the force only once to bodies with
+    /// multiple colliders inside of the volume.
+    pub(crate) fn apply(
+        &self,
+        collider: ColliderHandle,
+        bodies: &mut RigidBodySet,
+        colliders: &ColliderSet,
+        narrow_phase: &NarrowPhase,
+        workspace: &mut Vec<RigidBodyHandle>,
+    ) {
+        workspace.clear();
+        workspace.extend(
+            narrow_phase
+                .intersection_pairs_with(collider)
+                .filter(|(_, _, intersecting)| *intersecting)
+                .filter_map(|(co1, co2, _)| {
+                    let other = crate::utils::select_other((co1, co2), collider);
+                    colliders.get(other)?.parent()
+                }),
+        );
+        workspace.sort_unstable_by_key(|handle| handle.into_raw_parts());
+        workspace.dedup();
+
+        for handle in workspace.iter() {
+            let rb = bodies.index_mut_internal(*handle);
+
+            if !rb.is_dynamic() || rb.is_sleeping() || !rb.is_enabled() {
+                continue;
+            }
+
+            let vector = (self.function)(rb, rb.mprops.world_com);
+            rb.forces.force += match self.mode {
+                ForceVolumeMode::Force => vector,
+                ForceVolumeMode::Acceleration => vector.component_mul(&rb.mprops.effective_mass()),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ForceVolume, ForceVolumeMode};
+    use crate::dynamics::{IntegrationParameters, RigidBodyBuilder};
+    use crate::geometry::ColliderBuilder;
+    use crate::math::Vector;
+    use crate::pipeline::{PhysicsPipeline, PhysicsState};
+
+    #[test]
+    fn force_volumes_push_bodies_inside() {
+        let mut state = PhysicsState::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let params = IntegrationParameters::default();
+
+        // Two overlapping volumes, only covering the first two bodies.
+        for _ in 0..2 {
+            #[cfg(feature = "dim2")]
+            let sensor = ColliderBuilder::cuboid(5.0, 1.0).sensor(true);
+            #[cfg(feature = "dim3")]
+            let sensor = ColliderBuilder::cuboid(5.0, 1.0, 5.0).sensor(true);
+            let sensor = state.colliders.insert(sensor);
+            pipeline.insert_force_volume(
+                sensor,
+                ForceVolume::constant(ForceVolumeMode::Acceleration, Vector::x()),
+            );
+        }
+
+        let handles: Vec<_> = [(0.0, 1.0), (2.0, 10.0), (20.0, 1.0)]
+            .into_iter()
+            .map(|(x, density)| {
+                let body = state
+                    .bodies
+                    .insert(RigidBodyBuilder::dynamic().translation(Vector::x() * x));
+                let collider = ColliderBuilder::ball(0.5).density(density);
+                state
+                    .colliders
+                    .insert_with_parent(collider, body, &mut state.bodies);
+                body
+            })
+            .collect();
+
+        for _ in 0..10 {
+            state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+        }
+
+        // The first two bodies accelerate at the sum of the volumes’ accelerations, regardless
+        // of their masses. The third one is out of the volumes.
+        let linvels: Vec<_> = handles.iter().map(|h| *state.bodies[*h].linvel()).collect();
+        assert!(linvels[0].x > 0.0);
+        assert!((linvels[0] - linvels[1]).norm() < 1.0e-5);
+        // NOTE: the volumes might not have been detected yet at the first step.
+        assert!(linvels[0].x >= 2.0 * 9.0 * params.dt - 1.0e-3);
+        assert!(linvels[0].x <= 2.0 * 10.0 * params.dt + 1.0e-3);
+        assert_eq!(linvels[2], Vector::zeros());
+    }
+}
@@ -2,9 +2,16 @@
 
 pub use collision_pipeline::CollisionPipeline;
 pub use event_handler::{ActiveEvents, ChannelEventCollector, EventHandler};
+pub use force_volume::{ForceVolume, ForceVolumeFn, ForceVolumeMode};
 pub use physics_hooks::{ActiveHooks, ContactModificationContext, PairFilterContext, PhysicsHooks};
-pub use physics_pipeline::PhysicsPipeline;
-pub use query_pipeline::{QueryFilter, QueryFilterFlags, QueryPipeline, QueryPipelineMut};
+pub use physics_pipeline::{
+    ColliderRemovalObserver, GravityField, OutOfBoundsObserver, PhysicsPipeline,
+};
+pub use physics_state::{PhysicsSnapshot, PhysicsState};
+pub use query_pipeline::{
+    QueryFilter, QueryFilterFlags, QueryPipeline, QueryPipelineMut, RayCastOptions,
+    ShapeCastPenetration,
+};
 
 #[cfg(feature = "debug-render")]
 pub use self::debug_render_pipeline::{
@@ -14,8 +21,10 @@ pub use self::debug_render_pipeline::{
 
 mod collision_pipeline;
 mod event_handler;
+mod force_volume;
 mod physics_hooks;
 mod physics_pipeline;
+mod physics_state;
 mod query_pipeline;
 mod user_changes;
 
@@ -6,18 +6,37 @@ use crate::dynamics::IslandSolver;
 #[cfg(feature = "parallel")]
 use crate::dynamics::JointGraphEdge;
 use crate::dynamics::{
-    CCDSolver, ImpulseJointSet, IntegrationParameters, IslandManager, MultibodyJointSet,
-    RigidBodyChanges, RigidBodyType,
+    CCDSolver, ImpulseJointHandle, ImpulseJointSet, IntegrationParameters, IslandManager,
+    JointBrokenEvent, MultibodyJointSet, PredictedImpacts, RigidBody, RigidBodyChanges,
+    RigidBodyHandle, RigidBodyType,
 };
 use crate::geometry::{
-    BroadPhaseBvh, BroadPhasePairEvent, ColliderChanges, ColliderHandle, ColliderPair,
-    ContactManifoldIndex, ModifiedColliders, NarrowPhase, TemporaryInteractionIndex,
+    Aabb, BoundingVolume, BroadPhaseBvh, BroadPhasePairEvent, ColliderChanges, ColliderHandle,
+    ColliderPair, ContactManifoldIndex, ModifiedColliders, NarrowPhase, TemporaryInteractionIndex,
 };
-use crate::math::{Real, Vector};
-use crate::pipeline::{EventHandler, PhysicsHooks};
+use crate::math::{Point, Real, Vector};
+use crate::pipeline::{EventHandler, ForceVolume, PhysicsHooks};
 use crate::prelude::ModifiedRigidBodies;
+use std::sync::Arc;
 use {crate::dynamics::RigidBodySet, crate::geometry::ColliderSet};
 
+/// A gravity field computing the gravity acceleration applied to a rigid-body.
+///
+/// It is given the rigid-body and its world-space center of mass. Returning `None` applies the
+/// uniform gravity given to [`PhysicsPipeline::step`] instead.
+pub type GravityField = dyn Fn(&RigidBody, Point<Real>) -> Option<Vector<Real>> + Send + Sync;
+
+/// A callback called once for each collider removed from the collider set.
+///
+/// See [`PhysicsPipeline::set_collider_removal_observer`].
+pub type ColliderRemovalObserver = dyn Fn(ColliderHandle) + Send + Sync;
+
+/// A callback called for each rigid-body leaving the world bounds.
+///
+/// It is given the rigid-body and the AABB of its colliders at its out-of-bounds position. See
+/// [`PhysicsPipeline::set_world_bounds`].
+pub type OutOfBoundsObserver = dyn Fn(RigidBodyHandle, Aabb) + Send + Sync;
+
 /// The main physics simulation engine that runs your physics world forward in time.
 ///
 /// Think of this as the "game loop" for your physics simulation. Each frame, you call
@@ -26,9 +45,10 @@ use {crate::dynamics::RigidBodySet, crate::geometry::ColliderSet};
 /// resolving contacts so objects don't overlap, and updating positions and velocities.
 ///
 /// ## Performance note
-/// This structure only contains temporary working memory (scratch buffers). You can create
-/// a new one anytime, but it's more efficient to reuse the same instance across frames
-/// since Rapier can reuse allocated memory.
+/// Apart from the settings configured on it (the gravity field, the force volumes, the world
+/// bounds and the observers), this structure only contains temporary working memory (scratch
+/// buffers). You can create a new one anytime, but it's more efficient to reuse the same
+/// instance across frames since Rapier can reuse allocated memory.
 ///
 /// ## How it works (simplified)
 /// Rapier uses a time-stepping approach where each step involves:
@@ -36,7 +56,8 @@ use {crate::dynamics::RigidBodySet, crate::geometry::ColliderSet};
 /// 2. **Constraint solving**: Calculate forces to prevent overlaps and enforce joint constraints
 /// 3. **Integration**: Update object positions and velocities based on forces and gravity
 /// 4. **Position correction**: Fix any remaining overlaps that might have occurred
-// NOTE: this contains only workspace data, so there is no point in making this serializable.
+// NOTE: apart from the user settings (some of which are closures), this contains only workspace
+//       data, so there is no point in making this serializable.
 pub struct PhysicsPipeline {
     /// Counters used for benchmarking only.
     pub counters: Counters,
@@ -45,7 +66,17 @@ pub struct PhysicsPipeline {
     joint_constraint_indices: Vec<Vec<ContactManifoldIndex>>,
     broadphase_collider_pairs: Vec<ColliderPair>,
     broad_phase_events: Vec<BroadPhasePairEvent>,
+    broken_joints: Vec<ImpulseJointHandle>,
     solvers: Vec<IslandSolver>,
+    gravity_field: Option<Arc<GravityField>>,
+    force_volumes: Vec<(ColliderHandle, ForceVolume)>,
+    force_volume_bodies: Vec<RigidBodyHandle>,
+    collider_removal_observer: Option<Arc<ColliderRemovalObserver>>,
+    ccd_clamped_bodies: Vec<RigidBodyHandle>,
+    ccd_teleported_colliders: Vec<ColliderHandle>,
+    world_bounds: Option<Aabb>,
+    disable_out_of_bounds_bodies: bool,
+    out_of_bounds_observer: Option<Arc<OutOfBoundsObserver>>,
 }
 
 impl Default for PhysicsPipeline {
@@ -74,9 +105,205 @@ impl PhysicsPipeline {
             joint_constraint_indices: vec![],
             broadphase_collider_pairs: vec![],
             broad_phase_events: vec![],
+            broken_joints: vec![],
+            gravity_field: None,
+            force_volumes: vec![],
+            force_volume_bodies: vec![],
+            collider_removal_observer: None,
+            ccd_clamped_bodies: vec![],
+            ccd_teleported_colliders: vec![],
+            world_bounds: None,
+            disable_out_of_bounds_bodies: false,
+            out_of_bounds_observer: None,
+        }
+    }
+
+    /// Sets a gravity field replacing the uniform gravity for the rigid-bodies it applies to.
+    ///
+    /// The field is evaluated once per timestep for each awake rigid-body. The returned
+    /// acceleration is multiplied by the body’s mass and [`RigidBody::gravity_scale`], just
+    /// like the uniform gravity. Return `None` to apply the uniform gravity given to
+    /// [`PhysicsPipeline::step`] (e.g. for bodies far from any attractor) at no additional cost.
+    ///
+    /// # Example
+    /// ```
+    /// # use rapier3d::prelude::*;
+    /// let mut pipeline = PhysicsPipeline::new();
+    /// // A planet of mass-parameter `GM` at the origin.
+    /// const GM: Real = 1000.0;
+    /// pipeline.set_gravity_field(|_, com| {
+    ///     let dist = com.coords.norm();
+    ///     (dist > 1.0e-3).then(|| -com.coords * (GM / (dist * dist * dist)))
+    /// });
+    /// ```
+    pub fn set_gravity_field(
+        &mut self,
+        field: impl Fn(&RigidBody, Point<Real>) -> Option<Vector<Real>> + Send + Sync + 'static,
+    ) {
+        self.gravity_field = Some(Arc::new(field));
+    }
+
+    /// Removes the gravity field set with [`PhysicsPipeline::set_gravity_field`], if any.
+    pub fn remove_gravity_field(&mut self) {
+        self.gravity_field = None;
+    }
+
+    /// The gravity field replacing the uniform gravity, if any.
+    pub fn gravity_field(&self) -> Option<&GravityField> {
+        self.gravity_field.as_deref()
+    }
+
+    /// Turns the sensor collider `collider` into a force volume.
+    ///
+    /// If `collider` was already a force volume, its previous force volume is replaced and
+    /// returned. Force volumes are applied in their insertion order. A force volume whose
+    /// collider was removed from the collider set is ignored until it is removed with
+    /// [`PhysicsPipeline::remove_force_volume`].
+    pub fn insert_force_volume(
+        &mut self,
+        collider: ColliderHandle,
+        volume: ForceVolume,
+    ) -> Option<ForceVolume> {
+        if let Some(existing) = self.force_volumes.iter_mut().find(|(h, _)| *h == collider) {
+            Some(std::mem::replace(&mut existing.1, volume))
+        } else {
+            self.force_volumes.push((collider, volume));
+            None
         }
     }
 
+    /// Removes the force volume attached to `collider`, if any.
+    pub fn remove_force_volume(&mut self, collider: ColliderHandle) -> Option<ForceVolume> {
+        let id = self
+            .force_volumes
+            .iter()
+            .position(|(h, _)| *h == collider)?;
+        Some(self.force_volumes.remove(id).1)
+    }
+
+    /// The force volumes, together with the sensor colliders they are attached to.
+    pub fn force_volumes(
+        &self,
+    ) -> impl ExactSizeIterator<Item = (ColliderHandle, &ForceVolume)> + '_ {
+        self.force_volumes.iter().map(|(h, volume)| (*h, volume))
+    }
+
+    /// Sets a callback called for each collider removed from the collider set.
+    ///
+    /// The callback is called during the next [`PhysicsPipeline::step`] following the removal,
+    /// once the removed colliders have been removed from the broad-phase and the narrow-phase.
+    /// It is called exactly once for each removed collider, including colliders removed
+    /// alongside their parent rigid-body. Unlike the collision events emitted for each contact
+    /// pair involving a removed collider, this is also called for colliders that weren’t
+    /// touching anything. Disabled colliders aren’t reported.
+    ///
+    /// This is useful for releasing external resources associated to the colliders.
+    ///
+    /// # Example
+    /// ```
+    /// # use rapier3d::prelude::*;
+    /// # use std::sync::mpsc::channel;
+    /// let mut pipeline = PhysicsPipeline::new();
+    /// let (removed_send, removed_recv) = channel();
+    /// pipeline.set_collider_removal_observer(move |handle| {
+    ///     let _ = removed_send.send(handle);
+    /// });
+    /// ```
+    pub fn set_collider_removal_observer(
+        &mut self,
+        observer: impl Fn(ColliderHandle) + Send + Sync + 'static,
+    ) {
+        self.collider_removal_observer = Some(Arc::new(observer));
+    }
+
+    /// Removes the callback set with [`PhysicsPipeline::set_collider_removal_observer`], if any.
+    pub fn remove_collider_removal_observer(&mut self) {
+        self.collider_removal_observer = None;
+    }
+
+    /// The callback called for each removed collider, if any.
+    pub fn collider_removal_observer(&self) -> Option<&ColliderRemovalObserver> {
+        self.collider_removal_observer.as_deref()
+    }
+
+    /// Sets the region of space the rigid-bodies are expected to remain in.
+    ///
+    /// At the end of each timestep, every awake non-fixed rigid-body whose colliders’ AABB (or
+    /// position, if it has no collider) isn’t contained in `bounds` is reported to the callback
+    /// set with [`PhysicsPipeline::set_out_of_bounds_observer`]. The AABBs used are the ones
+    /// stored in the broad-phase, i.e., enlarged by the prediction distance. Bodies with
+    /// non-finite positions (e.g. after a NaN velocity) are always considered out of bounds.
+    ///
+    /// This gives a chance to recover from bodies flying away (e.g. because of an explosion or
+    /// numerical instabilities) before their huge AABBs degrade the broad-phase. See
+    /// [`PhysicsPipeline::set_disable_out_of_bounds_bodies`] to disable these bodies
+    /// automatically.
+    ///
+    /// # Example
+    /// ```
+    /// # use rapier3d::prelude::*;
+    /// let mut pipeline = PhysicsPipeline::new();
+    /// let half_extent = 1.0e4;
+    /// pipeline.set_world_bounds(Aabb::from_half_extents(
+    ///     Point::origin(),
+    ///     Vector::repeat(half_extent),
+    /// ));
+    /// pipeline.set_disable_out_of_bounds_bodies(true);
+    /// pipeline.set_out_of_bounds_observer(|handle, aabb| {
+    ///     println!("Body {:?} left the world with the AABB {:?}.", handle, aabb);
+    /// });
+    /// ```
+    pub fn set_world_bounds(&mut self, bounds: Aabb) {
+        self.world_bounds = Some(bounds);
+    }
+
+    /// Removes the world bounds set with [`PhysicsPipeline::set_world_bounds`], if any.
+    pub fn remove_world_bounds(&mut self) {
+        self.world_bounds = None;
+    }
+
+    /// The region of space the rigid-bodies are expected to remain in, if any.
+    pub fn world_bounds(&self) -> Option<&Aabb> {
+        self.world_bounds.as_ref()
+    }
+
+    /// Sets whether the rigid-bodies leaving the world bounds are disabled automatically
+    /// (default: `false`).
+    ///
+    /// If `true`, a body out of the world bounds at the end of a timestep is disabled with
+    /// [`RigidBody::set_enabled`]. Its colliders are then removed from the broad-phase and
+    /// narrow-phase at the beginning of the next timestep. Its position and velocities are left
+    /// untouched: they should be fixed before enabling it again. If `false`, out-of-bounds bodies
+    /// are only reported, at each timestep they spend out of bounds.
+    pub fn set_disable_out_of_bounds_bodies(&mut self, disable: bool) {
+        self.disable_out_of_bounds_bodies = disable;
+    }
+
+    /// Are the rigid-bodies leaving the world bounds disabled automatically?
+    pub fn disable_out_of_bounds_bodies(&self) -> bool {
+        self.disable_out_of_bounds_bodies
+    }
+
+    /// Sets a callback called for each rigid-body leaving the world bounds.
+    ///
+    /// See [`PhysicsPipeline::set_world_bounds`].
+    pub fn set_out_of_bounds_observer(
+        &mut self,
+        observer: impl Fn(RigidBodyHandle, Aabb) + Send + Sync + 'static,
+    ) {
+        self.out_of_bounds_observer = Some(Arc::new(observer));
+    }
+
+    /// Removes the callback set with [`PhysicsPipeline::set_out_of_bounds_observer`], if any.
+    pub fn remove_out_of_bounds_observer(&mut self) {
+        self.out_of_bounds_observer = None;
+    }
+
+    /// The callback called for each rigid-body leaving the world bounds, if any.
+    pub fn out_of_bounds_observer(&self) -> Option<&OutOfBoundsObserver> {
+        self.out_of_bounds_observer.as_deref()
+    }
+
     fn clear_modified_colliders(
         &mut self,
         colliders: &mut ColliderSet,
@@ -170,7 +397,13 @@ impl PhysicsPipeline {
             hooks,
             events,
         );
-        narrow_phase.compute_intersections(bodies, colliders, hooks, events);
+        narrow_phase.compute_intersections(
+            integration_parameters.dt,
+            bodies,
+            colliders,
+            hooks,
+            events,
+        );
 
         self.counters.cd.narrow_phase_time.pause();
         self.counters.stages.collision_detection_time.pause();
@@ -200,6 +433,10 @@ impl PhysicsPipeline {
             integration_parameters.min_island_size,
         );
 
+        for event in islands.sleep_events.drain(..) {
+            events.handle_sleep_state_changed_event(bodies, colliders, event);
+        }
+
         if self.manifold_indices.len() < islands.num_islands() {
             self.manifold_indices
                 .resize(islands.num_islands(), Vec::new());
@@ -233,8 +470,26 @@ impl PhysicsPipeline {
             rb.mprops
                 .update_world_mass_properties(rb.body_type, &rb.pos.position);
             let effective_mass = rb.mprops.effective_mass();
-            rb.forces
-                .compute_effective_force_and_torque(gravity, &effective_mass);
+            let body_gravity = self
+                .gravity_field
+                .as_ref()
+                .and_then(|field| field(rb, rb.mprops.world_com));
+            rb.forces.compute_effective_force_and_torque(
+                body_gravity.as_ref().unwrap_or(gravity),
+                &effective_mass,
+            );
+        }
+
+        for (collider, volume) in &self.force_volumes {
+            if colliders.contains(*collider) {
+                volume.apply(
+                    *collider,
+                    bodies,
+                    colliders,
+                    narrow_phase,
+                    &mut self.force_volume_bodies,
+                );
+            }
         }
         self.counters.stages.update_time.pause();
 
@@ -324,11 +579,17 @@ impl PhysicsPipeline {
             let pair = narrow_phase.contact_pair_at_index(pair_id);
             let co1 = &colliders[pair.collider1];
             let co2 = &colliders[pair.collider2];
-            let threshold = co1
-                .effective_contact_force_event_threshold()
-                .min(co2.effective_contact_force_event_threshold());
+            // NOTE: a collider without contact force events enabled doesn’t take part in
+            //       the choice of the threshold.
+            let threshold = match (
+                co1.effective_contact_force_event_threshold(),
+                co2.effective_contact_force_event_threshold(),
+            ) {
+                (Some(t1), Some(t2)) => Some(t1.max(t2)),
+                (t1, t2) => t1.or(t2),
+            };
 
-            if threshold < Real::MAX {
+            if let Some(threshold) = threshold {
                 let total_magnitude = pair.total_impulse_magnitude() * inv_dt;
 
                 // NOTE: the strict inequality is important here, so we don’t
@@ -345,6 +606,28 @@ impl PhysicsPipeline {
             }
         }
 
+        // Compute the joint forces, and find the joints that need to break. Broken joints are
+        // removed at the end of the timestep.
+        // NOTE: the impulses written back by the solver are the ones applied during the last
+        //       solver substep, so they must be converted to forces using the substep length.
+        let joints = impulse_joints.joints_mut();
+        for (island_id, island_joints) in self.joint_constraint_indices[..islands.num_islands()]
+            .iter()
+            .enumerate()
+        {
+            let num_solver_iterations = integration_parameters.num_solver_iterations
+                + islands.active_island_additional_solver_iterations(island_id);
+            let substep_inv_dt = inv_dt * num_solver_iterations as Real;
+
+            for joint_id in island_joints {
+                let joint = &mut joints[*joint_id].weight;
+                joint.update_forces(substep_inv_dt, bodies);
+                if joint.exceeds_breaking_thresholds() {
+                    self.broken_joints.push(joint.handle);
+                }
+            }
+        }
+
         self.counters.stages.solver_time.pause();
     }
 
@@ -358,7 +641,7 @@ impl PhysicsPipeline {
         narrow_phase: &NarrowPhase,
         ccd_solver: &mut CCDSolver,
         events: &dyn EventHandler,
-    ) {
+    ) -> usize {
         self.counters.ccd.toi_computation_time.start();
         // Handle CCD
         let impacts = ccd_solver.predict_impacts_at_next_positions(
@@ -371,7 +654,13 @@ impl PhysicsPipeline {
             events,
         );
         ccd_solver.clamp_motions(integration_parameters.dt, bodies, &impacts);
+        if let PredictedImpacts::Impacts(tois) = &impacts {
+            self.ccd_clamped_bodies.extend(tois.keys().copied());
+        }
+        self.counters.ccd.num_toi_computations = ccd_solver.num_toi_computations;
+        self.counters.ccd.num_toi_budget_exceeded = ccd_solver.num_toi_budget_exceeded;
         self.counters.ccd.toi_computation_time.pause();
+        ccd_solver.num_impacts
     }
 
     fn advance_to_final_positions(
@@ -390,6 +679,54 @@ impl PhysicsPipeline {
         }
     }
 
+    /// Reports, and optionally disables, the active rigid-bodies that left the world bounds.
+    ///
+    /// This runs once per timestep, after the CCD, and relies on the collider AABBs stored in the
+    /// broad-phase. The bodies are disabled through the modification-tracking accessor, so their
+    /// colliders are removed from the broad-phase and narrow-phase by the user-changes handling of
+    /// the next timestep.
+    fn handle_out_of_bounds_bodies(
+        &self,
+        islands: &IslandManager,
+        broad_phase: &BroadPhaseBvh,
+        bodies: &mut RigidBodySet,
+    ) {
+        let Some(bounds) = self.world_bounds else {
+            return;
+        };
+
+        for handle in islands.active_bodies() {
+            let rb = &bodies[*handle];
+            let aabb = if rb.colliders().is_empty() {
+                let center = Point::from(rb.pos.position.translation.vector);
+                Aabb::new(center, center)
+            } else {
+                let mut aabb = Aabb::new_invalid();
+                for co_aabb in rb
+                    .colliders()
+                    .iter()
+                    .filter_map(|co_handle| broad_phase.collider_aabb(*co_handle))
+                {
+                    aabb.merge(&co_aabb);
+                }
+                aabb
+            };
+
+            // NOTE: `contains` is false if the AABB has NaN components.
+            if bounds.contains(&aabb) {
+                continue;
+            }
+
+            if let Some(observer) = &self.out_of_bounds_observer {
+                observer(*handle, aabb);
+            }
+
+            if self.disable_out_of_bounds_bodies {
+                bodies[*handle].set_enabled(false);
+            }
+        }
+    }
+
     fn interpolate_kinematic_velocities(
         &mut self,
         integration_parameters: &IntegrationParameters,
@@ -407,6 +744,10 @@ impl PhysicsPipeline {
 
             match rb.body_type {
                 RigidBodyType::KinematicPositionBased => {
+                    if let Some(path) = &mut rb.kinematic_path {
+                        rb.pos.next_position = path.advance(integration_parameters.dt);
+                    }
+
                     rb.vels = rb.pos.interpolate_velocity(
                         integration_parameters.inv_dt(),
                         &rb.mprops.local_mprops.local_com,
@@ -505,6 +846,7 @@ impl PhysicsPipeline {
         // Apply modifications.
         let mut modified_colliders = colliders.take_modified();
         let mut removed_colliders = colliders.take_removed();
+        let num_actually_removed = removed_colliders.len();
 
         super::user_changes::handle_user_changes_to_colliders(
             bodies,
@@ -532,6 +874,20 @@ impl PhysicsPipeline {
                 .copied()
                 .filter(|h| colliders.get(*h).map(|c| !c.is_enabled()).unwrap_or(false)),
         );
+
+        // Colliders teleported relative to their parent are ignored by the CCD during this
+        // timestep only.
+        for handle in self.ccd_teleported_colliders.drain(..) {
+            if let Some(co) = colliders.get_mut_internal(handle) {
+                co.ccd_teleported = false;
+            }
+        }
+        self.ccd_teleported_colliders.extend(
+            modified_colliders
+                .iter()
+                .copied()
+                .filter(|h| colliders.get(*h).is_some_and(|co| co.ccd_teleported)),
+        );
         self.counters.stages.user_changes.pause();
 
         // TODO: do this only on user-change.
@@ -559,6 +915,13 @@ impl PhysicsPipeline {
             true,
         );
 
+        // The removed colliders are now gone from the broad-phase and narrow-phase.
+        if let Some(observer) = &self.collider_removal_observer {
+            for handle in &removed_colliders[..num_actually_removed] {
+                observer(*handle);
+            }
+        }
+
         self.counters.stages.user_changes.resume();
         self.clear_modified_colliders(colliders, &mut modified_colliders);
         self.clear_modified_bodies(bodies, &mut modified_bodies);
@@ -567,6 +930,16 @@ impl PhysicsPipeline {
 
         let mut remaining_time = integration_parameters.dt;
         let mut integration_parameters = *integration_parameters;
+        ccd_solver.reset_step_counters();
+        for handle in self.ccd_clamped_bodies.drain(..) {
+            if let Some(rb) = bodies.get_mut_internal(handle) {
+                rb.ccd.ccd_clamped = false;
+            }
+        }
+
+        if cfg!(debug_assertions) {
+            ccd_solver.prune_tunneling_warnings(bodies);
+        }
 
         let (ccd_is_enabled, mut remaining_substeps) =
             if integration_parameters.max_ccd_substeps == 0 {
@@ -586,6 +959,9 @@ impl PhysicsPipeline {
             //
             // If there is only one or zero CCD substep, there is no need
             // to split the timestep interval. So we can just skip this part.
+            // Is this the last substep the CCD is allowed to run?
+            let mut last_ccd_substep = false;
+
             if ccd_is_enabled && remaining_substeps > 1 {
                 // NOTE: Take forces into account when updating the bodies CCD activation flags
                 //       these forces have not been integrated to the body's velocity yet.
@@ -604,6 +980,8 @@ impl PhysicsPipeline {
                 } else {
                     None
                 };
+                self.counters.ccd.num_toi_computations = ccd_solver.num_toi_computations;
+                self.counters.ccd.num_toi_budget_exceeded = ccd_solver.num_toi_budget_exceeded;
 
                 if let Some(toi) = first_impact {
                     let original_interval = remaining_time / (remaining_substeps as Real);
@@ -630,6 +1008,9 @@ impl PhysicsPipeline {
                     remaining_substeps = 0;
                 }
             } else {
+                // Only report the substeps exhausted by previous impacts, not the case where
+                // the CCD isn’t allowed to split the timestep at all.
+                last_ccd_substep = ccd_is_enabled && integration_parameters.max_ccd_substeps > 1;
                 integration_parameters.dt = remaining_time;
                 remaining_time = 0.0;
                 remaining_substeps = 0;
@@ -650,6 +1031,15 @@ impl PhysicsPipeline {
                 events,
             );
 
+            if cfg!(debug_assertions) {
+                ccd_solver.warn_about_tunneling_risks(
+                    islands,
+                    bodies,
+                    integration_parameters.dt,
+                    ccd_is_enabled,
+                );
+            }
+
             // If CCD is enabled, execute the CCD motion clamping.
             if ccd_is_enabled {
                 // NOTE: don't the forces into account when updating the CCD active flags because
@@ -661,7 +1051,7 @@ impl PhysicsPipeline {
                     false,
                 );
                 if ccd_active {
-                    self.run_ccd_motion_clamping(
+                    let num_impacts = self.run_ccd_motion_clamping(
                         &integration_parameters,
                         islands,
                         bodies,
@@ -671,6 +1061,12 @@ impl PhysicsPipeline {
                         ccd_solver,
                         events,
                     );
+
+                    // The bodies stopped by these impacts lose the rest of their motion since
+                    // there is no substep left to simulate it.
+                    if last_ccd_substep {
+                        self.counters.ccd.num_impacts_after_last_substep += num_impacts;
+                    }
                 }
             }
 
@@ -709,6 +1105,21 @@ impl PhysicsPipeline {
             }
         }
 
+        self.handle_out_of_bounds_bodies(islands, broad_phase, bodies);
+
+        // Remove the joints that broke during this timestep.
+        for handle in self.broken_joints.drain(..) {
+            // NOTE: the same joint may have been marked as broken by multiple CCD substeps.
+            if let Some(joint) = impulse_joints.remove(handle, true) {
+                let event = JointBrokenEvent {
+                    handle,
+                    body1: joint.body1,
+                    body2: joint.body2,
+                };
+                events.handle_joint_broken_event(bodies, colliders, event);
+            }
+        }
+
         // Finally, make sure we update the world mass-properties of the rigid-bodies
         // that moved. Otherwise, users may end up applying forces with respect to an
         // outdated center of mass.
@@ -738,11 +1149,35 @@ mod test {
         CCDSolver, ImpulseJointSet, IntegrationParameters, IslandManager, RigidBodyBuilder,
         RigidBodySet,
     };
-    use crate::geometry::{BroadPhaseBvh, ColliderBuilder, ColliderSet, NarrowPhase};
-    use crate::math::Vector;
-    use crate::pipeline::PhysicsPipeline;
+    use crate::geometry::{
+        BroadPhaseBvh, ColliderBuilder, ColliderHandle, ColliderSet, Cuboid, NarrowPhase,
+        SharedShape,
+    };
+    use crate::math::{Real, Vector};
+    use crate::pipeline::{PhysicsPipeline, PhysicsState};
     use crate::prelude::{MultibodyJointSet, RevoluteJointBuilder, RigidBodyType};
 
+    /// An empty physics state and the pipeline stepping it.
+    fn scene() -> (PhysicsState, PhysicsPipeline) {
+        (PhysicsState::new(), PhysicsPipeline::new())
+    }
+
+    /// A cuboid collider with the given half-extents. `_hz` is ignored in 2D.
+    fn cuboid(hx: Real, hy: Real, _hz: Real) -> ColliderBuilder {
+        #[cfg(feature = "dim2")]
+        let half_extents = Vector::new(hx, hy);
+        #[cfg(feature = "dim3")]
+        let half_extents = Vector::new(hx, hy, _hz);
+        ColliderBuilder::new(SharedShape::new(Cuboid::new(half_extents)))
+    }
+
+    /// Inserts a fixed ground collider, 20 units wide, with its top face at `y = 0`.
+    fn insert_ground(state: &mut PhysicsState) -> ColliderHandle {
+        state
+            .colliders
+            .insert(cuboid(10.0, 0.5, 10.0).translation(Vector::y() * -0.5))
+    }
+
     #[test]
     fn kinematic_and_fixed_contact_crash() {
         let mut colliders = ColliderSet::new();
@@ -1071,88 +1506,661 @@ mod test {
     }
 
     #[test]
-    #[cfg(feature = "dim2")]
-    fn test_multi_sap_disable_body() {
-        use na::vector;
-        let mut rigid_body_set = RigidBodySet::new();
-        let mut collider_set = ColliderSet::new();
-
-        /* Create the ground. */
-        let collider = ColliderBuilder::cuboid(100.0, 0.1).build();
-        collider_set.insert(collider);
-
-        /* Create the bouncing ball. */
-        let rigid_body = RigidBodyBuilder::dynamic()
-            .translation(vector![0.0, 10.0])
-            .build();
-        let collider = ColliderBuilder::ball(0.5).restitution(0.7).build();
-        let ball_body_handle = rigid_body_set.insert(rigid_body);
-        collider_set.insert_with_parent(collider, ball_body_handle, &mut rigid_body_set);
-
-        /* Create other structures necessary for the simulation. */
-        let gravity = vector![0.0, -9.81];
-        let integration_parameters = IntegrationParameters::default();
-        let mut physics_pipeline = PhysicsPipeline::new();
-        let mut island_manager = IslandManager::new();
-        let mut broad_phase = BroadPhaseBvh::new();
-        let mut narrow_phase = NarrowPhase::new();
-        let mut impulse_joint_set = ImpulseJointSet::new();
-        let mut multibody_joint_set = MultibodyJointSet::new();
-        let mut ccd_solver = CCDSolver::new();
-        let physics_hooks = ();
-        let event_handler = ();
-
-        physics_pipeline.step(
-            &gravity,
-            &integration_parameters,
-            &mut island_manager,
-            &mut broad_phase,
-            &mut narrow_phase,
-            &mut rigid_body_set,
-            &mut collider_set,
-            &mut impulse_joint_set,
-            &mut multibody_joint_set,
-            &mut ccd_solver,
-            &physics_hooks,
-            &event_handler,
-        );
+    fn joint_force_matches_supported_weight() {
+        use crate::prelude::PrismaticJointBuilder;
 
-        // Test RigidBodyChanges::POSITION and disable
-        {
-            let ball_body = &mut rigid_body_set[ball_body_handle];
+        let mut colliders = ColliderSet::new();
+        let mut impulse_joints = ImpulseJointSet::new();
+        let mut multibody_joints = MultibodyJointSet::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let mut bf = BroadPhaseBvh::new();
+        let mut nf = NarrowPhase::new();
+        let mut islands = IslandManager::new();
+        let mut bodies = RigidBodySet::new();
 
-            // Also, change the translation and rotation to different values
-            ball_body.set_translation(vector![1.0, 1.0], true);
-            ball_body.set_rotation(nalgebra::UnitComplex::new(1.0), true);
+        // A body of mass 2.0 sliding along a horizontal prismatic joint. The joint must support
+        // its whole weight.
+        let fixed = bodies.insert(RigidBodyBuilder::fixed());
+        let rb = RigidBodyBuilder::dynamic().additional_mass(2.0).build();
+        let h_dynamic = bodies.insert(rb);
+        let joint = PrismaticJointBuilder::new(Vector::x_axis());
+        let joint_handle = impulse_joints.insert(fixed, h_dynamic, joint, true);
 
-            ball_body.set_enabled(false);
+        let gravity = Vector::y() * -9.81;
+        for _ in 0..10 {
+            pipeline.step(
+                &gravity,
+                &IntegrationParameters::default(),
+                &mut islands,
+                &mut bf,
+                &mut nf,
+                &mut bodies,
+                &mut colliders,
+                &mut impulse_joints,
+                &mut multibody_joints,
+                &mut CCDSolver::new(),
+                &(),
+                &(),
+            );
         }
 
-        physics_pipeline.step(
-            &gravity,
-            &integration_parameters,
-            &mut island_manager,
-            &mut broad_phase,
-            &mut narrow_phase,
-            &mut rigid_body_set,
-            &mut collider_set,
-            &mut impulse_joint_set,
-            &mut multibody_joint_set,
-            &mut ccd_solver,
-            &physics_hooks,
-            &event_handler,
-        );
+        let weight = 2.0 * 9.81;
+        let force = impulse_joints.get(joint_handle).unwrap().force();
+        assert!((force.norm() - weight).abs() < weight * 1.0e-2);
+        assert!(force.x.abs() < weight * 1.0e-2);
+    }
 
-        // Test RigidBodyChanges::POSITION and enable
-        {
-            let ball_body = &mut rigid_body_set[ball_body_handle];
+    #[test]
+    #[cfg(feature = "dim3")]
+    fn locked_rotation_axis_only_allows_rotations_along_axis() {
+        let mut colliders = ColliderSet::new();
+        let mut impulse_joints = ImpulseJointSet::new();
+        let mut multibody_joints = MultibodyJointSet::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let mut bf = BroadPhaseBvh::new();
+        let mut nf = NarrowPhase::new();
+        let mut islands = IslandManager::new();
+        let mut bodies = RigidBodySet::new();
 
-            // Also, change the translation and rotation to different values
-            ball_body.set_translation(vector![0.0, 0.0], true);
-            ball_body.set_rotation(nalgebra::UnitComplex::new(0.0), true);
+        let rb = RigidBodyBuilder::dynamic()
+            .angvel(Vector::new(1.0, 2.0, 3.0))
+            .build();
+        let handle = bodies.insert(rb);
+        let co = ColliderBuilder::cuboid(1.0, 0.5, 0.25).build();
+        colliders.insert_with_parent(co, handle, &mut bodies);
 
-            ball_body.set_enabled(true);
-        }
+        let axis = na::Unit::new_normalize(Vector::new(0.0, 1.0, 1.0));
+        bodies[handle].set_locked_rotation_axis(Some(axis), true);
+        // An off-axis torque must only affect the rotation along the axis.
+        bodies[handle].apply_torque_impulse(Vector::new(5.0, 1.0, 0.0), true);
+
+        for _ in 0..10 {
+            pipeline.step(
+                &Vector::zeros(),
+                &IntegrationParameters::default(),
+                &mut islands,
+                &mut bf,
+                &mut nf,
+                &mut bodies,
+                &mut colliders,
+                &mut impulse_joints,
+                &mut multibody_joints,
+                &mut CCDSolver::new(),
+                &(),
+                &(),
+            );
+
+            let angvel = *bodies[handle].angvel();
+            assert!(angvel.cross(&axis).norm() < 1.0e-5);
+            assert!(angvel.norm() > 0.0);
+        }
+    }
+
+    #[test]
+    fn spinning_body_conserves_kinetic_energy() {
+        let mut colliders = ColliderSet::new();
+        let mut impulse_joints = ImpulseJointSet::new();
+        let mut multibody_joints = MultibodyJointSet::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let mut bf = BroadPhaseBvh::new();
+        let mut nf = NarrowPhase::new();
+        let mut islands = IslandManager::new();
+        let mut bodies = RigidBodySet::new();
+
+        #[cfg(feature = "dim2")]
+        let angvel = 3.0;
+        #[cfg(feature = "dim3")]
+        let angvel = Vector::new(1.0, 2.0, 3.0);
+        let rb = RigidBodyBuilder::dynamic()
+            .linvel(Vector::x() * 2.0)
+            .angvel(angvel)
+            .build();
+        let handle = bodies.insert(rb);
+        colliders.insert_with_parent(ColliderBuilder::ball(0.5), handle, &mut bodies);
+
+        let initial_energy = bodies[handle].kinetic_energy();
+        assert!(initial_energy > 0.0);
+        assert_eq!(bodies.total_kinetic_energy(), initial_energy);
+
+        for _ in 0..100 {
+            pipeline.step(
+                &Vector::zeros(),
+                &IntegrationParameters::default(),
+                &mut islands,
+                &mut bf,
+                &mut nf,
+                &mut bodies,
+                &mut colliders,
+                &mut impulse_joints,
+                &mut multibody_joints,
+                &mut CCDSolver::new(),
+                &(),
+                &(),
+            );
+
+            let energy = bodies.total_kinetic_energy();
+            assert!((energy - initial_energy).abs() < initial_energy * 1.0e-4);
+        }
+    }
+
+    #[test]
+    fn joint_breaks_above_breaking_force() {
+        use crate::dynamics::GenericJoint;
+        use crate::pipeline::ChannelEventCollector;
+        use std::sync::mpsc::channel;
+
+        let mut colliders = ColliderSet::new();
+        let mut impulse_joints = ImpulseJointSet::new();
+        let mut multibody_joints = MultibodyJointSet::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let mut bf = BroadPhaseBvh::new();
+        let mut nf = NarrowPhase::new();
+        let mut islands = IslandManager::new();
+        let mut bodies = RigidBodySet::new();
+
+        let (collision_send, _) = channel();
+        let (contact_force_send, _) = channel();
+        let (joint_broken_send, joint_broken_recv) = channel();
+        let events = ChannelEventCollector::new(collision_send, contact_force_send)
+            .with_joint_broken_event_sender(joint_broken_send);
+
+        // Two bodies of mass 1.0 hanging from a fixed body. The weight they need to be
+        // supported against is ~9.81N, which exceeds only the first joint’s threshold.
+        let fixed = bodies.insert(RigidBodyBuilder::fixed());
+        let mut joint_handles = vec![];
+
+        for breaking_force in [1.0, 100.0] {
+            let rb = RigidBodyBuilder::dynamic().additional_mass(1.0).build();
+            let h_dynamic = bodies.insert(rb);
+
+            #[cfg(feature = "dim2")]
+            let joint = RevoluteJointBuilder::new().local_anchor2(point![0.0, 1.0]);
+            #[cfg(feature = "dim3")]
+            let joint =
+                RevoluteJointBuilder::new(Vector::z_axis()).local_anchor2(point![0.0, 1.0, 0.0]);
+            let mut joint: GenericJoint = joint.into();
+            joint.set_breaking_force(Some(breaking_force));
+            joint_handles.push(impulse_joints.insert(fixed, h_dynamic, joint, true));
+        }
+
+        let gravity = Vector::y() * -9.81;
+        for _ in 0..2 {
+            pipeline.step(
+                &gravity,
+                &IntegrationParameters::default(),
+                &mut islands,
+                &mut bf,
+                &mut nf,
+                &mut bodies,
+                &mut colliders,
+                &mut impulse_joints,
+                &mut multibody_joints,
+                &mut CCDSolver::new(),
+                &(),
+                &events,
+            );
+        }
+
+        assert!(!impulse_joints.contains(joint_handles[0]));
+        assert!(impulse_joints.contains(joint_handles[1]));
+
+        let event = joint_broken_recv.try_recv().unwrap();
+        assert_eq!(event.handle, joint_handles[0]);
+        assert_eq!(event.body1, fixed);
+        assert!(joint_broken_recv.try_recv().is_err());
+    }
+
+    #[test]
+    fn joint_frame_changes_keep_impulses_unless_rotated() {
+        use crate::dynamics::PrismaticJointBuilder;
+        use crate::math::UnitVector;
+
+        let (mut state, mut pipeline) = scene();
+        let params = IntegrationParameters::default();
+
+        // A body sliding along a horizontal prismatic joint, supported against gravity.
+        let fixed = state.bodies.insert(RigidBodyBuilder::fixed());
+        let rb = RigidBodyBuilder::dynamic().additional_mass(2.0);
+        let body = state.bodies.insert(rb);
+        let joint = PrismaticJointBuilder::new(Vector::x_axis());
+        let handle = state.impulse_joints.insert(fixed, body, joint, true);
+
+        let gravity = Vector::y() * -9.81;
+        for _ in 0..5 {
+            state.step(&mut pipeline, &gravity, &params, &(), &());
+        }
+
+        let joint = state.impulse_joints.get_mut(handle, true).unwrap();
+        let impulses = joint.impulses;
+        assert!(impulses.norm() > 0.0);
+
+        // Moving the anchors or slightly tilting the axis keeps the impulses.
+        let mut frame = joint.data.local_frame2;
+        frame.translation.vector += Vector::x() * 0.5;
+        joint.set_local_frame2(frame);
+        joint.set_local_axis1(UnitVector::new_normalize(Vector::x() + Vector::y() * 0.01));
+        assert_eq!(joint.impulses, impulses);
+
+        // A large rotation makes them meaningless.
+        joint.set_local_axis1(Vector::y_axis());
+        assert_eq!(joint.impulses.norm(), 0.0);
+
+        // The joint keeps working with its new frames.
+        state.step(&mut pipeline, &gravity, &params, &(), &());
+        assert!(state.impulse_joints.get(handle).unwrap().impulses.norm() > 0.0);
+    }
+
+    #[test]
+    fn compliant_joints_stretch_proportionally_to_their_load() {
+        use crate::dynamics::{FixedJointBuilder, GenericJoint, RopeJointBuilder};
+        use crate::math::{Point, Real};
+
+        let (mut state, mut pipeline) = scene();
+        let params = IntegrationParameters::default();
+        let compliance = 1.0e-3;
+        let mass = 2.0;
+
+        // Bodies hanging one meter below fixed bodies, from a distance joint and a fixed joint.
+        let joints: [GenericJoint; 2] = [
+            RopeJointBuilder::new(1.0)
+                .min_distance(1.0)
+                .compliance(compliance)
+                .into(),
+            FixedJointBuilder::new()
+                .local_anchor1(Point::from(-Vector::y()))
+                .compliance(compliance)
+                .into(),
+        ];
+        let handles: Vec<_> = joints
+            .into_iter()
+            .enumerate()
+            .map(|(i, joint)| {
+                let offset = Vector::x() * (i as Real * 10.0);
+                let fixed = RigidBodyBuilder::fixed().translation(offset);
+                let fixed = state.bodies.insert(fixed);
+                let rb = RigidBodyBuilder::dynamic()
+                    .translation(offset - Vector::y())
+                    .additional_mass(mass);
+                let body = state.bodies.insert(rb);
+                state.impulse_joints.insert(fixed, body, joint, true);
+                body
+            })
+            .collect();
+
+        let gravity = Vector::y() * -9.81;
+        for _ in 0..200 {
+            state.step(&mut pipeline, &gravity, &params, &(), &());
+        }
+
+        let expected = compliance * mass * 9.81;
+        for handle in handles {
+            let extension = -state.bodies[handle].translation().y - 1.0;
+            assert!((extension - expected).abs() < expected * 1.0e-2);
+        }
+    }
+
+    #[test]
+    fn wake_up_and_rejoin_merges_sleeping_islands() {
+        use crate::dynamics::FixedJointBuilder;
+
+        let (mut state, mut pipeline) = scene();
+        let params = IntegrationParameters::default();
+
+        let handles: Vec<_> = [0.0, 2.0, 4.0]
+            .into_iter()
+            .map(|x| {
+                state.bodies.insert(
+                    RigidBodyBuilder::dynamic()
+                        .translation(Vector::x() * x)
+                        .sleeping(true),
+                )
+            })
+            .collect();
+
+        // Connect the sleeping bodies without waking them up.
+        state
+            .impulse_joints
+            .insert(handles[1], handles[2], FixedJointBuilder::new(), false);
+        state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+        assert!(handles.iter().all(|h| state.bodies[*h].is_sleeping()));
+
+        state
+            .impulse_joints
+            .insert(handles[0], handles[1], FixedJointBuilder::new(), false);
+        state.islands.wake_up_and_rejoin(
+            &mut state.bodies,
+            &state.colliders,
+            &state.narrow_phase,
+            &state.impulse_joints,
+            &state.multibody_joints,
+            handles[0],
+        );
+        assert!(handles.iter().all(|h| !state.bodies[*h].is_sleeping()));
+
+        state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+        assert!(handles.iter().all(|h| !state.bodies[*h].is_sleeping()));
+        assert_eq!(state.islands.active_bodies().len(), 3);
+    }
+
+    #[test]
+    fn islands_group_interacting_bodies() {
+        use crate::dynamics::FixedJointBuilder;
+
+        let (mut state, mut pipeline) = scene();
+        let params = IntegrationParameters {
+            min_island_size: 1,
+            ..IntegrationParameters::default()
+        };
+
+        let handles: Vec<_> = [0.0, 2.0, 4.0, 10.0]
+            .into_iter()
+            .map(|x| {
+                state
+                    .bodies
+                    .insert(RigidBodyBuilder::dynamic().translation(Vector::x() * x))
+            })
+            .collect();
+        let sleeping = state
+            .bodies
+            .insert(RigidBodyBuilder::dynamic().sleeping(true));
+        state
+            .impulse_joints
+            .insert(handles[0], handles[1], FixedJointBuilder::new(), true);
+        state
+            .impulse_joints
+            .insert(handles[1], handles[2], FixedJointBuilder::new(), true);
+
+        state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+
+        let mut islands: Vec<Vec<_>> = state
+            .islands
+            .islands()
+            .map(|island| {
+                assert!(!island.sleeping);
+                let mut island = island.bodies.to_vec();
+                island.sort_by_key(|h| h.into_raw_parts());
+                island
+            })
+            .collect();
+        islands.sort_by_key(|island| island.len());
+
+        assert_eq!(islands, vec![vec![handles[3]], handles[..3].to_vec()]);
+        assert!(state.bodies[sleeping].is_sleeping());
+    }
+
+    #[test]
+    fn collider_removal_observer_reports_each_removed_collider() {
+        use std::sync::{Arc, Mutex};
+
+        let (mut state, mut pipeline) = scene();
+        let params = IntegrationParameters::default();
+        let removed = Arc::new(Mutex::new(vec![]));
+        let removed_clone = removed.clone();
+        pipeline.set_collider_removal_observer(move |handle| {
+            removed_clone.lock().unwrap().push(handle)
+        });
+
+        let body = state.bodies.insert(RigidBodyBuilder::dynamic());
+        let attached: Vec<_> = (0..2)
+            .map(|_| {
+                state.colliders.insert_with_parent(
+                    ColliderBuilder::ball(0.5),
+                    body,
+                    &mut state.bodies,
+                )
+            })
+            .collect();
+        let standalone = state.colliders.insert(ColliderBuilder::ball(0.5));
+        let disabled = state.colliders.insert(ColliderBuilder::ball(0.5));
+        state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+        assert!(removed.lock().unwrap().is_empty());
+
+        state
+            .colliders
+            .remove(standalone, &mut state.islands, &mut state.bodies, false);
+        state.bodies.remove(
+            body,
+            &mut state.islands,
+            &mut state.colliders,
+            &mut state.impulse_joints,
+            &mut state.multibody_joints,
+            true,
+        );
+        state.colliders[disabled].set_enabled(false);
+        // Nothing is reported until the next step.
+        assert!(removed.lock().unwrap().is_empty());
+
+        state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+        let mut reported = removed.lock().unwrap().clone();
+        let mut expected = vec![standalone, attached[0], attached[1]];
+        reported.sort_by_key(|h| h.into_raw_parts());
+        expected.sort_by_key(|h| h.into_raw_parts());
+        assert_eq!(reported, expected);
+
+        // Each collider is only reported once.
+        state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+        assert_eq!(removed.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn gravity_field_keeps_body_in_circular_orbit() {
+        use crate::math::Real;
+
+        let (mut state, mut pipeline) = scene();
+        let params = IntegrationParameters::default();
+
+        // A point mass at the origin, and a body on a circular orbit of radius 1.
+        const GM: Real = 10.0;
+        pipeline.set_gravity_field(|_, com| {
+            let dist = com.coords.norm();
+            Some(-com.coords * (GM / (dist * dist * dist)))
+        });
+
+        let speed = GM.sqrt();
+        let handle = state.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .additional_mass(1.0)
+                .translation(Vector::x())
+                .linvel(Vector::y() * speed)
+                .can_sleep(false),
+        );
+
+        // About one and a half revolutions.
+        for _ in 0..180 {
+            state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+            let radius = state.bodies[handle].translation().norm();
+            assert!((radius - 1.0).abs() < 0.1, "radius: {radius}");
+        }
+
+        assert!((state.bodies[handle].linvel().norm() - speed).abs() < 0.1);
+    }
+
+    #[test]
+    fn per_body_sleep_threshold() {
+        let mut colliders = ColliderSet::new();
+        let mut impulse_joints = ImpulseJointSet::new();
+        let mut multibody_joints = MultibodyJointSet::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let mut bf = BroadPhaseBvh::new();
+        let mut nf = NarrowPhase::new();
+        let mut islands = IslandManager::new();
+        let mut bodies = RigidBodySet::new();
+
+        // Two bodies drifting slowly, below the default sleep thresholds.
+        let handles: Vec<_> = [0.0, 5.0]
+            .into_iter()
+            .map(|x| {
+                bodies.insert(
+                    RigidBodyBuilder::dynamic()
+                        .translation(Vector::x() * x)
+                        .linvel(Vector::y() * 0.1),
+                )
+            })
+            .collect();
+        bodies[handles[1]].set_sleep_threshold(-1.0, -1.0);
+
+        for _ in 0..300 {
+            pipeline.step(
+                &Vector::zeros(),
+                &IntegrationParameters::default(),
+                &mut islands,
+                &mut bf,
+                &mut nf,
+                &mut bodies,
+                &mut colliders,
+                &mut impulse_joints,
+                &mut multibody_joints,
+                &mut CCDSolver::new(),
+                &(),
+                &(),
+            );
+        }
+
+        assert!(bodies[handles[0]].is_sleeping());
+        assert!(!bodies[handles[1]].is_sleeping());
+        assert_eq!(bodies[handles[1]].linvel(), &(Vector::y() * 0.1));
+    }
+
+    #[test]
+    fn settled_body_emits_one_sleep_event() {
+        use crate::dynamics::SleepStateChangedEvent;
+        use crate::pipeline::ChannelEventCollector;
+        use std::sync::mpsc::channel;
+
+        let mut colliders = ColliderSet::new();
+        let mut impulse_joints = ImpulseJointSet::new();
+        let mut multibody_joints = MultibodyJointSet::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let mut bf = BroadPhaseBvh::new();
+        let mut nf = NarrowPhase::new();
+        let mut islands = IslandManager::new();
+        let mut bodies = RigidBodySet::new();
+        islands.set_sleep_events_enabled(true);
+
+        let (collision_send, _) = channel();
+        let (contact_force_send, _) = channel();
+        let (sleep_send, sleep_recv) = channel();
+        let events = ChannelEventCollector::new(collision_send, contact_force_send)
+            .with_sleep_state_changed_event_sender(sleep_send);
+
+        let ground = cuboid(10.0, 0.5, 10.0);
+        colliders.insert(ground);
+
+        let handle = bodies.insert(RigidBodyBuilder::dynamic().translation(Vector::y() * 2.0));
+        let collider = cuboid(0.5, 0.5, 0.5);
+        colliders.insert_with_parent(collider, handle, &mut bodies);
+
+        let gravity = Vector::y() * -9.81;
+        for _ in 0..600 {
+            pipeline.step(
+                &gravity,
+                &IntegrationParameters::default(),
+                &mut islands,
+                &mut bf,
+                &mut nf,
+                &mut bodies,
+                &mut colliders,
+                &mut impulse_joints,
+                &mut multibody_joints,
+                &mut CCDSolver::new(),
+                &(),
+                &events,
+            );
+        }
+
+        assert!(bodies[handle].is_sleeping());
+        assert_eq!(
+            sleep_recv.try_iter().collect::<Vec<_>>(),
+            vec![SleepStateChangedEvent {
+                handle,
+                sleeping: true
+            }]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "dim2")]
+    fn test_multi_sap_disable_body() {
+        use na::vector;
+        let mut rigid_body_set = RigidBodySet::new();
+        let mut collider_set = ColliderSet::new();
+
+        /* Create the ground. */
+        let collider = ColliderBuilder::cuboid(100.0, 0.1).build();
+        collider_set.insert(collider);
+
+        /* Create the bouncing ball. */
+        let rigid_body = RigidBodyBuilder::dynamic()
+            .translation(vector![0.0, 10.0])
+            .build();
+        let collider = ColliderBuilder::ball(0.5).restitution(0.7).build();
+        let ball_body_handle = rigid_body_set.insert(rigid_body);
+        collider_set.insert_with_parent(collider, ball_body_handle, &mut rigid_body_set);
+
+        /* Create other structures necessary for the simulation. */
+        let gravity = vector![0.0, -9.81];
+        let integration_parameters = IntegrationParameters::default();
+        let mut physics_pipeline = PhysicsPipeline::new();
+        let mut island_manager = IslandManager::new();
+        let mut broad_phase = BroadPhaseBvh::new();
+        let mut narrow_phase = NarrowPhase::new();
+        let mut impulse_joint_set = ImpulseJointSet::new();
+        let mut multibody_joint_set = MultibodyJointSet::new();
+        let mut ccd_solver = CCDSolver::new();
+        let physics_hooks = ();
+        let event_handler = ();
+
+        physics_pipeline.step(
+            &gravity,
+            &integration_parameters,
+            &mut island_manager,
+            &mut broad_phase,
+            &mut narrow_phase,
+            &mut rigid_body_set,
+            &mut collider_set,
+            &mut impulse_joint_set,
+            &mut multibody_joint_set,
+            &mut ccd_solver,
+            &physics_hooks,
+            &event_handler,
+        );
+
+        // Test RigidBodyChanges::POSITION and disable
+        {
+            let ball_body = &mut rigid_body_set[ball_body_handle];
+
+            // Also, change the translation and rotation to different values
+            ball_body.set_translation(vector![1.0, 1.0], true);
+            ball_body.set_rotation(nalgebra::UnitComplex::new(1.0), true);
+
+            ball_body.set_enabled(false);
+        }
+
+        physics_pipeline.step(
+            &gravity,
+            &integration_parameters,
+            &mut island_manager,
+            &mut broad_phase,
+            &mut narrow_phase,
+            &mut rigid_body_set,
+            &mut collider_set,
+            &mut impulse_joint_set,
+            &mut multibody_joint_set,
+            &mut ccd_solver,
+            &physics_hooks,
+            &event_handler,
+        );
+
+        // Test RigidBodyChanges::POSITION and enable
+        {
+            let ball_body = &mut rigid_body_set[ball_body_handle];
+
+            // Also, change the translation and rotation to different values
+            ball_body.set_translation(vector![0.0, 0.0], true);
+            ball_body.set_rotation(nalgebra::UnitComplex::new(0.0), true);
+
+            ball_body.set_enabled(true);
+        }
 
         physics_pipeline.step(
             &gravity,
@@ -1169,4 +2177,1550 @@ mod test {
             &event_handler,
         );
     }
+
+    #[test]
+    #[cfg(feature = "dim3")]
+    fn modified_heightfield_supports_ball_at_new_height() {
+        let mut colliders = ColliderSet::new();
+        let mut bodies = RigidBodySet::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let mut bf = BroadPhaseBvh::new();
+        let mut nf = NarrowPhase::new();
+        let mut islands = IslandManager::new();
+        let mut impulse_joints = ImpulseJointSet::new();
+        let mut multibody_joints = MultibodyJointSet::new();
+        let mut ccd = CCDSolver::new();
+        let params = IntegrationParameters::default();
+        let gravity = Vector::y() * -9.81;
+
+        let heightfield = colliders.insert(ColliderBuilder::heightfield(
+            na::DMatrix::zeros(11, 11),
+            Vector::new(10.0, 1.0, 10.0),
+        ));
+        let ball = bodies.insert(RigidBodyBuilder::dynamic().translation(Vector::y() * 0.5));
+        colliders.insert_with_parent(ColliderBuilder::ball(0.5), ball, &mut bodies);
+
+        let mut step = |bodies: &mut RigidBodySet, colliders: &mut ColliderSet, n| {
+            for _ in 0..n {
+                pipeline.step(
+                    &gravity,
+                    &params,
+                    &mut islands,
+                    &mut bf,
+                    &mut nf,
+                    bodies,
+                    colliders,
+                    &mut impulse_joints,
+                    &mut multibody_joints,
+                    &mut ccd,
+                    &(),
+                    &(),
+                );
+            }
+        };
+
+        step(&mut bodies, &mut colliders, 60);
+        approx::assert_relative_eq!(bodies[ball].translation().y, 0.5, epsilon = 0.05);
+
+        // Raise the cells around the ball.
+        let modified = colliders[heightfield].modify_heightfield(|heights| {
+            for i in 4..7 {
+                for j in 4..7 {
+                    heights[(i, j)] = 1.0;
+                }
+            }
+        });
+        assert!(modified);
+        bodies[ball].set_translation(Vector::y() * 1.5, true);
+
+        step(&mut bodies, &mut colliders, 60);
+        approx::assert_relative_eq!(bodies[ball].translation().y, 1.5, epsilon = 0.05);
+    }
+
+    #[test]
+    #[cfg(feature = "dim2")]
+    fn one_way_platform_lets_bodies_through_from_below() {
+        use na::vector;
+
+        let mut colliders = ColliderSet::new();
+        let mut bodies = RigidBodySet::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let mut bf = BroadPhaseBvh::new();
+        let mut nf = NarrowPhase::new();
+        let mut islands = IslandManager::new();
+        let mut impulse_joints = ImpulseJointSet::new();
+        let mut multibody_joints = MultibodyJointSet::new();
+        let mut ccd = CCDSolver::new();
+        let params = IntegrationParameters::default();
+        let gravity = Vector::y() * -9.81;
+
+        colliders
+            .insert(ColliderBuilder::cuboid(10.0, 0.1).one_way_platform(Vector::y_axis(), 0.05));
+
+        // A ball jumping through the platform from below, and a fast ball falling on it from above.
+        let jumper = bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(vector![-2.0, -1.0])
+                .linvel(vector![0.0, 8.0]),
+        );
+        colliders.insert_with_parent(ColliderBuilder::ball(0.25), jumper, &mut bodies);
+        let faller = bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(vector![2.0, 5.0])
+                .linvel(vector![0.0, -200.0])
+                .ccd_enabled(true),
+        );
+        colliders.insert_with_parent(ColliderBuilder::ball(0.25), faller, &mut bodies);
+
+        for _ in 0..200 {
+            pipeline.step(
+                &gravity,
+                &params,
+                &mut islands,
+                &mut bf,
+                &mut nf,
+                &mut bodies,
+                &mut colliders,
+                &mut impulse_joints,
+                &mut multibody_joints,
+                &mut ccd,
+                &(),
+                &(),
+            );
+        }
+
+        // Both balls end up resting on top of the platform.
+        approx::assert_relative_eq!(bodies[jumper].translation().y, 0.35, epsilon = 0.05);
+        approx::assert_relative_eq!(bodies[faller].translation().y, 0.35, epsilon = 0.05);
+    }
+
+    #[test]
+    fn deep_penetration_is_resolved_at_max_corrective_velocity() {
+        let (mut state, mut pipeline) = scene();
+        let mut params = IntegrationParameters::default();
+        params.normalized_max_corrective_velocity = 2.0;
+
+        let (wall, cube) = (cuboid(10.0, 1.0, 10.0), cuboid(0.5, 0.5, 0.5));
+        state.colliders.insert(wall);
+
+        // Teleport the cube 1m deep inside of the wall.
+        let handle = state
+            .bodies
+            .insert(RigidBodyBuilder::dynamic().translation(Vector::y() * 0.5));
+        state
+            .colliders
+            .insert_with_parent(cube, handle, &mut state.bodies);
+
+        let max_step = params.max_corrective_velocity() * params.dt * 1.1;
+        let mut prev_y = state.bodies[handle].translation().y;
+
+        for _ in 0..60 {
+            state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+            let rb = &state.bodies[handle];
+            assert!(rb.translation().y - prev_y <= max_step);
+            assert!(rb.linvel().norm() <= params.max_corrective_velocity() * 1.1);
+            prev_y = rb.translation().y;
+        }
+
+        // The cube is pushed out smoothly instead of being launched.
+        let rb = &state.bodies[handle];
+        assert!((rb.translation().y - 1.5).abs() < 0.01);
+        assert!(rb.linvel().norm() < 0.1);
+    }
+
+    #[test]
+    fn soft_ccd_prevents_tunneling_through_thin_wall() {
+        for soft_ccd_prediction in [0.0, 1.0] {
+            let (mut state, mut pipeline) = scene();
+            let params = IntegrationParameters::default();
+
+            let wall = cuboid(0.05, 10.0, 10.0);
+            state.colliders.insert(wall.translation(Vector::x() * 2.0));
+
+            // The ball moves by 0.5 per timestep, so it skips over the wall without CCD.
+            let handle = state.bodies.insert(
+                RigidBodyBuilder::dynamic()
+                    .translation(Vector::x() * 0.2)
+                    .linvel(Vector::x() * 30.0)
+                    .soft_ccd_prediction(soft_ccd_prediction),
+            );
+            state.colliders.insert_with_parent(
+                ColliderBuilder::ball(0.1),
+                handle,
+                &mut state.bodies,
+            );
+
+            for _ in 0..10 {
+                state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+            }
+
+            let tunneled = state.bodies[handle].translation().x > 2.0;
+            assert_eq!(tunneled, soft_ccd_prediction == 0.0);
+        }
+    }
+
+    #[test]
+    fn ccd_toi_budget_lets_bodies_tunnel_once_exhausted() {
+        for max_toi_computations in [None, Some(0)] {
+            let (mut state, mut pipeline) = scene();
+            state
+                .ccd_solver
+                .set_max_toi_computations_per_step(max_toi_computations);
+            let params = IntegrationParameters::default();
+
+            let wall = cuboid(0.05, 10.0, 10.0);
+            state.colliders.insert(wall.translation(Vector::x() * 2.0));
+
+            let handle = state.bodies.insert(
+                RigidBodyBuilder::dynamic()
+                    .translation(Vector::x() * 0.2)
+                    .linvel(Vector::x() * 30.0)
+                    .ccd_enabled(true),
+            );
+            state.colliders.insert_with_parent(
+                ColliderBuilder::ball(0.1),
+                handle,
+                &mut state.bodies,
+            );
+
+            let mut num_budget_exceeded = 0;
+            for _ in 0..10 {
+                state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+                num_budget_exceeded += pipeline.counters.ccd.num_toi_budget_exceeded;
+            }
+
+            let tunneled = state.bodies[handle].translation().x > 2.0;
+            assert_eq!(tunneled, max_toi_computations.is_some());
+            assert_eq!(num_budget_exceeded > 0, max_toi_computations.is_some());
+        }
+    }
+
+    #[test]
+    fn ccd_projectile_triggers_thin_sensor() {
+        use crate::pipeline::{ActiveEvents, ChannelEventCollector};
+        use std::sync::mpsc::channel;
+
+        let (mut state, mut pipeline) = scene();
+        let params = IntegrationParameters::default();
+        let (collision_send, collision_recv) = channel();
+        let (contact_force_send, _) = channel();
+        let events = ChannelEventCollector::new(collision_send, contact_force_send);
+
+        // A 1cm-thick trigger wall.
+        let trigger = cuboid(0.005, 10.0, 10.0);
+        let trigger = state.colliders.insert(
+            trigger
+                .translation(Vector::x() * 2.0)
+                .sensor(true)
+                .active_events(ActiveEvents::COLLISION_EVENTS),
+        );
+
+        // The projectile moves by more than 1.5m per timestep.
+        let handle = state.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::x() * 0.2)
+                .linvel(Vector::x() * 100.0)
+                .ccd_enabled(true),
+        );
+        let projectile = state.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.05),
+            handle,
+            &mut state.bodies,
+        );
+
+        for _ in 0..5 {
+            state.step(&mut pipeline, &Vector::zeros(), &params, &(), &events);
+        }
+
+        // The projectile went through the trigger without being stopped.
+        assert!(state.bodies[handle].translation().x > 2.0);
+        let events: Vec<_> = collision_recv.try_iter().collect();
+        assert_eq!(events.len(), 2);
+        assert!(events[0].started() && events[1].stopped());
+        for event in events {
+            assert!(event.sensor());
+            let pair = [event.collider1(), event.collider2()];
+            assert!(pair.contains(&trigger) && pair.contains(&projectile));
+        }
+    }
+
+    #[test]
+    fn only_bodies_stopped_by_ccd_are_reported_clamped() {
+        let (mut state, mut pipeline) = scene();
+        let params = IntegrationParameters::default();
+
+        let wall = cuboid(0.05, 10.0, 10.0);
+        state.colliders.insert(wall.translation(Vector::x() * 2.0));
+
+        let handles = [(30.0, 0.0), (1.0, 2.0)].map(|(speed, y)| {
+            let handle = state.bodies.insert(
+                RigidBodyBuilder::dynamic()
+                    .translation(Vector::x() * 0.2 + Vector::y() * y)
+                    .linvel(Vector::x() * speed)
+                    .ccd_enabled(true),
+            );
+            state.colliders.insert_with_parent(
+                ColliderBuilder::ball(0.1),
+                handle,
+                &mut state.bodies,
+            );
+            handle
+        });
+
+        // The fast body would cross the wall during the fourth timestep.
+        let mut clamped = vec![];
+        for _ in 0..5 {
+            state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+            clamped.push(handles.map(|h| state.bodies[h].was_clamped_by_ccd()));
+        }
+
+        assert_eq!(
+            clamped[..4],
+            [
+                [false, false],
+                [false, false],
+                [false, false],
+                [true, false]
+            ]
+        );
+        assert!(!clamped[4][1]);
+        assert!(state.bodies[handles[0]].translation().x < 2.0);
+    }
+
+    #[test]
+    fn impacts_after_exhausting_ccd_substeps_are_counted() {
+        let default_substeps = IntegrationParameters::default().max_ccd_substeps;
+        for (max_ccd_substeps, expected) in [
+            (default_substeps, [0, 0, 0, 0, 0]),
+            (2, [0, 0, 0, 1, 0]),
+            (10, [0, 0, 0, 0, 0]),
+        ] {
+            let (mut state, mut pipeline) = scene();
+            let params = IntegrationParameters {
+                max_ccd_substeps,
+                ..Default::default()
+            };
+
+            let wall = cuboid(0.05, 10.0, 10.0);
+            state.colliders.insert(wall.translation(Vector::x() * 2.0));
+
+            // Both balls hit the wall during the fourth timestep, at 40% and 90% of its duration.
+            for (x, y) in [(0.15, 0.0), (-0.1, 1.0)] {
+                let handle = state.bodies.insert(
+                    RigidBodyBuilder::dynamic()
+                        .translation(Vector::x() * x + Vector::y() * y)
+                        .linvel(Vector::x() * 30.0)
+                        .ccd_enabled(true),
+                );
+                state.colliders.insert_with_parent(
+                    ColliderBuilder::ball(0.1),
+                    handle,
+                    &mut state.bodies,
+                );
+            }
+
+            // With two substeps, the first one is used by the first impact, leaving the second
+            // impact to the last substep. With more substeps, each impact gets its own substep.
+            let mut num_impacts = vec![];
+            for _ in 0..5 {
+                state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+                num_impacts.push(pipeline.counters.ccd.num_impacts_after_last_substep);
+                assert_eq!(pipeline.counters.ccd.num_toi_budget_exceeded, 0);
+            }
+
+            assert_eq!(num_impacts, expected);
+        }
+    }
+
+    #[test]
+    fn sensors_use_their_own_prediction_distance_in_broad_phase() {
+        let count_pairs = |params: &IntegrationParameters| {
+            let (mut state, mut pipeline) = scene();
+
+            let sensor = cuboid(0.5, 0.5, 0.5);
+            state.colliders.insert(sensor.sensor(true));
+
+            // A ball separated from the sensor by a gap of 0.5.
+            let handle = state
+                .bodies
+                .insert(RigidBodyBuilder::dynamic().translation(Vector::x() * 1.5));
+            state.colliders.insert_with_parent(
+                ColliderBuilder::ball(0.5),
+                handle,
+                &mut state.bodies,
+            );
+
+            state.step(&mut pipeline, &Vector::zeros(), params, &(), &());
+
+            let mut num_pairs = 0;
+            state.broad_phase.for_each_pair(|_, _| num_pairs += 1);
+            num_pairs
+        };
+
+        assert_eq!(count_pairs(&IntegrationParameters::default()), 0);
+
+        // The sensor’s AABB is loosened by half of its prediction distance, and the ball’s by
+        // half of the regular prediction distance.
+        let params = IntegrationParameters {
+            normalized_sensor_prediction_distance: Some(1.0),
+            ..Default::default()
+        };
+        assert_eq!(count_pairs(&params), 1);
+    }
+
+    #[test]
+    fn collision_group_changes_remove_stale_contacts() {
+        use crate::geometry::InteractionGroups;
+        use crate::pipeline::{ActiveEvents, ChannelEventCollector};
+        use std::sync::mpsc::channel;
+
+        let (mut state, mut pipeline) = scene();
+        let params = IntegrationParameters::default();
+        let gravity = Vector::y() * -9.81;
+        let (collision_send, collision_recv) = channel();
+        let (contact_force_send, _) = channel();
+        let events = ChannelEventCollector::new(collision_send, contact_force_send);
+
+        let ground = insert_ground(&mut state);
+        state.colliders[ground].set_active_events(ActiveEvents::COLLISION_EVENTS);
+
+        let handle = state
+            .bodies
+            .insert(RigidBodyBuilder::dynamic().translation(Vector::y() * 0.5));
+        let ball = state.colliders.insert_with_parent(
+            ColliderBuilder::ball(0.5),
+            handle,
+            &mut state.bodies,
+        );
+
+        let in_contact = |state: &PhysicsState| {
+            state
+                .narrow_phase
+                .contact_pair(ground, ball)
+                .is_some_and(|pair| pair.has_any_active_contact)
+        };
+        let num_broad_phase_pairs = |state: &PhysicsState| {
+            let mut num_pairs = 0;
+            state.broad_phase.for_each_pair(|_, _| num_pairs += 1);
+            num_pairs
+        };
+
+        for _ in 0..5 {
+            state.step(&mut pipeline, &gravity, &params, &(), &events);
+        }
+        assert!(in_contact(&state));
+        assert!(collision_recv.try_iter().all(|e| e.started()));
+
+        // The ball no longer interacts with the ground, even though nothing moved.
+        state.colliders[ground].set_collision_groups(InteractionGroups::none());
+        state.step(&mut pipeline, &gravity, &params, &(), &events);
+        assert!(!in_contact(&state));
+        let stopped: Vec<_> = collision_recv.try_iter().collect();
+        assert_eq!(stopped.len(), 1);
+        assert!(stopped[0].stopped());
+
+        // The broad-phase pair is kept, so restoring the groups restores the contact right away.
+        assert_eq!(num_broad_phase_pairs(&state), 1);
+        state.colliders[ground].set_collision_groups(InteractionGroups::all());
+        state.step(&mut pipeline, &gravity, &params, &(), &events);
+        assert!(in_contact(&state));
+    }
+
+    #[test]
+    fn bodies_leaving_world_bounds_are_reported_and_disabled() {
+        use crate::geometry::{Aabb, BoundingVolume};
+        use crate::math::Point;
+        use std::sync::{Arc, Mutex};
+
+        for disable in [false, true] {
+            let (mut state, mut pipeline) = scene();
+            let params = IntegrationParameters::default();
+            let bounds = Aabb::from_half_extents(Point::origin(), Vector::repeat(10.0));
+            let reported = Arc::new(Mutex::new(vec![]));
+            let reported_clone = reported.clone();
+            pipeline.set_world_bounds(bounds);
+            pipeline.set_disable_out_of_bounds_bodies(disable);
+            pipeline.set_out_of_bounds_observer(move |handle, aabb| {
+                reported_clone.lock().unwrap().push((handle, aabb));
+            });
+
+            // The first body leaves the bounds during the third timestep, the second stays in.
+            let handles = [Vector::x() * 240.0, Vector::x() * 3.0].map(|linvel| {
+                let handle = state
+                    .bodies
+                    .insert(RigidBodyBuilder::dynamic().linvel(linvel));
+                state.colliders.insert_with_parent(
+                    ColliderBuilder::ball(0.5),
+                    handle,
+                    &mut state.bodies,
+                );
+                handle
+            });
+
+            for _ in 0..5 {
+                state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+            }
+
+            let reported = reported.lock().unwrap();
+            assert_eq!(reported.len(), if disable { 1 } else { 3 });
+            assert!(reported.iter().all(|(handle, _)| *handle == handles[0]));
+            assert!(reported.iter().all(|(_, aabb)| !bounds.contains(aabb)));
+
+            let body = &state.bodies[handles[0]];
+            assert_eq!(body.is_enabled(), !disable);
+            if disable {
+                // The body stays where it left the bounds, and its collider is no longer part
+                // of the broad-phase.
+                assert!((body.translation().x - 12.0).abs() < 1.0e-3);
+                assert!(
+                    state
+                        .broad_phase
+                        .collider_aabb(body.colliders()[0])
+                        .is_none()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn colliders_teleported_on_their_parent_are_ignored_by_ccd() {
+        use crate::geometry::ColliderPositionSync;
+
+        for position_sync in [ColliderPositionSync::Swept, ColliderPositionSync::Teleport] {
+            let (mut state, mut pipeline) = scene();
+            let params = IntegrationParameters::default();
+
+            let wall = cuboid(0.05, 10.0, 10.0);
+            state.colliders.insert(wall.translation(Vector::x() * 5.0));
+
+            let handle = state.bodies.insert(
+                RigidBodyBuilder::dynamic()
+                    .linvel(Vector::x() * 100.0)
+                    .ccd_enabled(true),
+            );
+            let collider = state.colliders.insert_with_parent(
+                ColliderBuilder::ball(0.1).position_sync(position_sync),
+                handle,
+                &mut state.bodies,
+            );
+
+            state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+            assert!(!state.bodies[handle].was_clamped_by_ccd());
+
+            // Move the collider forward on its parent so it crosses the wall during the next step.
+            state.colliders[collider].set_translation_wrt_parent(Vector::x() * 2.5);
+            state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+
+            let teleported = position_sync == ColliderPositionSync::Teleport;
+            assert_eq!(state.bodies[handle].was_clamped_by_ccd(), !teleported);
+            assert_eq!(state.colliders[collider].ccd_teleported, teleported);
+
+            // The collider is only ignored during one timestep.
+            state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+            assert!(!state.colliders[collider].ccd_teleported);
+        }
+    }
+
+    #[test]
+    fn max_restitution_combine_rule_bounces_off_non_bouncy_floor() {
+        use crate::dynamics::CoefficientCombineRule;
+        use crate::math::Real;
+
+        let (mut state, mut pipeline) = scene();
+        let params = IntegrationParameters::default();
+
+        let floor = cuboid(20.0, 0.1, 20.0);
+        state.colliders.insert(floor.restitution(0.0));
+
+        // Two perfectly bouncy balls falling on the floor, only one of them making its
+        // restitution win over the floor’s.
+        let drop_height = 1.5;
+        let handles: Vec<_> = [CoefficientCombineRule::Average, CoefficientCombineRule::Max]
+            .into_iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                let rb = RigidBodyBuilder::dynamic().translation(
+                    Vector::x() * (i as Real * 5.0) + Vector::y() * (drop_height + 0.6),
+                );
+                let handle = state.bodies.insert(rb);
+                let ball = ColliderBuilder::ball(0.5)
+                    .restitution(1.0)
+                    .restitution_combine_rule(rule);
+                state
+                    .colliders
+                    .insert_with_parent(ball, handle, &mut state.bodies);
+                handle
+            })
+            .collect();
+
+        let gravity = Vector::y() * -9.81;
+        let mut rebound_speeds = [0.0; 2];
+        for _ in 0..60 {
+            state.step(&mut pipeline, &gravity, &params, &(), &());
+            for (speed, handle) in rebound_speeds.iter_mut().zip(handles.iter()) {
+                *speed = state.bodies[*handle].linvel().y.max(*speed);
+            }
+        }
+
+        // With `Average`, the effective restitution is 0.5. With `Max`, it is 1.0.
+        let impact_speed = (2.0 * 9.81 * drop_height).sqrt();
+        assert!(rebound_speeds[0] < impact_speed * 0.6);
+        assert!(rebound_speeds[1] > impact_speed * 0.9);
+    }
+
+    #[test]
+    fn rolling_resistance_stops_rolling_balls() {
+        let params = IntegrationParameters::default();
+        let gravity = Vector::y() * -9.81;
+        let (radius, speed) = (0.5, 1.0);
+
+        let mut steps_to_rest = vec![];
+        for rolling_resistance in [0.0, 0.02, 0.05] {
+            let (mut state, mut pipeline) = scene();
+
+            let floor = cuboid(50.0, 0.1, 50.0);
+            state
+                .colliders
+                .insert(floor.rolling_resistance(rolling_resistance));
+
+            // A ball already rolling without slipping along the X axis.
+            #[cfg(feature = "dim2")]
+            let angvel = -speed / radius;
+            #[cfg(feature = "dim3")]
+            let angvel = Vector::z() * (-speed / radius);
+            let rb = RigidBodyBuilder::dynamic()
+                .translation(Vector::y() * (0.1 + radius))
+                .linvel(Vector::x() * speed)
+                .angvel(angvel);
+            let handle = state.bodies.insert(rb);
+            let ball = ColliderBuilder::ball(radius).rolling_resistance(rolling_resistance);
+            state
+                .colliders
+                .insert_with_parent(ball, handle, &mut state.bodies);
+
+            let mut steps = None;
+            for i in 0..600 {
+                state.step(&mut pipeline, &gravity, &params, &(), &());
+                if state.bodies[handle].linvel().norm() < 1.0e-3 {
+                    steps = Some(i);
+                    break;
+                }
+            }
+
+            steps_to_rest.push(steps);
+        }
+
+        // Without rolling resistance, the ball rolls forever.
+        assert_eq!(steps_to_rest[0], None);
+        assert!(steps_to_rest[1].unwrap() > steps_to_rest[2].unwrap());
+    }
+
+    #[test]
+    fn ccd_falls_back_to_linear_toi_unless_disabled() {
+        for linear_fallback_scale in [0.0, 1.0, 2.0] {
+            let (mut state, mut pipeline) = scene();
+            state
+                .ccd_solver
+                .set_linear_fallback_scale(linear_fallback_scale);
+            let params = IntegrationParameters::default();
+
+            // The nonlinear time of impact isn’t supported against half-spaces.
+            state
+                .colliders
+                .insert(ColliderBuilder::halfspace(Vector::y_axis()));
+
+            // The ball moves by more than 1.5m per timestep.
+            let handle = state.bodies.insert(
+                RigidBodyBuilder::dynamic()
+                    .translation(Vector::y())
+                    .linvel(Vector::y() * -100.0)
+                    .ccd_enabled(true),
+            );
+            state.colliders.insert_with_parent(
+                ColliderBuilder::ball(0.1),
+                handle,
+                &mut state.bodies,
+            );
+
+            state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+
+            let tunneled = state.bodies[handle].translation().y < 0.0;
+            assert_eq!(tunneled, linear_fallback_scale == 0.0);
+        }
+    }
+
+    #[test]
+    fn ccd_pair_filter_is_evaluated_once_per_pair_and_timestep() {
+        use std::sync::{Arc, Mutex};
+
+        let (mut state, mut pipeline) = scene();
+        let params = IntegrationParameters::default();
+
+        let wall = cuboid(0.05, 10.0, 10.0);
+        state.colliders.insert(wall.translation(Vector::x() * 2.0));
+
+        // Two projectiles flying side by side, close enough to be CCD candidates.
+        let projectiles = [0.0, 0.25].map(|y| {
+            let handle = state.bodies.insert(
+                RigidBodyBuilder::dynamic()
+                    .translation(Vector::x() * 0.2 + Vector::y() * y)
+                    .linvel(Vector::x() * 30.0)
+                    .ccd_enabled(true),
+            );
+            let collider = state.colliders.insert_with_parent(
+                ColliderBuilder::ball(0.1),
+                handle,
+                &mut state.bodies,
+            );
+            (handle, collider)
+        });
+
+        let calls = Arc::new(Mutex::new(vec![]));
+        let filter_calls = calls.clone();
+        let [(_, projectile1), (_, projectile2)] = projectiles;
+        state.ccd_solver.set_pair_filter(move |ch1, ch2| {
+            let (id1, id2) = (ch1.into_raw_parts().0, ch2.into_raw_parts().0);
+            filter_calls
+                .lock()
+                .unwrap()
+                .push((id1.min(id2), id1.max(id2)));
+            // Projectiles don’t run CCD against each other.
+            !((ch1 == projectile1 && ch2 == projectile2)
+                || (ch1 == projectile2 && ch2 == projectile1))
+        });
+
+        let mut num_calls = 0;
+        for _ in 0..5 {
+            state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+
+            let mut step_calls = std::mem::take(&mut *calls.lock().unwrap());
+            num_calls += step_calls.len();
+            let num_step_calls = step_calls.len();
+            step_calls.sort();
+            step_calls.dedup();
+            assert_eq!(step_calls.len(), num_step_calls);
+        }
+
+        // The projectiles were both stopped by the wall.
+        assert!(num_calls > 0);
+        for (handle, _) in projectiles {
+            assert!(state.bodies[handle].translation().x < 2.0);
+        }
+    }
+
+    #[test]
+    fn kinematic_ccd_crossings_respect_active_collision_types() {
+        use crate::geometry::ActiveCollisionTypes;
+        use crate::pipeline::{ActiveEvents, ChannelEventCollector};
+        use std::sync::mpsc::channel;
+
+        for active_collision_types in [
+            ActiveCollisionTypes::default(),
+            ActiveCollisionTypes::default() | ActiveCollisionTypes::KINEMATIC_KINEMATIC,
+        ] {
+            let (mut state, mut pipeline) = scene();
+            let params = IntegrationParameters::default();
+            let (collision_send, collision_recv) = channel();
+            let (contact_force_send, _) = channel();
+            let events = ChannelEventCollector::new(collision_send, contact_force_send);
+
+            // A 1cm-thick trigger zone attached to a kinematic body.
+            let zone = cuboid(0.005, 10.0, 10.0);
+            let zone_body = state.bodies.insert(
+                RigidBodyBuilder::kinematic_position_based().translation(Vector::x() * 2.0),
+            );
+            state.colliders.insert_with_parent(
+                zone.active_events(ActiveEvents::COLLISION_EVENTS)
+                    .active_collision_types(active_collision_types),
+                zone_body,
+                &mut state.bodies,
+            );
+
+            // A kinematic platform sweeping through the zone in a single timestep.
+            let platform = state.bodies.insert(
+                RigidBodyBuilder::kinematic_velocity_based()
+                    .translation(Vector::x() * 0.2)
+                    .linvel(Vector::x() * 100.0)
+                    .ccd_enabled(true),
+            );
+            state.colliders.insert_with_parent(
+                ColliderBuilder::ball(0.05).active_collision_types(active_collision_types),
+                platform,
+                &mut state.bodies,
+            );
+
+            for _ in 0..5 {
+                state.step(&mut pipeline, &Vector::zeros(), &params, &(), &events);
+            }
+
+            // The platform is never stopped.
+            assert!(state.bodies[platform].translation().x > 2.0);
+            let events: Vec<_> = collision_recv.try_iter().collect();
+            if active_collision_types.contains(ActiveCollisionTypes::KINEMATIC_KINEMATIC) {
+                assert_eq!(events.len(), 2);
+                assert!(events[0].started() && events[1].stopped());
+            } else {
+                assert!(events.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn tunneling_warnings_are_emitted_once_and_forgotten_on_removal() {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+
+        let (mut state, mut pipeline) = scene();
+        let params = IntegrationParameters::default();
+
+        // Only the fast body without CCD is at risk of tunneling.
+        let handles =
+            [(0.0, 30.0, false), (1.0, 30.0, true), (2.0, 0.1, false)].map(|(y, speed, ccd)| {
+                let handle = state.bodies.insert(
+                    RigidBodyBuilder::dynamic()
+                        .translation(Vector::y() * y)
+                        .linvel(Vector::x() * speed)
+                        .ccd_enabled(ccd),
+                );
+                state.colliders.insert_with_parent(
+                    ColliderBuilder::ball(0.1),
+                    handle,
+                    &mut state.bodies,
+                );
+                handle
+            });
+
+        for _ in 0..3 {
+            state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+            assert_eq!(state.ccd_solver.tunneling_warnings_emitted.len(), 1);
+            assert!(
+                state
+                    .ccd_solver
+                    .tunneling_warnings_emitted
+                    .contains(&handles[0])
+            );
+        }
+
+        let _ = state.bodies.remove(
+            handles[0],
+            &mut state.islands,
+            &mut state.colliders,
+            &mut state.impulse_joints,
+            &mut state.multibody_joints,
+            true,
+        );
+        state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+        assert!(state.ccd_solver.tunneling_warnings_emitted.is_empty());
+
+        // The CCD-enabled body is at risk too if no CCD substep is allowed.
+        let params = IntegrationParameters {
+            max_ccd_substeps: 0,
+            ..params
+        };
+        state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+        assert_eq!(state.ccd_solver.tunneling_warnings_emitted.len(), 1);
+        assert!(
+            state
+                .ccd_solver
+                .tunneling_warnings_emitted
+                .contains(&handles[1])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "dim3")]
+    fn spherical_joint_cone_limit_survives_half_turn_swings() {
+        use crate::dynamics::SphericalJointBuilder;
+        use crate::math::{Real, Rotation};
+
+        for swing in [
+            <Real as na::RealField>::pi(),
+            <Real as na::RealField>::pi() - 1.0e-3,
+        ] {
+            let (mut state, mut pipeline) = scene();
+            let params = IntegrationParameters::default();
+
+            // The body starts with its X axis pointing (almost) opposite to the cone axis,
+            // where the swing-twist decomposition is singular.
+            let fixed = state.bodies.insert(RigidBodyBuilder::fixed());
+            let body = state
+                .bodies
+                .insert(RigidBodyBuilder::dynamic().rotation(Vector::y() * swing));
+            state
+                .colliders
+                .insert_with_parent(ColliderBuilder::ball(0.5), body, &mut state.bodies);
+            let joint = SphericalJointBuilder::new()
+                .cone_limit(0.5)
+                .twist_limit(-0.2, 0.2);
+            state.impulse_joints.insert(fixed, body, joint, true);
+
+            let swing_angle =
+                |rotation: &Rotation<Real>| (rotation * Vector::x()).angle(&Vector::x());
+            let initial_swing = swing_angle(state.bodies[body].rotation());
+
+            for _ in 0..60 {
+                state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+                let rb = &state.bodies[body];
+                assert!(rb.linvel().iter().all(|x| x.is_finite()));
+                assert!(rb.angvel().iter().all(|x| x.is_finite()));
+                assert!(rb.rotation().coords.iter().all(|x| x.is_finite()));
+            }
+
+            // The limit pulls the body back toward the cone.
+            assert!(swing_angle(state.bodies[body].rotation()) < initial_swing);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dim3")]
+    fn spherical_joint_twist_limit_holds_while_swinging() {
+        use crate::dynamics::SphericalJointBuilder;
+        use crate::math::Real;
+
+        let (mut state, mut pipeline) = scene();
+        let params = IntegrationParameters::default();
+
+        // A body both twisting along and swinging away from the X axis.
+        let fixed = state.bodies.insert(RigidBodyBuilder::fixed());
+        let body = state
+            .bodies
+            .insert(RigidBodyBuilder::dynamic().angvel(Vector::x() * 3.0 + Vector::z() * 2.0));
+        state
+            .colliders
+            .insert_with_parent(ColliderBuilder::ball(0.5), body, &mut state.bodies);
+        let (cone, twist) = (0.8, 0.3);
+        let joint = SphericalJointBuilder::new()
+            .cone_limit(cone)
+            .twist_limit(-twist, twist);
+        state.impulse_joints.insert(fixed, body, joint, true);
+
+        let mut max_swing: Real = 0.0;
+        for _ in 0..120 {
+            state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+
+            // Measure the twist along X, independently from the swing.
+            let rot = state.bodies[body].rotation();
+            let (x, w) = if rot.w < 0.0 {
+                (-rot.i, -rot.w)
+            } else {
+                (rot.i, rot.w)
+            };
+            let twist_angle = 2.0 * x.atan2(w);
+            let swing_angle = (rot * Vector::x()).angle(&Vector::x());
+            max_swing = max_swing.max(swing_angle);
+
+            assert!(twist_angle.abs() < twist + 0.1);
+            assert!(swing_angle < cone + 0.1);
+        }
+
+        // The body actually swung up to the cone limit.
+        assert!(max_swing > cone - 0.1);
+    }
+
+    #[test]
+    fn out_of_bounds_bodies_are_reported_once_per_timestep() {
+        use crate::geometry::Aabb;
+        use crate::math::Point;
+        use std::sync::{Arc, Mutex};
+
+        let (mut state, mut pipeline) = scene();
+        let params = IntegrationParameters {
+            max_ccd_substeps: 2,
+            ..Default::default()
+        };
+        let reported = Arc::new(Mutex::new(vec![]));
+        let reported_clone = reported.clone();
+        pipeline.set_world_bounds(Aabb::from_half_extents(
+            Point::origin(),
+            Vector::repeat(10.0),
+        ));
+        pipeline.set_out_of_bounds_observer(move |handle, _| {
+            reported_clone.lock().unwrap().push(handle);
+        });
+
+        let wall = cuboid(0.05, 5.0, 5.0);
+        state
+            .colliders
+            .insert(wall.translation(Vector::x() * 10.35));
+
+        // The ball leaves the bounds when it hits the wall, 80% into the timestep. This splits the
+        // timestep into two CCD substeps, and the ball remains out of the bounds during both.
+        let handle = state.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::x() * 9.0)
+                .linvel(Vector::x() * 60.0)
+                .ccd_enabled(true),
+        );
+        state
+            .colliders
+            .insert_with_parent(ColliderBuilder::ball(0.5), handle, &mut state.bodies);
+
+        state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+        assert_eq!(pipeline.counters.ccd.num_substeps, 2);
+        assert_eq!(*reported.lock().unwrap(), [handle]);
+    }
+
+    #[test]
+    fn rolling_resistance_is_independent_from_solver_iterations() {
+        let gravity = Vector::y() * -9.81;
+        let (radius, speed) = (0.5, 1.0);
+
+        let mut speed_losses = vec![];
+        for num_solver_iterations in [2, 8] {
+            let (mut state, mut pipeline) = scene();
+            let params = IntegrationParameters {
+                num_solver_iterations,
+                ..Default::default()
+            };
+
+            let floor = cuboid(50.0, 0.1, 50.0);
+            state.colliders.insert(floor.rolling_resistance(0.05));
+
+            // A ball already rolling without slipping along the X axis.
+            #[cfg(feature = "dim2")]
+            let angvel = -speed / radius;
+            #[cfg(feature = "dim3")]
+            let angvel = Vector::z() * (-speed / radius);
+            let rb = RigidBodyBuilder::dynamic()
+                .translation(Vector::y() * (0.1 + radius))
+                .linvel(Vector::x() * speed)
+                .angvel(angvel);
+            let handle = state.bodies.insert(rb);
+            let ball = ColliderBuilder::ball(radius).rolling_resistance(0.05);
+            state
+                .colliders
+                .insert_with_parent(ball, handle, &mut state.bodies);
+
+            for _ in 0..30 {
+                state.step(&mut pipeline, &gravity, &params, &(), &());
+            }
+
+            speed_losses.push(speed - state.bodies[handle].linvel().x);
+        }
+
+        // The resisting torque only depends on the load, not on the number of solver substeps.
+        assert!(speed_losses[0] > 0.1);
+        approx::assert_relative_eq!(speed_losses[0], speed_losses[1], max_relative = 0.1);
+    }
+
+    #[test]
+    fn joint_motor_max_velocity_ramps_toward_target_position() {
+        use crate::dynamics::PrismaticJointBuilder;
+        use crate::math::Real;
+
+        let params = IntegrationParameters::default();
+        let target = 2.0;
+
+        // A body driven to `target` by a stiff, critically-damped prismatic position motor.
+        let run = |max_vel: Option<Real>, num_steps: usize| {
+            let (mut state, mut pipeline) = scene();
+            let fixed = state.bodies.insert(RigidBodyBuilder::fixed());
+            let rb = RigidBodyBuilder::dynamic().additional_mass(1.0);
+            let body = state.bodies.insert(rb);
+            let mut joint =
+                PrismaticJointBuilder::new(Vector::x_axis()).motor_position(target, 100.0, 20.0);
+            if let Some(max_vel) = max_vel {
+                joint = joint.motor_max_velocity(max_vel);
+            }
+            state.impulse_joints.insert(fixed, body, joint, true);
+
+            let mut max_speed: Real = 0.0;
+            for _ in 0..num_steps {
+                state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+                max_speed = max_speed.max(state.bodies[body].linvel().x.abs());
+            }
+            (state.bodies[body].translation().x, max_speed)
+        };
+
+        // Without a maximum velocity, the motor reaches its target within a second.
+        let (pos, max_speed) = run(None, 60);
+        assert!((pos - target).abs() < 0.1);
+        assert!(max_speed > 2.0);
+
+        // With it, the motor moves at (at most) the maximum velocity.
+        let (pos, max_speed) = run(Some(0.5), 60);
+        assert!(max_speed <= 0.5 + 1.0e-3);
+        assert!(pos > 0.4 && pos <= 0.5 + 1.0e-3);
+
+        // And still reaches its target eventually.
+        let (pos, _) = run(Some(0.5), 360);
+        assert!((pos - target).abs() < 1.0e-2);
+
+        // An infinite maximum velocity behaves like no maximum velocity at all.
+        assert_eq!(run(Some(Real::INFINITY), 60), run(None, 60));
+    }
+
+    #[test]
+    fn rope_joint_is_slack_between_its_min_and_max_distances() {
+        use crate::dynamics::RopeJointBuilder;
+        use crate::math::Real;
+
+        let params = IntegrationParameters::default();
+
+        for speed in [-1.0 as Real, 1.0] {
+            let (mut state, mut pipeline) = scene();
+
+            // A body moving freely along a rope joint until it reaches one of its limits.
+            let fixed = state.bodies.insert(RigidBodyBuilder::fixed());
+            let rb = RigidBodyBuilder::dynamic()
+                .translation(Vector::x() * 1.5)
+                .linvel(Vector::x() * speed)
+                .additional_mass(1.0);
+            let body = state.bodies.insert(rb);
+            let joint = RopeJointBuilder::new(2.0).min_distance(1.0);
+            state.impulse_joints.insert(fixed, body, joint, true);
+
+            // No force is applied within the limits.
+            for _ in 0..20 {
+                state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+                assert!((state.bodies[body].linvel() - Vector::x() * speed).norm() < 1.0e-5);
+            }
+
+            // The body stops at the limit it moves toward.
+            for _ in 0..100 {
+                state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+            }
+            let expected = if speed < 0.0 { 1.0 } else { 2.0 };
+            let dist = state.bodies[body].translation().norm();
+            assert!((dist - expected).abs() < 1.0e-2);
+            assert!(state.bodies[body].linvel().norm() < 1.0e-2);
+        }
+    }
+
+    #[test]
+    fn contact_force_events_use_the_largest_threshold_of_the_pair() {
+        use crate::math::Real;
+        use crate::pipeline::{ActiveEvents, ChannelEventCollector};
+        use std::sync::mpsc::channel;
+
+        let params = IntegrationParameters::default();
+
+        // A ball weighing more than 20N resting on a floor, with the given thresholds, or
+        // `None` if contact force events are disabled.
+        let num_force_events = |floor_threshold: Option<Real>, ball_threshold: Option<Real>| {
+            let (mut state, mut pipeline) = scene();
+            let (collision_send, _) = channel();
+            let (contact_force_send, contact_force_recv) = channel();
+            let events = ChannelEventCollector::new(collision_send, contact_force_send);
+            let with_threshold = |co: ColliderBuilder, threshold: Option<Real>| match threshold {
+                Some(threshold) => co
+                    .active_events(ActiveEvents::CONTACT_FORCE_EVENTS)
+                    .contact_force_event_threshold(threshold),
+                None => co,
+            };
+
+            let floor = cuboid(10.0, 0.5, 10.0).translation(-Vector::y() * 0.5);
+            state
+                .colliders
+                .insert(with_threshold(floor, floor_threshold));
+            let rb = RigidBodyBuilder::dynamic()
+                .translation(Vector::y() * 0.5)
+                .additional_mass(2.0);
+            let body = state.bodies.insert(rb);
+            let ball = with_threshold(ColliderBuilder::ball(0.5), ball_threshold);
+            state
+                .colliders
+                .insert_with_parent(ball, body, &mut state.bodies);
+
+            for _ in 0..30 {
+                state.step(&mut pipeline, &(Vector::y() * -9.81), &params, &(), &events);
+            }
+            contact_force_recv.try_iter().count()
+        };
+
+        // The largest threshold is used if both colliders enable contact force events.
+        assert_eq!(num_force_events(Some(10.0), Some(100.0)), 0);
+        assert_eq!(num_force_events(Some(100.0), Some(10.0)), 0);
+        assert!(num_force_events(Some(10.0), Some(1.0)) > 0);
+        // Otherwise, the threshold of the collider enabling them is used.
+        assert!(num_force_events(Some(10.0), None) > 0);
+        assert!(num_force_events(None, Some(10.0)) > 0);
+        assert_eq!(num_force_events(None, Some(100.0)), 0);
+        assert_eq!(num_force_events(None, None), 0);
+    }
+
+    #[test]
+    fn excluded_groups_never_interact() {
+        use crate::geometry::{Group, InteractionGroups};
+
+        let (mut state, mut pipeline) = scene();
+        let params = IntegrationParameters::default();
+
+        // Two overlapping parts of a ragdoll, colliding with everything but each other.
+        let ragdoll_groups =
+            InteractionGroups::with_exclusion(Group::GROUP_2, Group::ALL, Group::GROUP_2);
+        let ground = insert_ground(&mut state);
+        let parts: Vec<_> = [0.5, 0.8]
+            .into_iter()
+            .map(|y| {
+                let body = state
+                    .bodies
+                    .insert(RigidBodyBuilder::dynamic().translation(Vector::y() * y));
+                let co = ColliderBuilder::ball(0.5).collision_groups(ragdoll_groups);
+                state
+                    .colliders
+                    .insert_with_parent(co, body, &mut state.bodies)
+            })
+            .collect();
+
+        let in_contact = |state: &PhysicsState, co1, co2| {
+            state
+                .narrow_phase
+                .contact_pair(co1, co2)
+                .is_some_and(|pair| pair.has_any_active_contact)
+        };
+
+        for _ in 0..5 {
+            state.step(&mut pipeline, &(Vector::y() * -9.81), &params, &(), &());
+        }
+        assert!(!in_contact(&state, parts[0], parts[1]));
+        assert!(in_contact(&state, ground, parts[0]));
+
+        // Removing the exclusion mask lets the parts collide again.
+        for part in &parts {
+            state.colliders[*part].set_collision_groups(ragdoll_groups.with_exclude(Group::NONE));
+        }
+        state.step(&mut pipeline, &(Vector::y() * -9.81), &params, &(), &());
+        assert!(in_contact(&state, parts[0], parts[1]));
+    }
+
+    #[test]
+    fn velocity_at_point_matches_the_motion_of_points_on_the_body() {
+        use crate::math::{Point, Real};
+
+        let (mut state, mut pipeline) = scene();
+        let params = IntegrationParameters {
+            dt: 1.0 / 600.0,
+            ..IntegrationParameters::default()
+        };
+
+        // A spinning body with its center of mass away from its origin.
+        #[cfg(feature = "dim2")]
+        let angvel = 2.0;
+        #[cfg(feature = "dim3")]
+        let angvel = Vector::z() * 2.0;
+        let rb = RigidBodyBuilder::dynamic()
+            .translation(Vector::x() * 3.0)
+            .linvel(Vector::y())
+            .angvel(angvel);
+        let handle = state.bodies.insert(rb);
+        let co = ColliderBuilder::ball(0.5).translation(Vector::x() * 0.5);
+        state
+            .colliders
+            .insert_with_parent(co, handle, &mut state.bodies);
+        state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+
+        let local_point = Point::from(Vector::x() - Vector::y() * 0.3);
+        let rb = &state.bodies[handle];
+        let world_point = rb.position() * local_point;
+        let expected_vel = rb.velocity_at_point(&world_point);
+        assert_eq!(rb.velocity_at_point_local(&local_point), expected_vel);
+        // The center of mass only moves with the linear velocity.
+        let com_vel = rb.velocity_at_point(rb.center_of_mass());
+        assert!((com_vel - rb.linvel()).norm() < 1.0e-5);
+
+        // The point on the body moves at the predicted velocity.
+        state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+        let new_world_point = state.bodies[handle].position() * local_point;
+        let fd_vel = (new_world_point - world_point) / params.dt;
+        assert!((fd_vel - expected_vel).norm() < 1.0e-2 as Real);
+        assert!(expected_vel.norm() > 1.0);
+    }
+
+    #[test]
+    fn ccd_impact_events_are_opt_in() {
+        use crate::math::Real;
+        use crate::pipeline::ChannelEventCollector;
+        use std::sync::mpsc::channel;
+
+        for enabled in [false, true] {
+            let (mut state, mut pipeline) = scene();
+            let params = IntegrationParameters::default();
+            let (collision_send, _) = channel();
+            let (contact_force_send, _) = channel();
+            let (impact_send, impact_recv) = channel();
+            let events = ChannelEventCollector::new(collision_send, contact_force_send)
+                .with_ccd_impact_event_sender(impact_send);
+            state.ccd_solver.set_impact_events_enabled(enabled);
+
+            let wall = cuboid(0.05, 10.0, 10.0);
+            let wall = state.colliders.insert(wall.translation(Vector::x() * 2.0));
+
+            // The ball moves by 0.5 per timestep, and reaches the wall during the fourth step.
+            let velocity = Vector::x() * 30.0;
+            let handle = state.bodies.insert(
+                RigidBodyBuilder::dynamic()
+                    .translation(Vector::x() * 0.2)
+                    .linvel(velocity)
+                    .ccd_enabled(true),
+            );
+            let ball = state.colliders.insert_with_parent(
+                ColliderBuilder::ball(0.1),
+                handle,
+                &mut state.bodies,
+            );
+
+            for _ in 0..3 {
+                state.step(&mut pipeline, &Vector::zeros(), &params, &(), &events);
+            }
+            assert!(impact_recv.try_recv().is_err());
+
+            state.step(&mut pipeline, &Vector::zeros(), &params, &(), &events);
+            let impacts: Vec<_> = impact_recv.try_iter().collect();
+            if !enabled {
+                assert!(impacts.is_empty());
+                continue;
+            }
+
+            // The event reports the velocity right before the ball was stopped.
+            assert_eq!(impacts.len(), 1);
+            let impact = impacts[0];
+            let pair = [impact.collider1, impact.collider2];
+            assert!(pair.contains(&wall) && pair.contains(&ball));
+            assert!(impact.toi >= 0.0 && impact.toi <= params.dt);
+            let sign: Real = if impact.collider2 == ball { 1.0 } else { -1.0 };
+            assert!((impact.relative_velocity - velocity * sign).norm() < 1.0e-3);
+            assert!(state.bodies[handle].translation().x < 2.0);
+        }
+    }
+
+    #[test]
+    fn ccd_counters_report_substeps_and_toi_computations() {
+        // The slow ball never moves by more than its thickness, so no TOI is ever computed.
+        for (speed, expect_impact) in [(1.0, false), (30.0, true)] {
+            let (mut state, mut pipeline) = scene();
+            let params = IntegrationParameters {
+                max_ccd_substeps: 2,
+                ..IntegrationParameters::default()
+            };
+
+            let wall = cuboid(0.05, 10.0, 10.0);
+            state.colliders.insert(wall.translation(Vector::x() * 2.0));
+
+            let handle = state.bodies.insert(
+                RigidBodyBuilder::dynamic()
+                    .translation(Vector::x() * 0.2)
+                    .linvel(Vector::x() * speed)
+                    .ccd_enabled(true),
+            );
+            state.colliders.insert_with_parent(
+                ColliderBuilder::ball(0.1),
+                handle,
+                &mut state.bodies,
+            );
+
+            // The fast ball reaches the wall during the fourth step, which gets split in two
+            // substeps by the impact.
+            let mut num_substeps = vec![];
+            let mut num_toi_computations = vec![];
+            for _ in 0..4 {
+                state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+                num_substeps.push(pipeline.counters.ccd.num_substeps);
+                num_toi_computations.push(pipeline.counters.ccd.num_toi_computations);
+            }
+
+            if expect_impact {
+                assert_eq!(num_substeps, [1, 1, 1, 2]);
+                assert!(num_toi_computations[3] > 0);
+            } else {
+                assert_eq!(num_substeps, [1, 1, 1, 1]);
+                assert_eq!(num_toi_computations, [0, 0, 0, 0]);
+            }
+        }
+    }
+
+    #[test]
+    fn joint_force_is_expressed_in_world_space() {
+        use crate::dynamics::PrismaticJointBuilder;
+        use crate::math::{Rotation, UnitVector};
+
+        let (mut state, mut pipeline) = scene();
+        let params = IntegrationParameters::default();
+
+        // A body sliding along a horizontal prismatic joint attached to a rotated fixed body.
+        // The joint’s first frame is rotated by the opposite angle so its axis is along world X.
+        let angle = std::f64::consts::FRAC_PI_4 as Real;
+        #[cfg(feature = "dim2")]
+        let (fixed_rotation, inv_rotation) = (angle, Rotation::new(-angle));
+        #[cfg(feature = "dim3")]
+        let (fixed_rotation, inv_rotation) = (
+            Vector::z() * angle,
+            Rotation::from_scaled_axis(Vector::z() * -angle),
+        );
+        let fixed = state
+            .bodies
+            .insert(RigidBodyBuilder::fixed().rotation(fixed_rotation));
+        let rb = RigidBodyBuilder::dynamic().additional_mass(2.0);
+        let body = state.bodies.insert(rb);
+        let joint = PrismaticJointBuilder::new(Vector::x_axis())
+            .local_axis1(UnitVector::new_normalize(inv_rotation * Vector::x()));
+        let handle = state.impulse_joints.insert(fixed, body, joint, true);
+
+        let gravity = Vector::y() * -9.81;
+        for _ in 0..10 {
+            state.step(&mut pipeline, &gravity, &params, &(), &());
+        }
+
+        // The joint supports the body’s weight along world Y, not along its frame’s axes.
+        let weight = 2.0 * 9.81;
+        let force = state.impulse_joints.get(handle).unwrap().force();
+        assert!((force.y.abs() - weight).abs() < weight * 1.0e-2);
+        assert!(force.x.abs() < weight * 1.0e-2);
+    }
+
+    #[test]
+    fn rope_joint_breaks_above_breaking_force() {
+        use crate::dynamics::{GenericJoint, RopeJointBuilder};
+        use crate::pipeline::ChannelEventCollector;
+        use std::sync::mpsc::channel;
+
+        let (mut state, mut pipeline) = scene();
+        let params = IntegrationParameters::default();
+
+        let (collision_send, _) = channel();
+        let (contact_force_send, _) = channel();
+        let (joint_broken_send, joint_broken_recv) = channel();
+        let events = ChannelEventCollector::new(collision_send, contact_force_send)
+            .with_joint_broken_event_sender(joint_broken_send);
+
+        // Two bodies of mass 1.0 hanging from taut ropes. The rope is only enforced by its
+        // distance limit, so the weight must be read from the limit impulses.
+        let fixed = state.bodies.insert(RigidBodyBuilder::fixed());
+        let mut joint_handles = vec![];
+
+        for breaking_force in [1.0, 100.0] {
+            let rb = RigidBodyBuilder::dynamic()
+                .translation(Vector::y() * -2.0)
+                .additional_mass(1.0);
+            let body = state.bodies.insert(rb);
+            let mut joint: GenericJoint = RopeJointBuilder::new(2.0).into();
+            joint.set_breaking_force(Some(breaking_force));
+            joint_handles.push(state.impulse_joints.insert(fixed, body, joint, true));
+        }
+
+        let gravity = Vector::y() * -9.81;
+        for _ in 0..2 {
+            state.step(&mut pipeline, &gravity, &params, &(), &events);
+        }
+
+        assert!(!state.impulse_joints.contains(joint_handles[0]));
+        let joint = state.impulse_joints.get(joint_handles[1]).unwrap();
+        assert!((joint.force().norm() - 9.81).abs() < 9.81 * 1.0e-1);
+        assert!(joint.force().x.abs() < 1.0e-3);
+
+        let event = joint_broken_recv.try_recv().unwrap();
+        assert_eq!(event.handle, joint_handles[0]);
+        assert!(joint_broken_recv.try_recv().is_err());
+    }
+
+    #[test]
+    fn islands_report_their_sleeping_state() {
+        use crate::dynamics::FixedJointBuilder;
+
+        let (mut state, mut pipeline) = scene();
+        let params = IntegrationParameters {
+            min_island_size: 1,
+            ..IntegrationParameters::default()
+        };
+
+        // Two bodies at rest attached together, and a body moving away from them.
+        let resting: Vec<_> = [0.0, 2.0]
+            .into_iter()
+            .map(|x| {
+                state
+                    .bodies
+                    .insert(RigidBodyBuilder::dynamic().translation(Vector::x() * x))
+            })
+            .collect();
+        state
+            .impulse_joints
+            .insert(resting[0], resting[1], FixedJointBuilder::new(), true);
+        let moving = state.bodies.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::y() * 10.0)
+                .linvel(Vector::y()),
+        );
+
+        let islands = |state: &PhysicsState| {
+            let mut islands: Vec<_> = state
+                .islands
+                .islands()
+                .map(|island| {
+                    let mut bodies = island.bodies.to_vec();
+                    bodies.sort_by_key(|h| h.into_raw_parts());
+                    (bodies, island.sleeping)
+                })
+                .collect();
+            islands.sort_by_key(|(bodies, _)| bodies[0].into_raw_parts());
+            islands
+        };
+
+        for _ in 0..200 {
+            state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+        }
+
+        assert!(state.bodies[resting[0]].is_sleeping());
+        assert_eq!(
+            islands(&state),
+            vec![(resting.clone(), true), (vec![moving], false)]
+        );
+
+        // Waking up one of the sleeping bodies wakes up its whole island.
+        state.islands.wake_up(&mut state.bodies, resting[1], true);
+        state.step(&mut pipeline, &Vector::zeros(), &params, &(), &());
+        assert_eq!(
+            islands(&state),
+            vec![(resting.clone(), false), (vec![moving], false)]
+        );
+    }
+
+    #[test]
+    fn rolling_resistance_uses_its_own_combine_rule() {
+        use crate::dynamics::CoefficientCombineRule;
+
+        let params = IntegrationParameters::default();
+        let gravity = Vector::y() * -9.81;
+        let (radius, speed) = (0.5, 1.0);
+
+        let mut final_speeds = vec![];
+        for rule in [CoefficientCombineRule::Min, CoefficientCombineRule::Max] {
+            let (mut state, mut pipeline) = scene();
+
+            // The friction combine rule must not affect the rolling resistance.
+            let floor = cuboid(50.0, 0.1, 50.0);
+            state.colliders.insert(
+                floor
+                    .rolling_resistance(0.05)
+                    .rolling_resistance_combine_rule(rule)
+                    .friction_combine_rule(CoefficientCombineRule::Max),
+            );
+
+            // A ball already rolling without slipping along the X axis.
+            #[cfg(feature = "dim2")]
+            let angvel = -speed / radius;
+            #[cfg(feature = "dim3")]
+            let angvel = Vector::z() * (-speed / radius);
+            let rb = RigidBodyBuilder::dynamic()
+                .translation(Vector::y() * (0.1 + radius))
+                .linvel(Vector::x() * speed)
+                .angvel(angvel);
+            let handle = state.bodies.insert(rb);
+            state.colliders.insert_with_parent(
+                ColliderBuilder::ball(radius),
+                handle,
+                &mut state.bodies,
+            );
+
+            for _ in 0..30 {
+                state.step(&mut pipeline, &gravity, &params, &(), &());
+            }
+
+            final_speeds.push(state.bodies[handle].linvel().x);
+        }
+
+        // With `Min`, the ball’s zero coefficient wins and it keeps rolling.
+        approx::assert_relative_eq!(final_speeds[0], speed, max_relative = 1.0e-3);
+        assert!(final_speeds[1] < speed - 0.1);
+    }
 }
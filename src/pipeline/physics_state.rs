@@ -0,0 +1,193 @@
+//! Physics state snapshot and restoration.
+
+use crate::dynamics::{
+    CCDSolver, ImpulseJointSet, IntegrationParameters, IslandManager, MultibodyJointSet,
+    RigidBodySet,
+};
+use crate::geometry::{BroadPhaseBvh, ColliderSet, NarrowPhase};
+use crate::math::{Real, Vector};
+use crate::pipeline::{EventHandler, PhysicsHooks, PhysicsPipeline};
+
+/// All the data structures making up the state of a physics simulation.
+///
+/// This groups every set and cache that persists from one timestep to the next: the rigid-bodies,
+/// colliders, joints, the broad-phase pairs, the narrow-phase contact manifolds (including the
+/// impulses used for warmstarting the constraints solver), the active islands, and the CCD solver.
+///
+/// The [`PhysicsPipeline`] is not part of the state. Besides its workspace data, it holds the
+/// settings configured on it, like the gravity field and the force volumes: use the same
+/// pipeline, or configure the new one identically, to continue a simulation from a snapshot.
+///
+/// # Example
+/// ```
+/// # use rapier3d::prelude::*;
+/// let mut state = PhysicsState::new();
+/// let mut pipeline = PhysicsPipeline::new();
+/// let params = IntegrationParameters::default();
+/// let gravity = vector![0.0, -9.81, 0.0];
+///
+/// let snapshot = state.snapshot();
+/// state.step(&mut pipeline, &gravity, &params, &(), &());
+///
+/// // Roll back to the state before the step.
+/// state.restore(&snapshot);
+/// ```
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Default)]
+pub struct PhysicsState {
+    /// The active islands and sleeping state management.
+    pub islands: IslandManager,
+    /// The broad-phase, including the set of collider pairs with intersecting AABBs.
+    pub broad_phase: BroadPhaseBvh,
+    /// The narrow-phase, including all the contact manifolds and their warmstart impulses.
+    pub narrow_phase: NarrowPhase,
+    /// The set of rigid-bodies.
+    pub bodies: RigidBodySet,
+    /// The set of colliders.
+    pub colliders: ColliderSet,
+    /// The set of impulse joints.
+    pub impulse_joints: ImpulseJointSet,
+    /// The set of multibody joints.
+    pub multibody_joints: MultibodyJointSet,
+    /// The CCD solver.
+    pub ccd_solver: CCDSolver,
+}
+
+/// A copy of a [`PhysicsState`] taken with [`PhysicsState::snapshot`].
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone)]
+pub struct PhysicsSnapshot {
+    state: PhysicsState,
+}
+
+impl PhysicsState {
+    /// Creates an empty physics state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advances the simulation stored in this state by one timestep.
+    ///
+    /// This is a shorthand for calling [`PhysicsPipeline::step`] with all the fields of `self`.
+    pub fn step(
+        &mut self,
+        pipeline: &mut PhysicsPipeline,
+        gravity: &Vector<Real>,
+        integration_parameters: &IntegrationParameters,
+        hooks: &dyn PhysicsHooks,
+        events: &dyn EventHandler,
+    ) {
+        pipeline.step(
+            gravity,
+            integration_parameters,
+            &mut self.islands,
+            &mut self.broad_phase,
+            &mut self.narrow_phase,
+            &mut self.bodies,
+            &mut self.colliders,
+            &mut self.impulse_joints,
+            &mut self.multibody_joints,
+            &mut self.ccd_solver,
+            hooks,
+            events,
+        );
+    }
+
+    /// Captures the complete state of the simulation.
+    ///
+    /// Unlike a serialization round-trip, the snapshot keeps the broad-phase pairs, the contact
+    /// manifolds and their warmstart impulses as-is. Restoring it with [`Self::restore`] lets the
+    /// simulation continue exactly as if it had never been modified since the snapshot, without
+    /// any settling frame.
+    ///
+    /// The settings of the [`PhysicsPipeline`] (e.g. the gravity field, force volumes, or world
+    /// bounds) aren’t part of the snapshot.
+    pub fn snapshot(&self) -> PhysicsSnapshot {
+        PhysicsSnapshot {
+            state: self.clone(),
+        }
+    }
+
+    /// Restores the complete state of the simulation from a snapshot.
+    ///
+    /// The same snapshot can be restored any number of times, e.g., for rollback networking.
+    /// The allocations of `self` are reused whenever possible.
+    pub fn restore(&mut self, snapshot: &PhysicsSnapshot) {
+        self.clone_from(&snapshot.state);
+    }
+}
+
+impl PhysicsSnapshot {
+    /// The physics state captured by this snapshot.
+    pub fn state(&self) -> &PhysicsState {
+        &self.state
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PhysicsState;
+    use crate::dynamics::{IntegrationParameters, RigidBodyBuilder};
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Isometry, Real, Vector};
+    use crate::pipeline::PhysicsPipeline;
+
+    #[test]
+    fn restored_snapshot_steps_identically() {
+        let mut state = PhysicsState::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let params = IntegrationParameters::default();
+        let gravity = Vector::y() * -9.81;
+
+        // A pile of boxes on the ground, so that there are many persistent contacts.
+        #[cfg(feature = "dim2")]
+        let ground = ColliderBuilder::cuboid(10.0, 0.5);
+        #[cfg(feature = "dim3")]
+        let ground = ColliderBuilder::cuboid(10.0, 0.5, 10.0);
+        state.colliders.insert(ground);
+
+        let mut handles = vec![];
+        for i in 0..5 {
+            let body = state.bodies.insert(
+                RigidBodyBuilder::dynamic().translation(Vector::y() * (1.0 + i as Real * 0.9)),
+            );
+            #[cfg(feature = "dim2")]
+            let collider = ColliderBuilder::cuboid(0.5, 0.5);
+            #[cfg(feature = "dim3")]
+            let collider = ColliderBuilder::cuboid(0.5, 0.5, 0.5);
+            state
+                .colliders
+                .insert_with_parent(collider, body, &mut state.bodies);
+            handles.push(body);
+        }
+
+        let positions = |state: &PhysicsState| -> Vec<Isometry<Real>> {
+            handles
+                .iter()
+                .map(|h| *state.bodies[*h].position())
+                .collect()
+        };
+
+        for _ in 0..20 {
+            state.step(&mut pipeline, &gravity, &params, &(), &());
+        }
+
+        let snapshot = state.snapshot();
+
+        let mut expected = vec![];
+        for _ in 0..20 {
+            state.step(&mut pipeline, &gravity, &params, &(), &());
+            expected.push(positions(&state));
+        }
+
+        // Restoring the snapshot twice must replay the exact same simulation.
+        for _ in 0..2 {
+            state.restore(&snapshot);
+
+            for expected in &expected {
+                state.step(&mut pipeline, &gravity, &params, &(), &());
+                assert_eq!(&positions(&state), expected);
+            }
+        }
+    }
+}
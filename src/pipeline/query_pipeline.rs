@@ -1,12 +1,12 @@
-use crate::dynamics::RigidBodyHandle;
+use crate::dynamics::{RigidBodyHandle, RigidBodyVelocity, TOIEntry};
 use crate::geometry::{Aabb, Collider, ColliderHandle, PointProjection, Ray, RayIntersection};
 use crate::geometry::{BroadPhaseBvh, InteractionGroups};
-use crate::math::{Isometry, Point, Real, Vector};
+use crate::math::{Isometry, Point, Real, UnitVector, Vector};
 use crate::{dynamics::RigidBodySet, geometry::ColliderSet};
 use parry::bounding_volume::BoundingVolume;
 use parry::partitioning::{Bvh, BvhNode};
 use parry::query::details::{NormalConstraints, ShapeCastOptions};
-use parry::query::{NonlinearRigidMotion, QueryDispatcher, RayCast, ShapeCastHit};
+use parry::query::{NonlinearRigidMotion, QueryDispatcher, RayCast, ShapeCastHit, ShapeCastStatus};
 use parry::shape::{CompositeShape, CompositeShapeRef, FeatureId, Shape, TypedCompositeShape};
 
 /// A query system for performing spatial queries on your physics world (raycasts, shape casts, intersections).
@@ -219,11 +219,46 @@ impl<'a> QueryPipeline<'a> {
         max_toi: Real,
         solid: bool,
     ) -> Option<(ColliderHandle, Real)> {
+        if self.filter.distance_predicate.is_some() {
+            return self
+                .cast_ray_with_distance_predicate(ray, max_toi, solid)
+                .map(|(handle, hit)| (handle, hit.time_of_impact));
+        }
+
         CompositeShapeRef(self)
             .cast_local_ray(ray, max_toi, solid)
             .and_then(|hit| self.id_to_handle(hit))
     }
 
+    /// Casts many rays through the world and returns the first collider each of them hits.
+    ///
+    /// This is equivalent to calling [`Self::cast_ray`] on each ray, with the result for
+    /// `rays[i]` written to `out[i]`.
+    ///
+    /// Note that the rays are cast sequentially: the query filter’s predicates aren’t required
+    /// to be `Sync`, so the query pipeline can’t be shared across threads.
+    ///
+    /// # Panics
+    /// Panics if `rays` and `out` don’t have the same length.
+    #[profiling::function]
+    pub fn cast_rays(
+        &self,
+        rays: &[Ray],
+        max_toi: Real,
+        solid: bool,
+        out: &mut [Option<(ColliderHandle, Real)>],
+    ) {
+        assert_eq!(
+            rays.len(),
+            out.len(),
+            "The output slice must have the same length as the rays slice."
+        );
+
+        for (ray, result) in rays.iter().zip(out.iter_mut()) {
+            *result = self.cast_ray(ray, max_toi, solid);
+        }
+    }
+
     /// Casts a ray and returns detailed information about the hit (including surface normal).
     ///
     /// Like [`cast_ray()`](Self::cast_ray), but returns more information useful for things like:
@@ -258,11 +293,147 @@ impl<'a> QueryPipeline<'a> {
         max_toi: Real,
         solid: bool,
     ) -> Option<(ColliderHandle, RayIntersection)> {
+        if self.filter.distance_predicate.is_some() {
+            return self.cast_ray_with_distance_predicate(ray, max_toi, solid);
+        }
+
         CompositeShapeRef(self)
             .cast_local_ray_and_get_normal(ray, max_toi, solid)
             .and_then(|hit| self.id_to_handle(hit))
     }
 
+    /// Casts a ray through the world and returns the first collider it hits, with additional
+    /// ray-casting options.
+    ///
+    /// This is the same as [`Self::cast_ray_and_get_normal`], with the behavior of the ray-cast
+    /// configured by `options`. See [`RayCastOptions`] for details.
+    ///
+    /// # Example
+    /// ```
+    /// # use rapier3d::prelude::*;
+    /// # let mut bodies = RigidBodySet::new();
+    /// # let mut colliders = ColliderSet::new();
+    /// # let broad_phase = BroadPhaseBvh::new();
+    /// # let narrow_phase = NarrowPhase::new();
+    /// # let query_pipeline = broad_phase.as_query_pipeline(narrow_phase.query_dispatcher(), &bodies, &colliders, QueryFilter::default());
+    /// // Visibility check from inside a room, ignoring the back of the walls.
+    /// let ray = Ray::new(point![0.0, 1.0, 0.0], vector![1.0, 0.0, 0.0]);
+    /// let options = RayCastOptions {
+    ///     cull_back_faces: true,
+    ///     ..Default::default()
+    /// };
+    /// if let Some((handle, hit)) = query_pipeline.cast_ray_with_options(&ray, 100.0, options) {
+    ///     println!("Hit {:?} at distance {}", handle, hit.time_of_impact);
+    /// }
+    /// ```
+    #[profiling::function]
+    pub fn cast_ray_with_options(
+        &self,
+        ray: &Ray,
+        max_toi: Real,
+        options: RayCastOptions,
+    ) -> Option<(ColliderHandle, RayIntersection)> {
+        #[cfg(feature = "dim3")]
+        if options.cull_back_faces {
+            return self.cast_ray_culling_back_faces(ray, max_toi, options.solid);
+        }
+
+        self.cast_ray_and_get_normal(ray, max_toi, options.solid)
+    }
+
+    /// Finds the closest ray hit, ignoring the back faces of triangle meshes and heightfields.
+    ///
+    /// Whenever a back face is hit, the ray is cast again from right after the hit point.
+    #[cfg(feature = "dim3")]
+    fn cast_ray_culling_back_faces(
+        &self,
+        ray: &Ray,
+        max_toi: Real,
+        solid: bool,
+    ) -> Option<(ColliderHandle, RayIntersection)> {
+        let distance_predicate = self.filter.distance_predicate;
+        let mut shifted_ray = *ray;
+        let mut shift = 0.0;
+
+        loop {
+            // The distance predicate must be given distances along the original ray.
+            let current_shift = shift;
+            let shifted_predicate =
+                move |handle: ColliderHandle, collider: &Collider, toi: Real| {
+                    distance_predicate.is_none_or(|f| f(handle, collider, toi + current_shift))
+                };
+            let mut filter = self.filter;
+            if distance_predicate.is_some() {
+                filter.distance_predicate = Some(&shifted_predicate);
+            }
+
+            let pipeline = QueryPipeline { filter, ..*self };
+            let (handle, mut hit) =
+                pipeline.cast_ray_and_get_normal(&shifted_ray, max_toi - shift, solid)?;
+
+            if !self.is_back_face_hit(handle, &shifted_ray.dir, &hit) {
+                hit.time_of_impact += shift;
+                return Some((handle, hit));
+            }
+
+            let skip = hit.time_of_impact + 1.0e-5;
+            shifted_ray.origin += shifted_ray.dir * skip;
+            shift += skip;
+
+            if shift > max_toi {
+                return None;
+            }
+        }
+    }
+
+    /// Does the given ray hit a triangle of a trimesh or heightfield from behind?
+    #[cfg(feature = "dim3")]
+    fn is_back_face_hit(
+        &self,
+        handle: ColliderHandle,
+        ray_dir: &Vector<Real>,
+        hit: &RayIntersection,
+    ) -> bool {
+        let (Some(co), FeatureId::Face(id)) = (self.colliders.get(handle), hit.feature) else {
+            return false;
+        };
+
+        // NOTE: back-face hits may be reported with a face id offset by the number of
+        //       triangles, hence the modulo.
+        let local_normal = if let Some(trimesh) = co.shape().as_trimesh() {
+            trimesh
+                .triangle(id % trimesh.num_triangles() as u32)
+                .scaled_normal()
+        } else if let Some(heightfield) = co.shape().as_heightfield() {
+            let num_triangles = (heightfield.nrows() * heightfield.ncols() * 2) as u32;
+            let Some(triangle) = heightfield.triangle_at_id(id % num_triangles) else {
+                return false;
+            };
+            // The front faces of a heightfield are pointing upward.
+            let normal = triangle.scaled_normal();
+            if normal.y < 0.0 { -normal } else { normal }
+        } else {
+            return false;
+        };
+
+        (co.position() * local_normal).dot(ray_dir) > 0.0
+    }
+
+    /// Finds the closest ray hit accepted by the filter’s distance predicate.
+    ///
+    /// The distance predicate can reject the closest hits, so we can’t rely on the BVH
+    /// traversal pruning subtrees farther than the current best hit.
+    fn cast_ray_with_distance_predicate(
+        &self,
+        ray: &Ray,
+        max_toi: Real,
+        solid: bool,
+    ) -> Option<(ColliderHandle, RayIntersection)> {
+        self.intersect_ray(*ray, max_toi, solid)
+            .min_by(|a, b| a.2.time_of_impact.total_cmp(&b.2.time_of_impact))
+            .map(|(handle, _, hit)| (handle, hit))
+    }
+
     /// Returns ALL colliders that a ray passes through (not just the first).
     ///
     /// Unlike [`cast_ray()`](Self::cast_ray) which stops at the first hit, this returns
@@ -305,7 +476,12 @@ impl<'a> QueryPipeline<'a> {
                         co.shape
                             .cast_ray_and_get_normal(co.position(), &ray, max_toi, solid)
                     {
-                        return Some((co_handle, co, intersection));
+                        if self
+                            .filter
+                            .test_distance(co_handle, co, intersection.time_of_impact)
+                        {
+                            return Some((co_handle, co, intersection));
+                        }
                     }
                 }
 
@@ -400,7 +576,9 @@ impl<'a> QueryPipeline<'a> {
 
     /// Find the projection of a point on the closest collider.
     ///
-    /// The results include the ID of the feature hit by the point.
+    /// The results include the ID of the feature hit by the point. Feature IDs are reported
+    /// by shapes implementing parry’s feature-aware point projection, like cuboids, convex
+    /// polyhedra, and triangle meshes. Other shapes report [`FeatureId::Unknown`].
     ///
     /// # Parameters
     /// * `point` - The point to project.
@@ -491,8 +669,138 @@ impl<'a> QueryPipeline<'a> {
             .and_then(|hit| self.id_to_handle(hit))
     }
 
+    /// Same as [`Self::cast_shape`], but also computes the penetration depth if the shape is
+    /// already penetrating the collider it hits at its starting position.
+    ///
+    /// If the hit has the [`ShapeCastStatus::PenetratingOrWithinTargetDist`] status (for example
+    /// with `options.stop_at_penetration` set to `true`), its time of impact is zero and doesn’t
+    /// tell how to resolve the initial overlap. In that case, the penetration of the shape at
+    /// `shape_pos` is computed and returned alongside the hit. It is `None` if the shape isn’t
+    /// actually penetrating (it is only within `options.target_distance`), or if the
+    /// penetration can’t be computed for this pair of shapes (e.g. for composite shapes).
+    ///
+    /// # Example
+    /// ```
+    /// # use rapier3d::prelude::*;
+    /// # use rapier3d::parry::{query::ShapeCastOptions, shape::Ball};
+    /// # let mut bodies = RigidBodySet::new();
+    /// # let mut colliders = ColliderSet::new();
+    /// # let narrow_phase = NarrowPhase::new();
+    /// # let broad_phase = BroadPhaseBvh::new();
+    /// # let query_pipeline = broad_phase.as_query_pipeline(narrow_phase.query_dispatcher(), &bodies, &colliders, QueryFilter::default());
+    /// let shape = Ball::new(0.5);
+    /// let mut spawn_pos = Isometry::translation(0.0, 0.2, 0.0);
+    /// let options = ShapeCastOptions {
+    ///     stop_at_penetration: true,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// // Push a newly-spawned object out of the geometry it overlaps.
+    /// if let Some((_, _, Some(penetration))) =
+    ///     query_pipeline.cast_shape_with_penetration(&spawn_pos, &Vector::zeros(), &shape, options)
+    /// {
+    ///     spawn_pos.translation.vector += penetration.separating_translation();
+    /// }
+    /// ```
+    #[profiling::function]
+    pub fn cast_shape_with_penetration(
+        &self,
+        shape_pos: &Isometry<Real>,
+        shape_vel: &Vector<Real>,
+        shape: &dyn Shape,
+        options: ShapeCastOptions,
+    ) -> Option<(ColliderHandle, ShapeCastHit, Option<ShapeCastPenetration>)> {
+        let (handle, hit) = self.cast_shape(shape_pos, shape_vel, shape, options)?;
+
+        if hit.status != ShapeCastStatus::PenetratingOrWithinTargetDist {
+            return Some((handle, hit, None));
+        }
+
+        let penetration = self.colliders.get(handle).and_then(|co| {
+            let pos12 = co.position().inv_mul(shape_pos);
+            let contact = self
+                .dispatcher
+                .contact(&pos12, co.shape(), shape, 0.0)
+                .ok()??;
+            (contact.dist < 0.0).then(|| ShapeCastPenetration {
+                depth: -contact.dist,
+                normal: co.position() * contact.normal1,
+            })
+        });
+
+        Some((handle, hit, penetration))
+    }
+
+    /// Sweeps a shape through the world and reports every collider it would hit.
+    ///
+    /// Unlike [`Self::cast_shape`] which only returns the first impact, this reports all the
+    /// colliders touched by the shape along its sweep (up to `options.max_time_of_impact`).
+    /// The hits are given to `callback` in non-decreasing time-of-impact order. The sweep
+    /// stops as soon as `callback` returns `false`.
+    ///
+    /// Similarly to [`Self::cast_shape`], witness and normal 1 of each hit refer to the
+    /// collider that was hit and are expressed in world space. Use the query filter to
+    /// exclude the caster’s own collider if needed.
+    ///
+    /// # Parameters
+    /// * `shape_pos` - Starting position/orientation of the shape
+    /// * `shape_vel` - Direction and speed to move the shape (velocity vector)
+    /// * `shape` - The shape to sweep (ball, cuboid, capsule, etc.)
+    /// * `options` - Maximum distance, collision filtering, etc.
+    /// * `callback` - Called on each hit; return `false` to stop the sweep.
+    #[profiling::function]
+    pub fn cast_shape_all(
+        &self,
+        shape_pos: &Isometry<Real>,
+        shape_vel: &Vector<Real>,
+        shape: &dyn Shape,
+        options: ShapeCastOptions,
+        mut callback: impl FnMut(ColliderHandle, ShapeCastHit) -> bool,
+    ) {
+        let end_pos = Isometry::from_parts(
+            (shape_pos.translation.vector + shape_vel * options.max_time_of_impact).into(),
+            shape_pos.rotation,
+        );
+        let swept_aabb = shape.compute_swept_aabb(shape_pos, &end_pos);
+
+        let mut hits: Vec<_> = self
+            .bvh
+            .leaves(|node: &BvhNode| node.aabb().intersects(&swept_aabb))
+            .filter_map(|leaf| {
+                let (co, co_handle) = self.colliders.get_unknown_gen(leaf)?;
+                if !self.filter.test(self.bodies, co_handle, co) {
+                    return None;
+                }
+
+                let pos12 = co.position().inv_mul(shape_pos);
+                let vel12 = co.position().inverse_transform_vector(shape_vel);
+                let hit = self
+                    .dispatcher
+                    .cast_shapes(&pos12, &vel12, co.shape(), shape, options)
+                    .ok()??;
+                if !self.filter.test_distance(co_handle, co, hit.time_of_impact) {
+                    return None;
+                }
+                Some((co_handle, hit.transform1_by(co.position())))
+            })
+            .collect();
+
+        hits.sort_by(|a, b| a.1.time_of_impact.total_cmp(&b.1.time_of_impact));
+
+        for (handle, hit) in hits {
+            if !callback(handle, hit) {
+                break;
+            }
+        }
+    }
+
     /// Casts a shape with an arbitrary continuous motion and retrieve the first collider it hits.
     ///
+    /// The motion combines a translation and a rotation, e.g., to preview the trajectory of a
+    /// spinning projectile. The colliders it may hit are found from the broad-phase using the
+    /// bounding volume swept by the shape, and the time of impact is computed the same way as
+    /// the CCD solver does. The other colliders are assumed to stay at their current positions.
+    ///
     /// In the resulting `TOI`, witness and normal 1 refer to the world collider, and are in world
     /// space.
     ///
@@ -530,6 +838,121 @@ impl<'a> QueryPipeline<'a> {
             .and_then(|hit| self.id_to_handle(hit))
     }
 
+    /// Predicts the first collider hit by `collider` if it moved with the given velocity for `dt`.
+    ///
+    /// This doesn’t step the simulation: it runs the same time-of-impact computation as the CCD
+    /// solver, using a nonlinear shape-cast when it is supported for the pair of shapes and
+    /// falling back to a linear shape-cast (ignoring the angular velocity) otherwise. The
+    /// velocity is the one of the collider’s parent rigid-body (rotating around its center of
+    /// mass) or, if the collider has no parent, of the collider itself.
+    ///
+    /// The other colliders are assumed to stay at their current positions. `collider` itself,
+    /// and all the colliders excluded by [`Self::filter`], are ignored. Similarly to CCD, a
+    /// collider already penetrating `collider` isn’t reported if their motions are separating,
+    /// unless one of them is a sensor, and one-way platforms are only hit from their allowed side.
+    ///
+    /// Returns the handle of the first collider hit and the time of impact, in `[0, dt]`.
+    ///
+    /// # Example
+    /// ```
+    /// # use rapier3d::prelude::*;
+    /// # let mut bodies = RigidBodySet::new();
+    /// # let mut colliders = ColliderSet::new();
+    /// # let broad_phase = BroadPhaseBvh::new();
+    /// # let narrow_phase = NarrowPhase::new();
+    /// # let player = colliders.insert(ColliderBuilder::ball(0.5));
+    /// # let query_pipeline = broad_phase.as_query_pipeline(narrow_phase.query_dispatcher(), &bodies, &colliders, QueryFilter::default());
+    /// let velocity = RigidBodyVelocity::new(vector![100.0, 0.0, 0.0], Vector::zeros());
+    /// if let Some((hit, toi)) = query_pipeline.predict_collision(player, &velocity, 1.0 / 60.0) {
+    ///     println!("Moving the player would hit {:?} after {} seconds", hit, toi);
+    /// }
+    /// ```
+    #[profiling::function]
+    pub fn predict_collision(
+        &self,
+        collider: ColliderHandle,
+        velocity: &RigidBodyVelocity<Real>,
+        dt: Real,
+    ) -> Option<(ColliderHandle, Real)> {
+        let co1 = self.colliders.get(collider)?;
+        let rb1 = co1.parent().and_then(|h| self.bodies.get(h));
+        let motion1 = match (rb1, co1.position_wrt_parent()) {
+            (Some(rb1), Some(pos_wrt_parent)) => NonlinearRigidMotion::new(
+                *rb1.position(),
+                *rb1.local_center_of_mass(),
+                velocity.linvel,
+                velocity.angvel,
+            )
+            .prepend(*pos_wrt_parent),
+            _ => NonlinearRigidMotion::new(
+                *co1.position(),
+                Point::origin(),
+                velocity.linvel,
+                velocity.angvel,
+            ),
+        };
+
+        // The AABB swept by the collider, enlarged to account for its rotation.
+        let start_pos = motion1.position_at_time(0.0);
+        let sphere = co1.shape().compute_bounding_sphere(&start_pos);
+        let rotation_radius =
+            na::distance(sphere.center(), &(motion1.start * motion1.local_center))
+                + sphere.radius();
+        #[cfg(feature = "dim2")]
+        let rotation_angle = velocity.angvel.abs() * dt;
+        #[cfg(feature = "dim3")]
+        let rotation_angle = velocity.angvel.norm() * dt;
+        let swept_aabb = co1
+            .shape()
+            .compute_swept_aabb(&start_pos, &motion1.position_at_time(dt))
+            .loosened(rotation_radius * rotation_angle.min(2.0));
+
+        let mut result: Option<(ColliderHandle, Real)> = None;
+
+        for leaf in self
+            .bvh
+            .leaves(|node: &BvhNode| node.aabb().intersects(&swept_aabb))
+        {
+            let Some((co2, ch2)) = self.colliders.get_unknown_gen(leaf) else {
+                continue;
+            };
+            if ch2 == collider || !self.filter.test(self.bodies, ch2, co2) {
+                continue;
+            }
+
+            let rb2 = co2.parent().and_then(|h| self.bodies.get(h));
+            let stop_at_penetration = co1.is_sensor()
+                || co2.is_sensor()
+                || !co1.solver_groups().test(co2.solver_groups())
+                || TOIEntry::is_kinematic_pair(rb1, rb2);
+            let end_time = result.map(|(_, toi)| toi).unwrap_or(dt);
+            let motion2 = NonlinearRigidMotion::constant_position(*co2.position());
+
+            if let Some(hit) = TOIEntry::cast_colliders(
+                self.dispatcher,
+                collider,
+                ch2,
+                co1,
+                co2,
+                &motion1,
+                &motion2,
+                -velocity.linvel,
+                0.0,
+                end_time,
+                stop_at_penetration,
+                1.0,
+            ) {
+                if self.filter.test_distance(ch2, co2, hit.time_of_impact)
+                    && result.is_none_or(|(_, toi)| hit.time_of_impact < toi)
+                {
+                    result = Some((ch2, hit.time_of_impact));
+                }
+            }
+        }
+
+        result
+    }
+
     /// Retrieve all the colliders intersecting the given shape.
     ///
     /// # Parameters
@@ -559,6 +982,41 @@ impl<'a> QueryPipeline<'a> {
     }
 }
 
+/// The initial penetration of a shape-cast, computed by
+/// [`QueryPipeline::cast_shape_with_penetration`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ShapeCastPenetration {
+    /// The penetration depth between the cast shape and the collider it hits.
+    pub depth: Real,
+    /// The penetration direction, in world-space.
+    ///
+    /// This points outward the collider hit, i.e., it is the direction the cast shape must be
+    /// moved toward to resolve the penetration.
+    pub normal: UnitVector<Real>,
+}
+
+impl ShapeCastPenetration {
+    /// The smallest translation of the cast shape resolving the penetration.
+    pub fn separating_translation(&self) -> Vector<Real> {
+        *self.normal * self.depth
+    }
+}
+
+/// Options for [`QueryPipeline::cast_ray_with_options`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RayCastOptions {
+    /// If `true`, a ray starting inside of a shape hits it at its origin. If `false`, the ray
+    /// passes through the shape from the inside until it exits it.
+    pub solid: bool,
+    /// If `true`, triangles of triangle meshes and heightfields hit from behind are ignored.
+    ///
+    /// The front face of a trimesh triangle is determined by the winding of its vertices
+    /// (counter-clockwise), and the front faces of a heightfield are pointing upward. This is
+    /// useful for visibility checks from inside of closed meshes. Disabled by default.
+    #[cfg(feature = "dim3")]
+    pub cull_back_faces: bool,
+}
+
 bitflags::bitflags! {
     #[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
     /// Flags for filtering spatial queries by body type or sensor status.
@@ -658,6 +1116,10 @@ impl QueryFilterFlags {
 ///         // Only hit colliders with user_data > 100
 ///         collider.user_data > 100
 ///     });
+///
+/// // Only hit colliders with the first bit of their user_data set
+/// let filter = QueryFilter::default()
+///     .user_data_predicate(&|user_data| user_data & 1 != 0);
 /// ```
 #[derive(Copy, Clone, Default)]
 pub struct QueryFilter<'a> {
@@ -672,6 +1134,20 @@ pub struct QueryFilter<'a> {
     /// Custom filtering function - collider included only if this returns `true`.
     #[allow(clippy::type_complexity)]
     pub predicate: Option<&'a dyn Fn(ColliderHandle, &Collider) -> bool>,
+    /// Custom filtering function on the [`Collider::user_data`] of the colliders - collider
+    /// included only if this returns `true`.
+    ///
+    /// This is tested before any other filter, so it is a cheap way of rejecting colliders
+    /// based on, e.g., bitmasks stored in their user-data.
+    pub user_data_predicate: Option<&'a dyn Fn(u128) -> bool>,
+    /// Custom filtering function receiving the time-of-impact (or distance) of the hit being
+    /// considered - the hit is accepted only if this returns `true`.
+    ///
+    /// This is applied by ray-casts and by [`QueryPipeline::cast_shape_all`], after the hit is
+    /// computed but before it is accepted. A collider with a rejected hit is ignored entirely
+    /// by the query.
+    #[allow(clippy::type_complexity)]
+    pub distance_predicate: Option<&'a dyn Fn(ColliderHandle, &Collider, Real) -> bool>,
 }
 
 impl QueryFilter<'_> {
@@ -679,7 +1155,9 @@ impl QueryFilter<'_> {
     /// included in a scene query (`true`) or not (`false`).
     #[inline]
     pub fn test(&self, bodies: &RigidBodySet, handle: ColliderHandle, collider: &Collider) -> bool {
-        self.exclude_collider != Some(handle)
+        self.user_data_predicate
+            .is_none_or(|f| f(collider.user_data))
+            && self.exclude_collider != Some(handle)
             && (self.exclude_rigid_body.is_none() // NOTE: deal with the `None` case separately otherwise the next test is incorrect if the collider’s parent is `None` too.
             || self.exclude_rigid_body != collider.parent.map(|p| p.handle))
             && self
@@ -689,6 +1167,15 @@ impl QueryFilter<'_> {
             && self.flags.test(bodies, collider)
             && self.predicate.map(|f| f(handle, collider)).unwrap_or(true)
     }
+
+    /// Applies the distance predicate of `self` to a hit at the given time-of-impact (or distance)
+    /// to determine if it has to be accepted by a scene query (`true`) or not (`false`).
+    #[inline]
+    pub fn test_distance(&self, handle: ColliderHandle, collider: &Collider, toi: Real) -> bool {
+        self.distance_predicate
+            .map(|f| f(handle, collider, toi))
+            .unwrap_or(true)
+    }
 }
 
 impl From<QueryFilterFlags> for QueryFilter<'_> {
@@ -782,4 +1269,489 @@ impl<'a> QueryFilter<'a> {
         self.predicate = Some(predicate);
         self
     }
+
+    /// Set the predicate to apply a custom filtering on the colliders’ user-data during the
+    /// scene query.
+    pub fn user_data_predicate(mut self, predicate: &'a impl Fn(u128) -> bool) -> Self {
+        self.user_data_predicate = Some(predicate);
+        self
+    }
+
+    /// Set the predicate to reject hits based on their time-of-impact (or distance) during the
+    /// scene query.
+    pub fn distance_predicate(
+        mut self,
+        predicate: &'a impl Fn(ColliderHandle, &Collider, Real) -> bool,
+    ) -> Self {
+        self.distance_predicate = Some(predicate);
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::QueryFilter;
+    use crate::dynamics::{IntegrationParameters, RigidBodyBuilder, RigidBodyVelocity};
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Real, Vector};
+    use crate::pipeline::{PhysicsPipeline, PhysicsState};
+
+    #[test]
+    fn predict_collision_finds_earliest_hit() {
+        let mut state = PhysicsState::new();
+        let mut pipeline = PhysicsPipeline::new();
+
+        #[cfg(feature = "dim2")]
+        let wall = ColliderBuilder::cuboid(0.5, 10.0);
+        #[cfg(feature = "dim3")]
+        let wall = ColliderBuilder::cuboid(0.5, 10.0, 10.0);
+        let near_wall = state
+            .colliders
+            .insert(wall.clone().translation(Vector::x() * 5.0));
+        let _far_wall = state.colliders.insert(wall.translation(Vector::x() * 10.0));
+        let body = state
+            .bodies
+            .insert(RigidBodyBuilder::kinematic_velocity_based());
+        let player =
+            state
+                .colliders
+                .insert_with_parent(ColliderBuilder::ball(0.5), body, &mut state.bodies);
+
+        // Populate the broad-phase.
+        state.step(
+            &mut pipeline,
+            &Vector::zeros(),
+            &IntegrationParameters::default(),
+            &(),
+            &(),
+        );
+
+        let query_pipeline = state.broad_phase.as_query_pipeline(
+            state.narrow_phase.query_dispatcher(),
+            &state.bodies,
+            &state.colliders,
+            QueryFilter::default(),
+        );
+        let velocity = RigidBodyVelocity::new(Vector::x() * 10.0, na::zero());
+
+        // The ball touches the near wall after moving by 5 - 0.5 - 0.5 = 4.
+        let (hit, toi) = query_pipeline
+            .predict_collision(player, &velocity, 1.0)
+            .unwrap();
+        assert_eq!(hit, near_wall);
+        assert!((toi - 0.4).abs() < 1.0e-3 as Real);
+
+        // Nothing is reached if the motion is too short or going away.
+        assert!(
+            query_pipeline
+                .predict_collision(player, &velocity, 0.3)
+                .is_none()
+        );
+        assert!(
+            query_pipeline
+                .predict_collision(
+                    player,
+                    &RigidBodyVelocity::new(-velocity.linvel, na::zero()),
+                    1.0
+                )
+                .is_none()
+        );
+
+        // The filter excludes colliders from the prediction.
+        let (hit, _) = query_pipeline
+            .with_filter(QueryFilter::new().exclude_collider(near_wall))
+            .predict_collision(player, &velocity, 2.0)
+            .unwrap();
+        assert_ne!(hit, near_wall);
+    }
+
+    #[cfg(feature = "dim3")]
+    #[test]
+    fn cast_ray_culling_back_faces() {
+        use super::RayCastOptions;
+        use crate::geometry::{Cuboid, Ray};
+        use crate::math::Point;
+
+        let mut state = PhysicsState::new();
+        let (vertices, indices) = Cuboid::new(Vector::repeat(1.0)).to_trimesh();
+        let room = state
+            .colliders
+            .insert(ColliderBuilder::trimesh(vertices, indices).unwrap());
+        state.step(
+            &mut PhysicsPipeline::new(),
+            &Vector::zeros(),
+            &IntegrationParameters::default(),
+            &(),
+            &(),
+        );
+
+        let query_pipeline = state.broad_phase.as_query_pipeline(
+            state.narrow_phase.query_dispatcher(),
+            &state.bodies,
+            &state.colliders,
+            QueryFilter::default(),
+        );
+        let culling = RayCastOptions {
+            cull_back_faces: true,
+            ..Default::default()
+        };
+
+        // From the inside, the walls are only hit if back faces aren’t culled.
+        let ray = Ray::new(Point::origin(), Vector::x());
+        let (hit, inter) = query_pipeline
+            .cast_ray_with_options(&ray, 10.0, RayCastOptions::default())
+            .unwrap();
+        assert_eq!(hit, room);
+        assert!((inter.time_of_impact - 1.0).abs() < 1.0e-5);
+        assert!(
+            query_pipeline
+                .cast_ray_with_options(&ray, 10.0, culling)
+                .is_none()
+        );
+
+        // From the outside, the near face is hit either way.
+        let ray = Ray::new(Point::origin() - Vector::x() * 3.0, Vector::x());
+        let (hit, inter) = query_pipeline
+            .cast_ray_with_options(&ray, 10.0, culling)
+            .unwrap();
+        assert_eq!(hit, room);
+        assert!((inter.time_of_impact - 2.0).abs() < 1.0e-5);
+    }
+
+    #[test]
+    fn cast_shape_initial_penetration() {
+        use crate::geometry::Ball;
+        use crate::math::{Isometry, Real};
+        use parry::query::ShapeCastOptions;
+
+        let mut state = PhysicsState::new();
+        #[cfg(feature = "dim2")]
+        let ground = ColliderBuilder::cuboid(10.0, 0.5);
+        #[cfg(feature = "dim3")]
+        let ground = ColliderBuilder::cuboid(10.0, 0.5, 10.0);
+        let ground = state
+            .colliders
+            .insert(ground.translation(-Vector::y() * 0.5));
+        state.step(
+            &mut PhysicsPipeline::new(),
+            &Vector::zeros(),
+            &IntegrationParameters::default(),
+            &(),
+            &(),
+        );
+
+        let query_pipeline = state.broad_phase.as_query_pipeline(
+            state.narrow_phase.query_dispatcher(),
+            &state.bodies,
+            &state.colliders,
+            QueryFilter::default(),
+        );
+        let options = ShapeCastOptions {
+            stop_at_penetration: true,
+            ..Default::default()
+        };
+        let shape = Ball::new(0.5);
+
+        // A ball sinking by 0.2 into the ground.
+        let pos = Isometry::new(Vector::y() * 0.3, na::zero());
+        let (hit, cast_hit, penetration) = query_pipeline
+            .cast_shape_with_penetration(&pos, &Vector::x(), &shape, options)
+            .unwrap();
+        let penetration = penetration.unwrap();
+        assert_eq!(hit, ground);
+        assert_eq!(cast_hit.time_of_impact, 0.0);
+        assert!((penetration.depth - 0.2).abs() < 1.0e-5 as Real);
+        assert!((penetration.separating_translation() - Vector::y() * 0.2).norm() < 1.0e-5);
+
+        // No penetration is reported for regular hits.
+        let pos = Isometry::new(Vector::y() * 1.3, na::zero());
+        let (_, _, penetration) = query_pipeline
+            .cast_shape_with_penetration(&pos, &-Vector::y(), &shape, options)
+            .unwrap();
+        assert!(penetration.is_none());
+    }
+
+    #[test]
+    fn user_data_predicate_filters_ray_casts() {
+        use crate::geometry::Ray;
+        use crate::math::Point;
+
+        let mut state = PhysicsState::new();
+        let balls: Vec<_> = (0..3)
+            .map(|i| {
+                state.colliders.insert(
+                    ColliderBuilder::ball(0.5)
+                        .translation(Vector::x() * (i + 1) as Real * 2.0)
+                        .user_data(1 << i),
+                )
+            })
+            .collect();
+        state.step(
+            &mut PhysicsPipeline::new(),
+            &Vector::zeros(),
+            &IntegrationParameters::default(),
+            &(),
+            &(),
+        );
+
+        let skip_first = |user_data: u128| user_data & 0b1 == 0;
+        let query_pipeline = state.broad_phase.as_query_pipeline(
+            state.narrow_phase.query_dispatcher(),
+            &state.bodies,
+            &state.colliders,
+            QueryFilter::new().user_data_predicate(&skip_first),
+        );
+        let ray = Ray::new(Point::origin(), Vector::x());
+        let (hit, toi) = query_pipeline.cast_ray(&ray, 10.0, true).unwrap();
+        assert_eq!(hit, balls[1]);
+        assert!((toi - 3.5).abs() < 1.0e-5);
+
+        let mut hits: Vec<_> = query_pipeline
+            .intersect_ray(ray, 10.0, true)
+            .map(|(handle, _, _)| handle)
+            .collect();
+        hits.sort_by_key(|h| h.into_raw_parts());
+        assert_eq!(hits, &balls[1..]);
+    }
+
+    #[test]
+    fn cast_shape_nonlinear_hits_with_rotation_only() {
+        use crate::geometry::Cuboid;
+        use crate::math::{Isometry, Point};
+        use parry::query::{NonlinearRigidMotion, ShapeCastOptions};
+
+        let mut state = PhysicsState::new();
+        let ball = state
+            .colliders
+            .insert(ColliderBuilder::ball(0.2).translation(Vector::y() * 1.5));
+        state.step(
+            &mut PhysicsPipeline::new(),
+            &Vector::zeros(),
+            &IntegrationParameters::default(),
+            &(),
+            &(),
+        );
+
+        let query_pipeline = state.broad_phase.as_query_pipeline(
+            state.narrow_phase.query_dispatcher(),
+            &state.bodies,
+            &state.colliders,
+            QueryFilter::default(),
+        );
+
+        // A long bar spinning around its center at a quarter turn per second.
+        let bar = Cuboid::new(Vector::x() * 2.0 + Vector::repeat(0.05));
+        #[cfg(feature = "dim2")]
+        let angvel = std::f64::consts::FRAC_PI_2 as Real;
+        #[cfg(feature = "dim3")]
+        let angvel = Vector::z() * std::f64::consts::FRAC_PI_2 as Real;
+        let motion = NonlinearRigidMotion::new(
+            Isometry::identity(),
+            Point::origin(),
+            Vector::zeros(),
+            angvel,
+        );
+
+        // A linear shape-cast doesn’t see the bar’s rotation.
+        assert!(
+            query_pipeline
+                .cast_shape(
+                    &Isometry::identity(),
+                    &Vector::zeros(),
+                    &bar,
+                    ShapeCastOptions::with_max_time_of_impact(1.0),
+                )
+                .is_none()
+        );
+
+        let (hit, cast_hit) = query_pipeline
+            .cast_shape_nonlinear(&motion, &bar, 0.0, 1.0, true)
+            .unwrap();
+        assert_eq!(hit, ball);
+        assert!(cast_hit.time_of_impact > 0.5 && cast_hit.time_of_impact < 1.0);
+
+        // The impact is out of reach over a shorter time interval.
+        assert!(
+            query_pipeline
+                .cast_shape_nonlinear(&motion, &bar, 0.0, 0.5, true)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn cast_shape_all_reports_every_hit_in_order() {
+        use crate::geometry::Ball;
+        use crate::math::Isometry;
+        use parry::query::ShapeCastOptions;
+
+        let mut state = PhysicsState::new();
+        // Inserted out of order so the hits don’t come sorted from the BVH by accident.
+        let balls: Vec<_> = [3.0, 1.0, 2.0]
+            .into_iter()
+            .map(|i: Real| {
+                state
+                    .colliders
+                    .insert(ColliderBuilder::ball(0.5).translation(Vector::x() * i * 2.0))
+            })
+            .collect();
+        state.step(
+            &mut PhysicsPipeline::new(),
+            &Vector::zeros(),
+            &IntegrationParameters::default(),
+            &(),
+            &(),
+        );
+
+        let query_pipeline = state.broad_phase.as_query_pipeline(
+            state.narrow_phase.query_dispatcher(),
+            &state.bodies,
+            &state.colliders,
+            QueryFilter::default(),
+        );
+        let shape = Ball::new(0.25);
+        let options = ShapeCastOptions::with_max_time_of_impact(10.0);
+
+        let mut hits = vec![];
+        query_pipeline.cast_shape_all(
+            &Isometry::identity(),
+            &Vector::x(),
+            &shape,
+            options,
+            |handle, hit| {
+                hits.push((handle, hit.time_of_impact));
+                true
+            },
+        );
+        assert_eq!(hits.len(), 3);
+        let expected = [(balls[1], 1.25), (balls[2], 3.25), (balls[0], 5.25)];
+        for ((handle, toi), (expected_handle, expected_toi)) in hits.iter().zip(expected) {
+            assert_eq!(*handle, expected_handle);
+            assert!((*toi - expected_toi).abs() < 1.0e-3 as Real);
+        }
+
+        // The sweep stops as soon as the callback returns `false`.
+        let mut num_hits = 0;
+        query_pipeline.cast_shape_all(
+            &Isometry::identity(),
+            &Vector::x(),
+            &shape,
+            options,
+            |_, _| {
+                num_hits += 1;
+                false
+            },
+        );
+        assert_eq!(num_hits, 1);
+
+        // Hits beyond the max time of impact or excluded by the filter aren’t reported.
+        let mut hits = vec![];
+        query_pipeline
+            .with_filter(QueryFilter::new().exclude_collider(balls[1]))
+            .cast_shape_all(
+                &Isometry::identity(),
+                &Vector::x(),
+                &shape,
+                ShapeCastOptions::with_max_time_of_impact(4.0),
+                |handle, _| {
+                    hits.push(handle);
+                    true
+                },
+            );
+        assert_eq!(hits, [balls[2]]);
+    }
+
+    #[test]
+    fn distance_predicate_rejects_close_ray_hits() {
+        use crate::geometry::{Collider, ColliderHandle, Ray};
+        use crate::math::Point;
+
+        let mut state = PhysicsState::new();
+        let balls: Vec<_> = (0..3)
+            .map(|i| {
+                state.colliders.insert(
+                    ColliderBuilder::ball(0.5).translation(Vector::x() * (i + 1) as Real * 2.0),
+                )
+            })
+            .collect();
+        state.step(
+            &mut PhysicsPipeline::new(),
+            &Vector::zeros(),
+            &IntegrationParameters::default(),
+            &(),
+            &(),
+        );
+
+        // Skip everything closer than a “muzzle offset” of 2.
+        let beyond_muzzle = |_: ColliderHandle, _: &Collider, toi: Real| toi >= 2.0;
+        let query_pipeline = state.broad_phase.as_query_pipeline(
+            state.narrow_phase.query_dispatcher(),
+            &state.bodies,
+            &state.colliders,
+            QueryFilter::new().distance_predicate(&beyond_muzzle),
+        );
+        let ray = Ray::new(Point::origin(), Vector::x());
+
+        let (hit, toi) = query_pipeline.cast_ray(&ray, 10.0, true).unwrap();
+        assert_eq!(hit, balls[1]);
+        assert!((toi - 3.5).abs() < 1.0e-5);
+
+        let (hit, inter) = query_pipeline
+            .cast_ray_and_get_normal(&ray, 10.0, true)
+            .unwrap();
+        assert_eq!(hit, balls[1]);
+        assert!((inter.normal + Vector::x()).norm() < 1.0e-5);
+
+        let mut hits: Vec<_> = query_pipeline
+            .intersect_ray(ray, 10.0, true)
+            .map(|(handle, _, _)| handle)
+            .collect();
+        hits.sort_by_key(|h| h.into_raw_parts());
+        assert_eq!(hits, &balls[1..]);
+
+        // Nothing is hit if all the hits within the max time of impact are rejected.
+        assert!(query_pipeline.cast_ray(&ray, 3.0, true).is_none());
+    }
+
+    #[test]
+    fn cast_rays_matches_individual_ray_casts() {
+        use crate::geometry::Ray;
+        use crate::math::Point;
+
+        let mut state = PhysicsState::new();
+        let ball = state
+            .colliders
+            .insert(ColliderBuilder::ball(0.5).translation(Vector::x() * 2.0));
+        state.step(
+            &mut PhysicsPipeline::new(),
+            &Vector::zeros(),
+            &IntegrationParameters::default(),
+            &(),
+            &(),
+        );
+
+        let query_pipeline = state.broad_phase.as_query_pipeline(
+            state.narrow_phase.query_dispatcher(),
+            &state.bodies,
+            &state.colliders,
+            QueryFilter::default(),
+        );
+        let rays = [
+            Ray::new(Point::origin(), Vector::x()),
+            Ray::new(Point::origin(), -Vector::x()),
+            Ray::new(Point::origin() - Vector::x(), Vector::x()),
+        ];
+        let mut out = [Some((ball, -1.0)); 3];
+        query_pipeline.cast_rays(&rays, 10.0, true, &mut out);
+
+        // Results are index-parallel with the rays, and misses overwrite the previous content.
+        assert_eq!(out[0].map(|hit| hit.0), Some(ball));
+        assert!((out[0].unwrap().1 - 1.5).abs() < 1.0e-5);
+        assert!(out[1].is_none());
+        assert_eq!(out[2].map(|hit| hit.0), Some(ball));
+        assert!((out[2].unwrap().1 - 2.5).abs() < 1.0e-5);
+
+        for (ray, result) in rays.iter().zip(out) {
+            assert_eq!(query_pipeline.cast_ray(ray, 10.0, true), result);
+        }
+    }
 }